@@ -0,0 +1,79 @@
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A backend that can be used to simulate user inputs.
+///
+/// Not all backends are available on every platform, and most of them must be enabled through
+/// the cargo feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// The X11 window manager, through `libX11` and `libXtst` (`x11` feature).
+    X11,
+    /// The X11 window manager, through the pure-Rust `x11rb` crate (`x11rb` feature).
+    X11rb,
+    /// A Wayland compositor implementing the virtual keyboard and pointer protocols (`wayland`
+    /// feature).
+    Wayland,
+    /// The XDG RemoteDesktop portal (`portal` feature).
+    Portal,
+    /// A `uinput` virtual device (`uinput` feature).
+    Uinput,
+    /// The `SendInput` function of the Windows operating system.
+    Windows,
+}
+
+impl Backend {
+    /// All the backends, in no particular order.
+    pub const ALL: [Self; 6] = [
+        Self::X11,
+        Self::X11rb,
+        Self::Wayland,
+        Self::Portal,
+        Self::Uinput,
+        Self::Windows,
+    ];
+
+    /// Returns the name of the backend, as accepted by the `KEYSIM_BACKEND` environment
+    /// variable.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::X11 => "x11",
+            Self::X11rb => "x11rb",
+            Self::Wayland => "wayland",
+            Self::Portal => "portal",
+            Self::Uinput => "uinput",
+            Self::Windows => "windows",
+        }
+    }
+
+    /// Returns whether the backend has been compiled in for the current platform.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Self::X11 => cfg!(all(target_os = "linux", feature = "x11")),
+            Self::X11rb => cfg!(all(target_os = "linux", feature = "x11rb")),
+            Self::Wayland => cfg!(all(target_os = "linux", feature = "wayland")),
+            Self::Portal => cfg!(all(target_os = "linux", feature = "portal")),
+            Self::Uinput => cfg!(all(target_os = "linux", feature = "uinput")),
+            Self::Windows => cfg!(target_os = "windows"),
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| crate::Error(crate::ErrorKind::UnknownBackend(s.to_owned())))
+    }
+}
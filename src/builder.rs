@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use crate::{Backend, Simulator};
+
+/// The environment variable that can be used to override the backends tried by a
+/// [`SimulatorBuilder`].
+///
+/// It must contain a comma-separated list of backend names, such as `wayland,uinput`. Blanks
+/// around the names and empty entries are ignored, and a list without any name does not override
+/// anything.
+pub const BACKEND_ENV_VAR: &str = "KEYSIM_BACKEND";
+
+/// The credentials used to connect to an X server.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum X11Authority {
+    /// The credentials are read from an `Xauthority` file, as the one referred to by the
+    /// `XAUTHORITY` environment variable.
+    File(PathBuf),
+    /// The credentials are provided explicitly.
+    Cookie {
+        /// The name of the authorization protocol, usually `MIT-MAGIC-COOKIE-1`.
+        name: String,
+        /// The authorization data, such as the 16 bytes of a `MIT-MAGIC-COOKIE-1` cookie.
+        data: Vec<u8>,
+    },
+}
+
+/// Configures how a [`Simulator`] is created.
+///
+/// By default, the backends worth trying are determined from the environment (for example, the
+/// `XDG_SESSION_TYPE` environment variable on Linux). The first backend that can be initialized
+/// is used.
+#[derive(Debug, Clone)]
+pub struct SimulatorBuilder {
+    /// The backends to try, in order. When `None`, they are determined from the environment.
+    backends: Option<Vec<Backend>>,
+    /// Whether the [`BACKEND_ENV_VAR`] environment variable is taken into account.
+    env_override: bool,
+
+    /// The name of the X display to connect to. When `None`, `$DISPLAY` is used.
+    pub(crate) x11_display: Option<String>,
+    /// The credentials used to connect to the X server. When `None`, `$XAUTHORITY` is used.
+    pub(crate) x11_authority: Option<X11Authority>,
+
+    /// The size of the desktop, used by the `uinput` backend to map absolute positions.
+    pub(crate) uinput_screen_size: Option<(u32, u32)>,
+}
+
+impl Default for SimulatorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatorBuilder {
+    /// Creates a new [`SimulatorBuilder`] with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            backends: None,
+            env_override: true,
+            x11_display: None,
+            x11_authority: None,
+            uinput_screen_size: None,
+        }
+    }
+
+    /// Adds `backend` at the end of the list of backends to try.
+    ///
+    /// As soon as a backend is added, only the backends explicitly added are tried.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backends.get_or_insert_with(Vec::new).push(backend);
+        self
+    }
+
+    /// Replaces the list of backends to try with `backends`, in order of preference.
+    pub fn backends(mut self, backends: impl IntoIterator<Item = Backend>) -> Self {
+        self.backends = Some(backends.into_iter().collect());
+        self
+    }
+
+    /// Sets whether the [`BACKEND_ENV_VAR`] environment variable may override the list of
+    /// backends to try.
+    ///
+    /// This is enabled by default.
+    pub fn env_override(mut self, yes: bool) -> Self {
+        self.env_override = yes;
+        self
+    }
+
+    /// Sets the name of the X display the X11 backends connect to, such as `":3"`.
+    ///
+    /// By default, the `DISPLAY` environment variable is used.
+    pub fn x11_display(mut self, display: impl Into<String>) -> Self {
+        self.x11_display = Some(display.into());
+        self
+    }
+
+    /// Sets the credentials the X11 backends use to connect to the X server.
+    ///
+    /// By default, they are read from the file referred to by the `XAUTHORITY` environment
+    /// variable.
+    pub fn x11_authority(mut self, authority: X11Authority) -> Self {
+        self.x11_authority = Some(authority);
+        self
+    }
+
+    /// Sets the size of the desktop, in pixels, which the `uinput` backend needs in order to move
+    /// the pointer to absolute positions.
+    ///
+    /// By default, the size of the first framebuffer (`/sys/class/graphics/fb0`) is used, which
+    /// usually matches the resolution of the primary monitor.
+    pub fn uinput_screen_size(mut self, width: u32, height: u32) -> Self {
+        self.uinput_screen_size = Some((width, height));
+        self
+    }
+
+    /// Returns the backends that [`build`](Self::build) would try, in order.
+    ///
+    /// This fails if the [`BACKEND_ENV_VAR`] environment variable contains an unknown backend.
+    pub fn resolve_backends(&self) -> Result<Vec<Backend>, crate::Error> {
+        let env = self
+            .env_override
+            .then(|| std::env::var(BACKEND_ENV_VAR).ok())
+            .flatten();
+
+        self.resolve_backends_from(env.as_deref())
+    }
+
+    /// Like [`resolve_backends`](Self::resolve_backends), with `env` as the value of the
+    /// [`BACKEND_ENV_VAR`] environment variable.
+    fn resolve_backends_from(&self, env: Option<&str>) -> Result<Vec<Backend>, crate::Error> {
+        let names: Vec<&str> = env
+            .into_iter()
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        match (names.is_empty(), &self.backends) {
+            (false, _) => names.into_iter().map(str::parse).collect(),
+            (true, Some(backends)) => Ok(backends.clone()),
+            (true, None) => Ok(crate::platform::Simulator::default_backends()),
+        }
+    }
+
+    /// Creates the [`Simulator`], using the first backend that can be initialized.
+    ///
+    /// When no backend can be initialized, the returned error lists every backend that was tried
+    /// and the reason it failed (see [`Error::attempts`](crate::Error::attempts)).
+    pub fn build(&self) -> Result<Simulator, crate::Error> {
+        let mut attempts = Vec::new();
+
+        for backend in self.resolve_backends()? {
+            let error = match crate::platform::Simulator::with_backend(backend, self) {
+                Some(Ok(sim)) => return Ok(Simulator::from_platform(sim)),
+                Some(Err(err)) => crate::Error::from(err),
+                None => crate::Error(crate::ErrorKind::Disabled(backend)),
+            };
+
+            attempts.push((backend, error));
+        }
+
+        Err(crate::Error(crate::ErrorKind::Attempts(attempts)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the backends `builder` would try when the environment variable is set to `env`.
+    fn resolve(builder: SimulatorBuilder, env: Option<&str>) -> Result<Vec<Backend>, String> {
+        builder
+            .resolve_backends_from(env)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[test]
+    fn env_names_are_parsed() {
+        let builder = SimulatorBuilder::new();
+        assert_eq!(
+            resolve(builder.clone(), Some("wayland,uinput")),
+            Ok(vec![Backend::Wayland, Backend::Uinput])
+        );
+        assert_eq!(
+            resolve(builder.clone(), Some(" X11 , x11rb ")),
+            Ok(vec![Backend::X11, Backend::X11rb])
+        );
+    }
+
+    #[test]
+    fn empty_env_entries_are_ignored() {
+        let builder = SimulatorBuilder::new();
+        assert_eq!(
+            resolve(builder.clone(), Some("x11,")),
+            Ok(vec![Backend::X11])
+        );
+        assert_eq!(
+            resolve(builder.clone(), Some(" , uinput")),
+            Ok(vec![Backend::Uinput])
+        );
+        assert_eq!(
+            resolve(builder.clone(), Some("portal,,windows")),
+            Ok(vec![Backend::Portal, Backend::Windows])
+        );
+    }
+
+    #[test]
+    fn unknown_env_names_are_rejected() {
+        let builder = SimulatorBuilder::new();
+        assert_eq!(
+            resolve(builder.clone(), Some("x11, foo")),
+            Err("UnknownBackend(\"foo\")".to_owned())
+        );
+    }
+
+    #[test]
+    fn env_overrides_the_builder() {
+        let builder = SimulatorBuilder::new().backend(Backend::Uinput);
+        assert_eq!(
+            resolve(builder.clone(), Some("wayland")),
+            Ok(vec![Backend::Wayland])
+        );
+        assert_eq!(resolve(builder, None), Ok(vec![Backend::Uinput]));
+    }
+
+    #[test]
+    fn blank_env_does_not_override_the_builder() {
+        let builder = SimulatorBuilder::new().backends([Backend::X11, Backend::Portal]);
+        for env in ["", "  ", ",", " , ,"] {
+            assert_eq!(
+                resolve(builder.clone(), Some(env)),
+                Ok(vec![Backend::X11, Backend::Portal])
+            );
+        }
+    }
+
+    #[test]
+    fn defaults_are_used_without_env_nor_builder() {
+        assert_eq!(
+            resolve(SimulatorBuilder::new(), Some(" ")),
+            Ok(crate::platform::Simulator::default_backends())
+        );
+    }
+
+    #[test]
+    fn env_override_can_be_disabled() {
+        let builder = SimulatorBuilder::new()
+            .backend(Backend::X11rb)
+            .env_override(false);
+        assert_eq!(builder.resolve_backends().unwrap(), [Backend::X11rb]);
+    }
+}
@@ -9,5 +9,9 @@ pub enum Button {
     /// The **Right** mouse button.
     Right,
     /// An extra mouse button.
+    ///
+    /// `Extra(0)` and `Extra(1)` are usually the "back" and "forward" buttons. On X11, `Extra(n)`
+    /// is the button `8 + n`. The number of extra buttons supported by a simulator is given by
+    /// [`Capabilities::max_extra_buttons`](crate::Capabilities::max_extra_buttons).
     Extra(u8),
 }
@@ -0,0 +1,128 @@
+/// How faithfully the simulated inputs imitate the ones produced by real devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Fidelity {
+    /// The inputs are not delivered to any application.
+    Recorded,
+    /// The inputs are flagged as synthetic, and some applications choose to ignore them.
+    ///
+    /// This is the case of the events sent through `XSendEvent` when the X server does not
+    /// support the "XTEST" extension.
+    Synthetic,
+    /// The inputs cannot be distinguished from the ones produced by real devices.
+    Native,
+}
+
+/// The characters that can be typed with [`Simulator::send_char`](crate::Simulator::send_char).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnicodeSupport {
+    /// Only printable ASCII characters can be typed, assuming a US keyboard layout.
+    Ascii,
+    /// Only the characters available in the active keyboard layout can be typed.
+    Layout,
+    /// Any character of the Basic Multilingual Plane can be typed.
+    Bmp,
+    /// Any character can be typed.
+    Full,
+}
+
+/// The kinds of inputs that can be simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct InputKinds {
+    /// Keys can be pressed and released.
+    pub keys: bool,
+    /// Mouse buttons can be pressed and released.
+    pub buttons: bool,
+    /// Characters can be typed.
+    pub chars: bool,
+    /// The mouse pointer can be moved.
+    pub pointer_motion: bool,
+    /// The mouse wheel can be scrolled.
+    pub scroll: bool,
+    /// The mouse wheel can be scrolled by fractions of a wheel click.
+    ///
+    /// When this is `false`, [`Simulator::scroll_smooth`](crate::Simulator::scroll_smooth)
+    /// falls back to whole wheel clicks.
+    pub smooth_scroll: bool,
+    /// Touchscreen contacts can be simulated.
+    ///
+    /// When this is `false`, [`Simulator::touch_down`](crate::Simulator::touch_down) and the
+    /// related functions return an error.
+    pub touch: bool,
+    /// Multi-finger touchpad gestures can be simulated.
+    ///
+    /// When this is `false`, [`Simulator::gesture`](crate::Simulator::gesture) returns an error.
+    pub gestures: bool,
+    /// Pen strokes, with pressure and tilt, can be simulated.
+    ///
+    /// When this is `false`, [`Simulator::stylus`](crate::Simulator::stylus) and the related
+    /// functions return an error.
+    pub stylus: bool,
+    /// Virtual gamepads can be created.
+    ///
+    /// When this is `false`, [`Simulator::create_gamepad`](crate::Simulator::create_gamepad)
+    /// returns an error.
+    pub gamepad: bool,
+}
+
+/// Describes what a [`Simulator`](crate::Simulator) is able to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The name of the backend used by the simulator, such as `"x11"` or `"uinput"`.
+    ///
+    /// This matches [`Backend::name`](crate::Backend::name) for the simulators that are not
+    /// recording.
+    pub backend: &'static str,
+    /// How faithfully the simulated inputs imitate the ones produced by real devices.
+    pub fidelity: Fidelity,
+    /// The kinds of inputs that can be simulated.
+    pub inputs: InputKinds,
+    /// The number of [`Button::Extra`](crate::Button::Extra) buttons that are supported.
+    ///
+    /// Extra buttons are numbered from `0` to `max_extra_buttons - 1`.
+    pub max_extra_buttons: u8,
+    /// The characters that can be typed.
+    pub unicode: UnicodeSupport,
+}
+
+impl Capabilities {
+    /// The capabilities shared by most backends: keys, buttons, characters, pointer motion and
+    /// smooth scrolling.
+    #[cfg_attr(
+        not(any(
+            target_os = "windows",
+            feature = "x11",
+            feature = "x11rb",
+            feature = "wayland",
+            feature = "portal",
+            feature = "uinput"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) const fn new(
+        backend: &'static str,
+        fidelity: Fidelity,
+        max_extra_buttons: u8,
+        unicode: UnicodeSupport,
+    ) -> Self {
+        Self {
+            backend,
+            fidelity,
+            inputs: InputKinds {
+                keys: true,
+                buttons: true,
+                chars: true,
+                pointer_motion: true,
+                scroll: true,
+                smooth_scroll: true,
+                touch: false,
+                gestures: false,
+                stylus: false,
+                gamepad: false,
+            },
+            max_extra_buttons,
+            unicode,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Describes how [`Simulator::click`](crate::Simulator::click) clicks a mouse button.
+
+use std::time::Duration;
+
+/// The double-click time assumed when the desktop does not provide one.
+pub(crate) const DEFAULT_DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// Describes how [`Simulator::click`](crate::Simulator::click) clicks a mouse button.
+///
+/// By default, the button is held for 20 milliseconds on each click, and consecutive clicks are
+/// spaced so that they all fit well within the double-click time of the desktop.
+#[derive(Debug, Clone)]
+pub struct ClickOptions {
+    /// The time the button is held on each click.
+    pub(crate) hold: Duration,
+    /// The time between the release of a click and the press of the next one, if it does not
+    /// depend on the double-click time.
+    interval: Option<Duration>,
+}
+
+impl Default for ClickOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClickOptions {
+    /// Creates a new [`ClickOptions`] with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            hold: Duration::from_millis(20),
+            interval: None,
+        }
+    }
+
+    /// Sets the time the button is held on each click.
+    ///
+    /// Long durations can be used to simulate a long press, which some applications and touch
+    /// oriented toolkits treat as a right click or as the start of a drag.
+    pub fn hold(mut self, hold: Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Sets the time between the release of a click and the press of the next one.
+    ///
+    /// By default, this is a fraction of the double-click time of the desktop, such that the
+    /// clicks are recognized as a single double- or triple-click. Intervals longer than the
+    /// double-click time produce separate clicks.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Returns the time between the release of a click and the press of the next one, given the
+    /// double-click time of the desktop.
+    pub(crate) fn gap(&self, double_click_time: Duration) -> Duration {
+        self.interval
+            .unwrap_or_else(|| (double_click_time / 4).saturating_sub(self.hold))
+    }
+}
@@ -0,0 +1,105 @@
+//! Describes how [`Simulator::drag`](crate::Simulator::drag) drags the mouse pointer.
+
+use std::time::Duration;
+
+use crate::glide::max_steps;
+
+/// Describes how [`Simulator::drag`](crate::Simulator::drag) drags the mouse pointer.
+///
+/// By default, the button is held for 100 milliseconds before and after the pointer moves, and
+/// the pointer reaches its target in 10 steps spread over 200 milliseconds.
+#[derive(Debug, Clone)]
+pub struct DragOptions {
+    /// The time the button is held before the pointer starts moving, and after it stops.
+    pub(crate) hold: Duration,
+    /// The number of motion events sent between the start and the end of the drag.
+    steps: u32,
+    /// The time taken by the pointer to reach its target.
+    duration: Duration,
+}
+
+impl Default for DragOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DragOptions {
+    /// Creates a new [`DragOptions`] with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            hold: Duration::from_millis(100),
+            steps: 10,
+            duration: Duration::from_millis(200),
+        }
+    }
+
+    /// Sets the time the button is held before the pointer starts moving, and after it reaches
+    /// its target.
+    ///
+    /// Some toolkits only start a drag once the button has been held for a while.
+    pub fn hold(mut self, hold: Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Sets the number of motion events sent while the pointer moves to its target.
+    ///
+    /// Some toolkits only recognize a drag after several motion events. A value of `0` is
+    /// treated as `1`, and at most one motion event is sent per millisecond of duration.
+    pub fn steps(mut self, steps: u32) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the time taken by the pointer to reach its target.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Returns the positions taken by the pointer when moving from `from` to `to`, along with
+    /// the delay that precedes each of them.
+    pub(crate) fn plan(&self, from: (i32, i32), to: (i32, i32)) -> (Duration, Vec<(i32, i32)>) {
+        let steps = self.steps.clamp(1, max_steps(self.duration));
+        let interval = self.duration / steps;
+
+        let lerp = |a: i32, b: i32, t: f64| (a as f64 + (b as f64 - a as f64) * t).round() as i32;
+        let points = (1..=steps)
+            .map(|i| {
+                let t = i as f64 / steps as f64;
+                (lerp(from.0, to.0, t), lerp(from.1, to.1, t))
+            })
+            .collect();
+
+        (interval, points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_end_at_the_target() {
+        let (interval, points) = DragOptions::new().plan((0, 0), (100, -50));
+        assert_eq!(interval, Duration::from_millis(20));
+        assert_eq!(points.len(), 10);
+        assert_eq!(points[0], (10, -5));
+        assert_eq!(points.last(), Some(&(100, -50)));
+    }
+
+    #[test]
+    fn steps_are_limited_by_the_duration() {
+        let options = DragOptions::new()
+            .steps(u32::MAX)
+            .duration(Duration::from_millis(5));
+        let (interval, points) = options.plan((0, 0), (10, 0));
+        assert_eq!(interval, Duration::from_millis(1));
+        assert_eq!(points, [(2, 0), (4, 0), (6, 0), (8, 0), (10, 0)]);
+
+        let (interval, points) = options.duration(Duration::ZERO).plan((0, 0), (10, 0));
+        assert_eq!(interval, Duration::ZERO);
+        assert_eq!(points, [(10, 0)]);
+    }
+}
@@ -1,23 +1,118 @@
 use std::fmt;
 use std::fmt::{Debug, Display};
 
+use crate::Backend;
+
 /// An error that might occur when interacting when simulating user inputs.
-pub struct Error(pub(crate) crate::platform::Error);
+pub struct Error(pub(crate) ErrorKind);
+
+/// The different kinds of [`Error`].
+pub(crate) enum ErrorKind {
+    Platform(crate::platform::Error),
+    #[cfg(feature = "mock")]
+    Recording(crate::RecordingError),
+    /// The backend is not available on the current platform, or has not been enabled.
+    Disabled(Backend),
+    /// A backend name could not be parsed.
+    UnknownBackend(String),
+    /// None of the backends that were tried could be initialized.
+    Attempts(Vec<(Backend, Error)>),
+    /// The position of the mouse pointer is not known.
+    UnknownPosition,
+    /// The backend cannot simulate the described kind of input.
+    Unsupported(&'static str),
+}
+
+impl Error {
+    /// Returns the backends that were tried when creating a [`Simulator`](crate::Simulator),
+    /// along with the reason they failed.
+    ///
+    /// This is empty unless the error was returned by [`SimulatorBuilder::build`] or
+    /// [`Simulator::new`](crate::Simulator::new).
+    ///
+    /// [`SimulatorBuilder::build`]: crate::SimulatorBuilder::build
+    pub fn attempts(&self) -> &[(Backend, Error)] {
+        match &self.0 {
+            ErrorKind::Attempts(attempts) => attempts,
+            _ => &[],
+        }
+    }
+}
+
+impl From<crate::platform::Error> for Error {
+    #[inline]
+    fn from(e: crate::platform::Error) -> Self {
+        Self(ErrorKind::Platform(e))
+    }
+}
+
+#[cfg(feature = "mock")]
+impl From<crate::RecordingError> for Error {
+    #[inline]
+    fn from(e: crate::RecordingError) -> Self {
+        Self(ErrorKind::Recording(e))
+    }
+}
 
 impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Debug::fmt(&self.0, f)
+        match &self.0 {
+            ErrorKind::Platform(e) => Debug::fmt(e, f),
+            #[cfg(feature = "mock")]
+            ErrorKind::Recording(e) => Debug::fmt(e, f),
+            ErrorKind::Disabled(b) => f.debug_tuple("Disabled").field(b).finish(),
+            ErrorKind::UnknownBackend(s) => f.debug_tuple("UnknownBackend").field(s).finish(),
+            ErrorKind::Attempts(a) => f.debug_tuple("Attempts").field(a).finish(),
+            ErrorKind::UnknownPosition => f.write_str("UnknownPosition"),
+            ErrorKind::Unsupported(s) => f.debug_tuple("Unsupported").field(s).finish(),
+        }
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&self.0, f)
+        match &self.0 {
+            ErrorKind::Platform(e) => Display::fmt(e, f),
+            #[cfg(feature = "mock")]
+            ErrorKind::Recording(e) => Display::fmt(e, f),
+            ErrorKind::Disabled(Backend::Windows) => {
+                f.write_str("this backend is only available on Windows")
+            }
+            ErrorKind::Disabled(b) if cfg!(target_os = "linux") => {
+                write!(f, "this backend must be enabled with the `{b}` feature")
+            }
+            ErrorKind::Disabled(_) => f.write_str("this backend is only available on Linux"),
+            ErrorKind::UnknownBackend(s) => {
+                write!(f, "unknown backend {s:?}, expected one of")?;
+                for (i, b) in Backend::ALL.iter().enumerate() {
+                    let sep = if i == 0 { " " } else { ", " };
+                    write!(f, "{sep}`{b}`")?;
+                }
+                Ok(())
+            }
+            ErrorKind::Attempts(a) if a.is_empty() => f.write_str("no backend was tried"),
+            ErrorKind::Attempts(a) => {
+                f.write_str("no backend could be initialized:")?;
+                for (backend, e) in a {
+                    write!(f, "\n  - {backend}: {e}")?;
+                }
+                Ok(())
+            }
+            ErrorKind::UnknownPosition => {
+                f.write_str("the position of the mouse pointer is not known")
+            }
+            ErrorKind::Unsupported(s) => write!(f, "the backend does not support {s}"),
+        }
     }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(&self.0)
+        match &self.0 {
+            ErrorKind::Platform(e) => Some(e),
+            #[cfg(feature = "mock")]
+            ErrorKind::Recording(e) => Some(e),
+            _ => None,
+        }
     }
 }
@@ -0,0 +1,195 @@
+//! Virtual gamepads, created with [`Simulator::create_gamepad`](crate::Simulator::create_gamepad).
+
+/// The value of a stick axis when the stick is pushed all the way.
+pub(crate) const STICK_MAX: i32 = 32767;
+/// The value of a trigger axis when the trigger is fully pressed.
+pub(crate) const TRIGGER_MAX: i32 = 255;
+
+/// A button of a gamepad, named after the layout of an Xbox controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GamepadButton {
+    /// The bottom face button, **A**.
+    A,
+    /// The right face button, **B**.
+    B,
+    /// The left face button, **X**.
+    X,
+    /// The top face button, **Y**.
+    Y,
+    /// The left shoulder button.
+    LeftBumper,
+    /// The right shoulder button.
+    RightBumper,
+    /// The **Back** (or **View**) button.
+    Back,
+    /// The **Start** (or **Menu**) button.
+    Start,
+    /// The **Guide** button, in the center of the gamepad.
+    Guide,
+    /// The left stick, pressed down.
+    LeftStick,
+    /// The right stick, pressed down.
+    RightStick,
+    /// The **Up** direction of the D-pad.
+    DPadUp,
+    /// The **Down** direction of the D-pad.
+    DPadDown,
+    /// The **Left** direction of the D-pad.
+    DPadLeft,
+    /// The **Right** direction of the D-pad.
+    DPadRight,
+}
+
+/// An analog axis of a gamepad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GamepadAxis {
+    /// The horizontal position of the left stick, positive to the right.
+    LeftStickX,
+    /// The vertical position of the left stick, positive downwards.
+    LeftStickY,
+    /// The horizontal position of the right stick, positive to the right.
+    RightStickX,
+    /// The vertical position of the right stick, positive downwards.
+    RightStickY,
+    /// How far the left trigger is pressed.
+    LeftTrigger,
+    /// How far the right trigger is pressed.
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    /// Converts `value`, from `-1.0` to `1.0` for sticks and from `0.0` to `1.0` for triggers,
+    /// into the range of the axis on an Xbox controller.
+    pub(crate) fn quantize(self, value: f64) -> i32 {
+        let (minimum, maximum) = match self {
+            Self::LeftTrigger | Self::RightTrigger => (0.0, TRIGGER_MAX),
+            _ => (-1.0, STICK_MAX),
+        };
+
+        // Casting turns NaN into zero.
+        (value.clamp(minimum, 1.0) * maximum as f64).round() as i32
+    }
+}
+
+/// Describes how a virtual gamepad identifies itself.
+///
+/// By default, the gamepad identifies itself as a wired Xbox 360 controller, which SDL, Steam
+/// Input and most games recognize without any configuration.
+#[derive(Debug, Clone)]
+pub struct GamepadOptions {
+    /// The name of the device.
+    pub(crate) name: String,
+    /// The USB vendor ID of the device.
+    pub(crate) vendor: u16,
+    /// The USB product ID of the device.
+    pub(crate) product: u16,
+}
+
+impl Default for GamepadOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GamepadOptions {
+    /// Creates a new [`GamepadOptions`] with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            name: String::from("Microsoft X-Box 360 pad"),
+            vendor: 0x045e,
+            product: 0x028e,
+        }
+    }
+
+    /// Sets the name of the device.
+    ///
+    /// Names longer than 79 bytes are truncated.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the USB vendor ID of the device.
+    pub fn vendor(mut self, vendor: u16) -> Self {
+        self.vendor = vendor;
+        self
+    }
+
+    /// Sets the USB product ID of the device.
+    ///
+    /// Games usually look the vendor and product IDs up in a database of known controllers to
+    /// decide how to map the buttons and axes. Using the IDs of a controller that does not have
+    /// the layout of an Xbox controller may result in a wrong mapping.
+    pub fn product(mut self, product: u16) -> Self {
+        self.product = product;
+        self
+    }
+}
+
+/// A virtual gamepad, created with [`Simulator::create_gamepad`](crate::Simulator::create_gamepad).
+///
+/// The gamepad is removed from the system when this value is dropped.
+pub struct Gamepad(Inner);
+
+/// The implementation used by a [`Gamepad`].
+enum Inner {
+    Platform(crate::platform::Gamepad),
+    #[cfg(feature = "mock")]
+    Recording(crate::Recorder),
+}
+
+impl Gamepad {
+    /// Wraps a platform gamepad.
+    #[inline]
+    pub(crate) fn from_platform(gamepad: crate::platform::Gamepad) -> Self {
+        Self(Inner::Platform(gamepad))
+    }
+
+    /// Creates a new [`Gamepad`] that records its inputs into `recorder`.
+    #[cfg(feature = "mock")]
+    #[inline]
+    pub(crate) fn recording(recorder: &crate::Recorder) -> Self {
+        Self(Inner::Recording(recorder.clone()))
+    }
+
+    /// Presses a button.
+    ///
+    /// Real D-pads cannot report two opposite directions at once: while both are pressed, the
+    /// D-pad is reported as centered along their axis.
+    #[inline]
+    pub fn press(&self, button: GamepadButton) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(gamepad) => gamepad.press(button).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.press_gamepad_button(button).map_err(crate::Error::from),
+        }
+    }
+
+    /// Releases a button.
+    #[inline]
+    pub fn release(&self, button: GamepadButton) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(gamepad) => gamepad.release(button).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec
+                .release_gamepad_button(button)
+                .map_err(crate::Error::from),
+        }
+    }
+
+    /// Sets the position of an analog axis.
+    ///
+    /// Sticks range from `-1.0` to `1.0`, with `0.0` being the rest position, and triggers range
+    /// from `0.0` (released) to `1.0` (fully pressed). Values outside of those ranges are
+    /// clamped.
+    #[inline]
+    pub fn set_axis(&self, axis: GamepadAxis, value: f64) -> Result<(), crate::Error> {
+        let value = axis.quantize(value);
+
+        match &self.0 {
+            Inner::Platform(gamepad) => gamepad.set_axis(axis, value).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.gamepad_axis(axis, value).map_err(crate::Error::from),
+        }
+    }
+}
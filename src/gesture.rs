@@ -0,0 +1,170 @@
+//! Describes the multi-finger gestures performed with
+//! [`Simulator::gesture`](crate::Simulator::gesture).
+
+use std::f64::consts::TAU;
+use std::time::Duration;
+
+/// The maximum number of fingers of a gesture.
+pub(crate) const MAX_FINGERS: u8 = 5;
+
+/// The distance between the fingers and the center of the gesture when it starts, in hundredths
+/// of a millimetre.
+const SPREAD: f64 = 1500.0;
+
+/// Half the width and height of the virtual touchpad, in hundredths of a millimetre.
+pub(crate) const TOUCHPAD_HALF_SIZE: (i32, i32) = (5000, 3500);
+
+/// A multi-finger gesture performed on a touchpad.
+///
+/// The fingers are placed on a circle around the center of the touchpad, then move
+/// simultaneously: the circle is scaled and rotated while its center is translated, until the
+/// gesture completes and the fingers are lifted.
+///
+/// By default, two fingers are used, and they do not move. The gesture lasts 300 milliseconds,
+/// during which 20 motion events are sent.
+#[derive(Debug, Clone)]
+pub struct Gesture {
+    /// The number of fingers touching the touchpad.
+    fingers: u8,
+    /// The factor by which the distance between the fingers is multiplied.
+    scale: f64,
+    /// The angle by which the fingers rotate around the center of the gesture, in degrees.
+    rotation: f64,
+    /// The distance travelled by the center of the gesture, in millimetres.
+    translation: (f64, f64),
+    /// The time taken by the fingers to complete the gesture.
+    duration: Duration,
+    /// The number of motion events sent between the start and the end of the gesture.
+    steps: u32,
+}
+
+impl Default for Gesture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Gesture {
+    /// Creates a new [`Gesture`] with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            fingers: 2,
+            scale: 1.0,
+            rotation: 0.0,
+            translation: (0.0, 0.0),
+            duration: Duration::from_millis(300),
+            steps: 20,
+        }
+    }
+
+    /// Creates a two-finger pinch that multiplies the distance between the fingers by `scale`.
+    ///
+    /// Values greater than `1` usually zoom in, and values lower than `1` zoom out.
+    pub fn pinch(scale: f64) -> Self {
+        Self::new().scale(scale)
+    }
+
+    /// Creates a two-finger rotation by `degrees`, clockwise.
+    pub fn rotate(degrees: f64) -> Self {
+        Self::new().rotation(degrees)
+    }
+
+    /// Creates a swipe of `fingers` fingers, which travel (`dx`, `dy`) millimetres.
+    ///
+    /// Desktop shells usually bind three- and four-finger swipes to actions such as switching
+    /// workspaces.
+    pub fn swipe(fingers: u8, dx: f64, dy: f64) -> Self {
+        Self::new().fingers(fingers).translation(dx, dy)
+    }
+
+    /// Sets the number of fingers touching the touchpad.
+    ///
+    /// The value is clamped to the range `1..=5`.
+    pub fn fingers(mut self, fingers: u8) -> Self {
+        self.fingers = fingers.clamp(1, MAX_FINGERS);
+        self
+    }
+
+    /// Sets the factor by which the distance between the fingers is multiplied over the course
+    /// of the gesture.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the angle by which the fingers rotate around the center of the gesture, in degrees.
+    ///
+    /// Positive angles rotate clockwise.
+    pub fn rotation(mut self, degrees: f64) -> Self {
+        self.rotation = degrees;
+        self
+    }
+
+    /// Sets the distance travelled by the fingers, in millimetres.
+    ///
+    /// The fingers cannot leave the touchpad, which is 100 millimetres wide and 70 millimetres
+    /// high.
+    pub fn translation(mut self, dx: f64, dy: f64) -> Self {
+        self.translation = (dx, dy);
+        self
+    }
+
+    /// Sets the time taken by the fingers to complete the gesture.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the number of motion events sent while the fingers move.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn steps(mut self, steps: u32) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Returns the position of the fingers at each step of the gesture, along with the delay
+    /// that precedes each step but the first.
+    ///
+    /// The positions are expressed in hundredths of a millimetre, relative to the center of the
+    /// touchpad.
+    pub(crate) fn plan(&self) -> (Duration, Vec<Vec<(i32, i32)>>) {
+        let steps = self.steps.max(1);
+        let interval = self.duration / steps;
+
+        let finite = |v: f64, default: f64| if v.is_finite() { v } else { default };
+        let scale = finite(self.scale, 1.0).max(0.0);
+        let rotation = finite(self.rotation, 0.0).to_radians();
+        let dx = finite(self.translation.0, 0.0) * 100.0;
+        let dy = finite(self.translation.1, 0.0) * 100.0;
+
+        let (half_width, half_height) = TOUCHPAD_HALF_SIZE;
+        let fingers = self.fingers;
+
+        let frames = (0..=steps)
+            .map(|i| {
+                let t = i as f64 / steps as f64;
+                let radius = match fingers {
+                    1 => 0.0,
+                    _ => SPREAD * (1.0 + (scale - 1.0) * t),
+                };
+                let angle = rotation * t;
+
+                (0..fingers)
+                    .map(|finger| {
+                        // The first finger starts right above the center.
+                        let a = angle + TAU * finger as f64 / fingers as f64 - TAU / 4.0;
+                        let x = dx * t + radius * a.cos();
+                        let y = dy * t + radius * a.sin();
+                        (
+                            (x.round() as i32).clamp(-half_width, half_width - 1),
+                            (y.round() as i32).clamp(-half_height, half_height - 1),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        (interval, frames)
+    }
+}
@@ -0,0 +1,486 @@
+//! Describes how [`Simulator::glide_mouse_to`](crate::Simulator::glide_mouse_to) moves the mouse
+//! pointer from one position to another.
+
+use std::collections::hash_map::RandomState;
+use std::f64::consts::PI;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// The shape of the path followed by the mouse pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MotionPath {
+    /// The pointer moves along a straight line.
+    Linear,
+    /// The pointer moves along a cubic Bezier curve whose control points are randomly offset
+    /// from the straight line.
+    Bezier {
+        /// The maximum offset of the control points, relative to the distance covered by the
+        /// pointer.
+        ///
+        /// `0.0` produces a straight line, and values above `0.5` produce very curved paths.
+        curvature: f64,
+    },
+    /// The pointer moves along the path produced by the *WindMouse* algorithm, which simulates
+    /// a particle pulled toward the target by gravity and pushed around by random wind.
+    WindMouse {
+        /// The strength of the pull toward the target.
+        gravity: f64,
+        /// The strength of the random wind.
+        wind: f64,
+    },
+}
+
+impl MotionPath {
+    /// A Bezier curve with a moderate curvature.
+    pub const BEZIER: Self = Self::Bezier { curvature: 0.2 };
+
+    /// The parameters commonly used with the *WindMouse* algorithm.
+    pub const WIND_MOUSE: Self = Self::WindMouse {
+        gravity: 9.0,
+        wind: 3.0,
+    };
+}
+
+/// How the progress of the pointer along its path evolves over time.
+#[derive(Clone, Copy)]
+pub enum Easing {
+    /// The pointer moves at a constant speed.
+    Linear,
+    /// The pointer starts slowly and accelerates.
+    EaseIn,
+    /// The pointer starts quickly and decelerates.
+    EaseOut,
+    /// The pointer accelerates, then decelerates.
+    EaseInOut,
+    /// A custom function that maps the elapsed fraction of the duration (from `0.0` to `1.0`) to
+    /// the covered fraction of the path.
+    ///
+    /// The returned value is clamped to `0.0..=1.0`.
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// Returns the fraction of the path covered after the fraction `t` of the duration.
+    fn apply(self, t: f64) -> f64 {
+        let ret = match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Self::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+            Self::Custom(f) => f(t),
+        };
+
+        if ret.is_nan() {
+            t
+        } else {
+            ret.clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Linear => f.write_str("Linear"),
+            Self::EaseIn => f.write_str("EaseIn"),
+            Self::EaseOut => f.write_str("EaseOut"),
+            Self::EaseInOut => f.write_str("EaseInOut"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Describes how [`Simulator::glide_mouse_to`](crate::Simulator::glide_mouse_to) moves the mouse
+/// pointer.
+///
+/// By default, the pointer follows a slightly curved path during 300 milliseconds, accelerating
+/// then decelerating, and its position is updated 120 times per second.
+#[derive(Debug, Clone)]
+pub struct MotionProfile {
+    /// The time taken to reach the target.
+    duration: Duration,
+    /// The number of motion events sent per second.
+    rate: f64,
+    /// The shape of the path.
+    path: MotionPath,
+    /// How the progress along the path evolves over time.
+    easing: Easing,
+    /// How far the pointer goes past the target, relative to the distance covered.
+    overshoot: f64,
+    /// The maximum random offset applied to the intermediate positions, in pixels.
+    jitter: f64,
+    /// The seed of the random number generator. When `None`, a random seed is used.
+    seed: Option<u64>,
+}
+
+impl Default for MotionProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MotionProfile {
+    /// Creates a new [`MotionProfile`] with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            duration: Duration::from_millis(300),
+            rate: 120.0,
+            path: MotionPath::BEZIER,
+            easing: Easing::EaseInOut,
+            overshoot: 0.0,
+            jitter: 0.0,
+            seed: None,
+        }
+    }
+
+    /// Sets the time taken by the pointer to reach its target.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the number of motion events sent per second.
+    ///
+    /// The actual rate may be lower if the backend is slow to send the events. At most one
+    /// event is sent per millisecond of duration.
+    pub fn rate(mut self, rate: f64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Sets the shape of the path followed by the pointer.
+    pub fn path(mut self, path: MotionPath) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Sets how the progress of the pointer along its path evolves over time.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Makes the pointer go past its target before coming back to it.
+    ///
+    /// `overshoot` is the maximum distance between the target and the furthest position reached
+    /// by the pointer, relative to the distance covered. For example, `0.1` makes a pointer
+    /// that moves by 500 pixels go up to 50 pixels too far.
+    pub fn overshoot(mut self, overshoot: f64) -> Self {
+        self.overshoot = overshoot;
+        self
+    }
+
+    /// Randomly offsets the intermediate positions of the pointer by up to `jitter` pixels.
+    ///
+    /// The first and last positions are never offset.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the seed of the random number generator used to produce the path.
+    ///
+    /// Two glides with the same profile, seed, start and target follow the same path. Without a
+    /// seed, a different path is produced every time.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the time elapsed between two motion events, when `steps` events are sent.
+    pub(crate) fn interval(&self, steps: usize) -> Duration {
+        self.duration / steps.max(1) as u32
+    }
+
+    /// Computes the positions taken by the pointer when moving from `from` to `to`.
+    ///
+    /// One position is returned per motion event, the last one being `to`.
+    pub(crate) fn plan(&self, from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+        let steps = match self.duration.as_secs_f64() * self.rate {
+            n if n.is_finite() && n >= 1.0 => {
+                n.ceil().min(max_steps(self.duration) as f64) as usize
+            }
+            _ => 1,
+        };
+
+        let mut rng = match self.seed {
+            Some(seed) => Rng(seed),
+            None => Rng(RandomState::new().build_hasher().finish()),
+        };
+
+        let from = (from.0 as f64, from.1 as f64);
+        let target = (to.0 as f64, to.1 as f64);
+        let distance = (target.0 - from.0).hypot(target.1 - from.1);
+
+        let mut polyline = vec![from];
+        if self.overshoot > 0.0 && distance > 0.0 {
+            let (ux, uy) = (
+                (target.0 - from.0) / distance,
+                (target.1 - from.1) / distance,
+            );
+            let past = self.overshoot * distance * rng.range(0.5, 1.0);
+            let aside = past * rng.range(-0.5, 0.5);
+            let turn = (
+                target.0 + ux * past - uy * aside,
+                target.1 + uy * past + ux * aside,
+            );
+
+            self.extend_path(&mut polyline, turn, &mut rng);
+            self.extend_path(&mut polyline, target, &mut rng);
+        } else {
+            self.extend_path(&mut polyline, target, &mut rng);
+        }
+
+        let lengths = cumulative_lengths(&polyline);
+        let total = lengths[lengths.len() - 1];
+
+        let mut ret = Vec::with_capacity(steps);
+        for step in 1..steps {
+            let t = step as f64 / steps as f64;
+            let (mut x, mut y) = point_at(&polyline, &lengths, self.easing.apply(t) * total);
+
+            if self.jitter > 0.0 {
+                // The jitter fades out near both ends of the path.
+                let amplitude = self.jitter * (PI * t).sin();
+                x += rng.range(-amplitude, amplitude);
+                y += rng.range(-amplitude, amplitude);
+            }
+
+            // Float to integer casts saturate.
+            ret.push((x.round() as i32, y.round() as i32));
+        }
+        ret.push(to);
+
+        ret
+    }
+
+    /// Appends the points of a path from the last point of `polyline` to `to`.
+    fn extend_path(&self, polyline: &mut Vec<(f64, f64)>, to: (f64, f64), rng: &mut Rng) {
+        let from = polyline[polyline.len() - 1];
+        let distance = (to.0 - from.0).hypot(to.1 - from.1);
+
+        match self.path {
+            MotionPath::Linear => (),
+            MotionPath::Bezier { curvature } => {
+                // The control points are offset perpendicularly to the straight line.
+                let (nx, ny) = ((from.1 - to.1), (to.0 - from.0));
+                let offset1 = curvature * rng.range(-1.0, 1.0);
+                let offset2 = curvature * rng.range(-1.0, 1.0);
+                let p1 = (
+                    from.0 + (to.0 - from.0) / 3.0 + nx * offset1,
+                    from.1 + (to.1 - from.1) / 3.0 + ny * offset1,
+                );
+                let p2 = (
+                    from.0 + (to.0 - from.0) * 2.0 / 3.0 + nx * offset2,
+                    from.1 + (to.1 - from.1) * 2.0 / 3.0 + ny * offset2,
+                );
+
+                const SAMPLES: usize = 64;
+                for i in 1..SAMPLES {
+                    let t = i as f64 / SAMPLES as f64;
+                    let u = 1.0 - t;
+                    let a = u * u * u;
+                    let b = 3.0 * u * u * t;
+                    let c = 3.0 * u * t * t;
+                    let d = t * t * t;
+                    polyline.push((
+                        a * from.0 + b * p1.0 + c * p2.0 + d * to.0,
+                        a * from.1 + b * p1.1 + c * p2.1 + d * to.1,
+                    ));
+                }
+            }
+            MotionPath::WindMouse { gravity, wind } => {
+                // The distance from the target below which the wind calms down.
+                const TARGET_AREA: f64 = 12.0;
+                // Bounds the number of iterations when the parameters prevent convergence.
+                const MAX_POINTS: usize = 10_000;
+
+                let mut max_step = 15.0f64.min(distance.max(1.0));
+                let (mut x, mut y) = from;
+                let (mut vx, mut vy) = (0.0, 0.0);
+                let (mut wx, mut wy) = (0.0, 0.0);
+
+                for _ in 0..MAX_POINTS {
+                    let distance = (to.0 - x).hypot(to.1 - y);
+                    if distance < 1.0 {
+                        break;
+                    }
+
+                    let wind = wind.min(distance);
+                    if distance >= TARGET_AREA {
+                        wx = wx / 3f64.sqrt() + rng.range(-1.0, 1.0) * wind / 5f64.sqrt();
+                        wy = wy / 3f64.sqrt() + rng.range(-1.0, 1.0) * wind / 5f64.sqrt();
+                    } else {
+                        wx /= 3f64.sqrt();
+                        wy /= 3f64.sqrt();
+                        if max_step < 3.0 {
+                            max_step = rng.range(3.0, 6.0);
+                        } else {
+                            max_step /= 5f64.sqrt();
+                        }
+                    }
+
+                    vx += wx + gravity * (to.0 - x) / distance;
+                    vy += wy + gravity * (to.1 - y) / distance;
+
+                    let speed = vx.hypot(vy);
+                    if speed > max_step {
+                        let clipped = rng.range(max_step / 2.0, max_step);
+                        vx = vx / speed * clipped;
+                        vy = vy / speed * clipped;
+                    }
+
+                    if !vx.is_finite() || !vy.is_finite() {
+                        break;
+                    }
+
+                    x += vx;
+                    y += vy;
+                    polyline.push((x, y));
+                }
+            }
+        }
+
+        polyline.push(to);
+    }
+}
+
+/// Returns the maximum number of motion events sent during `duration`, which is one per
+/// millisecond.
+///
+/// This bounds the number of positions computed ahead of time when the requested rate is absurd.
+pub(crate) fn max_steps(duration: Duration) -> u32 {
+    duration.as_millis().clamp(1, u32::MAX as u128) as u32
+}
+
+/// Returns the distance covered from the first point of `polyline` to each of its points.
+fn cumulative_lengths(polyline: &[(f64, f64)]) -> Vec<f64> {
+    let mut total = 0.0;
+    let mut ret = Vec::with_capacity(polyline.len());
+    ret.push(0.0);
+    for pair in polyline.windows(2) {
+        total += (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1);
+        ret.push(total);
+    }
+    ret
+}
+
+/// Returns the point of `polyline` located at the distance `length` from its first point.
+fn point_at(polyline: &[(f64, f64)], lengths: &[f64], length: f64) -> (f64, f64) {
+    let i = lengths.partition_point(|&l| l < length);
+    if i == 0 {
+        return polyline[0];
+    }
+    if i == lengths.len() {
+        return polyline[polyline.len() - 1];
+    }
+
+    let (a, b) = (polyline[i - 1], polyline[i]);
+    let segment = lengths[i] - lengths[i - 1];
+    let t = if segment > 0.0 {
+        (length - lengths[i - 1]) / segment
+    } else {
+        0.0
+    };
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// A small pseudo-random number generator (SplitMix64).
+///
+/// It is only used to produce plausible paths, and must not be used for anything that requires
+/// unpredictability.
+struct Rng(u64);
+
+impl Rng {
+    /// Returns the next pseudo-random 64-bit integer.
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudo-random number in the range `min..max`.
+    fn range(&mut self, min: f64, max: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        min + (max - min) * unit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_path() {
+        let profile = MotionProfile::new()
+            .path(MotionPath::WIND_MOUSE)
+            .overshoot(0.1)
+            .jitter(3.0)
+            .seed(42);
+        let path = profile.plan((10, 20), (800, 600));
+        assert_eq!(path, profile.plan((10, 20), (800, 600)));
+        assert_ne!(path, profile.clone().seed(43).plan((10, 20), (800, 600)));
+    }
+
+    #[test]
+    fn last_point_is_the_target() {
+        let paths = [
+            MotionPath::Linear,
+            MotionPath::BEZIER,
+            MotionPath::WIND_MOUSE,
+        ];
+        for path in paths {
+            let profile = MotionProfile::new()
+                .path(path)
+                .overshoot(0.2)
+                .jitter(5.0)
+                .seed(7);
+            let points = profile.plan((-50, 30), (1234, 567));
+            assert_eq!(points.len(), 36);
+            assert_eq!(points.last(), Some(&(1234, 567)));
+        }
+    }
+
+    #[test]
+    fn overshoot_goes_past_the_target() {
+        let profile = MotionProfile::new()
+            .path(MotionPath::Linear)
+            .easing(Easing::Linear)
+            .overshoot(0.5)
+            .seed(1);
+        let points = profile.plan((0, 0), (1000, 0));
+        assert!(points.iter().any(|&(x, _)| x > 1200));
+        assert_eq!(points.last(), Some(&(1000, 0)));
+
+        let points = profile.overshoot(0.0).plan((0, 0), (1000, 0));
+        assert!(points.iter().all(|&(x, _)| (0..=1000).contains(&x)));
+    }
+
+    #[test]
+    fn zero_distance() {
+        let profile = MotionProfile::new().overshoot(0.3).seed(3);
+        let points = profile.plan((40, 50), (40, 50));
+        assert_eq!(points.len(), 36);
+        assert!(points.iter().all(|&p| p == (40, 50)));
+    }
+
+    #[test]
+    fn zero_duration() {
+        let profile = MotionProfile::new().duration(Duration::ZERO).seed(3);
+        assert_eq!(profile.plan((0, 0), (100, 100)), [(100, 100)]);
+        assert_eq!(profile.interval(1), Duration::ZERO);
+    }
+
+    #[test]
+    fn steps_are_limited_by_the_duration() {
+        let profile = MotionProfile::new().rate(1e12).seed(3);
+        assert_eq!(profile.plan((0, 0), (100, 100)).len(), 300);
+
+        let profile = profile.rate(f64::INFINITY);
+        assert_eq!(profile.plan((0, 0), (100, 100)), [(100, 100)]);
+    }
+}
@@ -137,6 +137,46 @@ pub enum Key {
     #[doc(alias = "9")]
     Nine,
 
+    /// The **-** key, right of the **0** key on a US keyboard.
+    #[doc(alias = "-")]
+    Minus,
+    /// The **=** key, right of the **-** key on a US keyboard.
+    #[doc(alias = "=")]
+    Equal,
+    /// The **[** key, right of the **P** key on a US keyboard.
+    #[doc(alias = "[")]
+    BracketLeft,
+    /// The **]** key, right of the **[** key on a US keyboard.
+    #[doc(alias = "]")]
+    BracketRight,
+    /// The **;** key, right of the **L** key on a US keyboard.
+    #[doc(alias = ";")]
+    Semicolon,
+    /// The **'** key, right of the **;** key on a US keyboard.
+    ///
+    /// This key is sometimes named **Apostrophe**.
+    #[doc(alias = "Apostrophe")]
+    Quote,
+    /// The **,** key, right of the **M** key on a US keyboard.
+    #[doc(alias = ",")]
+    Comma,
+    /// The **.** key, right of the **,** key on a US keyboard.
+    #[doc(alias = ".")]
+    Period,
+    /// The **/** key, right of the **.** key on a US keyboard.
+    #[doc(alias = "/")]
+    Slash,
+    /// The **\\** key, above the **Enter** key on a US keyboard.
+    #[doc(alias = "\\")]
+    Backslash,
+    /// The **\`** key, left of the **1** key on a US keyboard.
+    ///
+    /// This key is sometimes named **Backquote** or **Tilde**.
+    #[doc(alias = "`")]
+    #[doc(alias = "Backquote")]
+    #[doc(alias = "Tilde")]
+    Grave,
+
     /// The **Escape** key.
     Escape,
     /// The **Tab** key.
@@ -182,6 +222,27 @@ pub enum Key {
     /// The **Backspace** key.
     Backspace,
 
+    /// The **Print Screen** key.
+    ///
+    /// This key is sometimes named **SysRq** or **Snapshot**.
+    #[doc(alias = "SysRq")]
+    #[doc(alias = "Snapshot")]
+    PrintScreen,
+    /// The **Scroll Lock** key.
+    ScrollLock,
+    /// The **Pause** key.
+    ///
+    /// This key is sometimes named **Break**.
+    #[doc(alias = "Break")]
+    Pause,
+    /// The **Menu** key, which opens the context menu.
+    ///
+    /// This key is sometimes named **Application** or **Compose**.
+    #[doc(alias = "Menu")]
+    #[doc(alias = "Application")]
+    #[doc(alias = "Compose")]
+    ContextMenu,
+
     /// The **Insert** key.
     Insert,
     /// The **Delete** key.
@@ -224,6 +285,9 @@ pub enum Key {
     /// The **\*** key, on the numpad.
     #[doc(alias = "*")]
     NumpadMultiply,
+    /// The **-** key, on the numpad.
+    #[doc(alias = "-")]
+    NumpadSubtract,
     /// The **+** key, on the numpad.
     #[doc(alias = "+")]
     NumpadAdd,
@@ -267,4 +331,82 @@ pub enum Key {
     MediaStop,
     /// The **Media Pause** key.
     MediaPause,
+    /// The **Media Play** key.
+    ///
+    /// On Windows, this is the same key as [`Key::MediaPause`], which toggles playback.
+    MediaPlay,
+
+    /// The **Browser Back** key.
+    BrowserBack,
+    /// The **Browser Forward** key.
+    BrowserForward,
+    /// The **Browser Refresh** key.
+    BrowserRefresh,
+
+    /// The **Brightness Up** key, which increases the brightness of the screen.
+    ///
+    /// This key is not supported on Windows.
+    BrightnessUp,
+    /// The **Brightness Down** key, which decreases the brightness of the screen.
+    ///
+    /// This key is not supported on Windows.
+    BrightnessDown,
+
+    /// The **Mail** key, which launches the mail client.
+    LaunchMail,
+    /// The **Calculator** key, which launches the calculator.
+    LaunchCalculator,
+
+    /// The key that produces an X11 keysym, such as `0x1008ff1b` (`XF86XK_Search`).
+    ///
+    /// Keysyms are supported by the X11, Wayland and XDG desktop portal backends, as long as
+    /// the keyboard layout has a key that produces them. The Wayland backend binds any keysym
+    /// in the keymap of its virtual keyboard.
+    Keysym(u32),
+    /// The key with a raw platform key code, regardless of the keyboard layout.
+    ///
+    /// On Linux, this is an evdev key code, as defined in `linux/input-event-codes.h`. On
+    /// Windows, this is a scan code of the set 1, where extended keys have the `0xe0` prefix in
+    /// their high byte (for example, `0xe05d` for the **Menu** key).
+    ///
+    /// Scan codes are supported by all the backends but the Wayland one, whose virtual keyboard
+    /// has its own keymap.
+    Scancode(u32),
+}
+
+/// Lists every named key, and checks that none of them is missing from the list.
+#[cfg(test)]
+macro_rules! named_keys {
+    ($($key:ident),* $(,)?) => {
+        /// Every variant of [`Key`], besides [`Key::Keysym`] and [`Key::Scancode`].
+        ///
+        /// This is only used by the tests of the backends, which may all be disabled.
+        #[allow(dead_code)]
+        pub(crate) const NAMED_KEYS: &[Key] = &[$(Key::$key),*];
+
+        /// Does not compile when a variant of [`Key`] is missing from [`NAMED_KEYS`].
+        #[allow(dead_code)]
+        fn assert_named_keys_are_exhaustive(key: Key) {
+            match key {
+                $(Key::$key)|* => (),
+                Key::Keysym(_) | Key::Scancode(_) => (),
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+named_keys! {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, F1, F2, F3,
+    F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21, F22,
+    F23, F24, Zero, One, Two, Three, Four, Five, Six, Seven, Eight, Nine, Minus, Equal,
+    BracketLeft, BracketRight, Semicolon, Quote, Comma, Period, Slash, Backslash, Grave, Escape,
+    Tab, CapsLock, LeftShift, LeftControl, LeftAlt, LeftMeta, Space, RightMeta, RightAlt,
+    RightControl, RightShift, Enter, Backspace, PrintScreen, ScrollLock, Pause, ContextMenu,
+    Insert, Delete, Home, End, PageUp, PageDown, ArrowUp, ArrowDown, ArrowLeft, ArrowRight,
+    NumLock, NumpadEqual, NumpadDivide, NumpadMultiply, NumpadSubtract, NumpadAdd, NumpadEnter,
+    NumpadDecimal, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7,
+    Numpad8, Numpad9, VolumeUp, VolumeDown, VolumeMute, MediaNext, MediaPrevious, MediaStop,
+    MediaPause, MediaPlay, BrowserBack, BrowserForward, BrowserRefresh, BrightnessUp,
+    BrightnessDown, LaunchMail, LaunchCalculator,
 }
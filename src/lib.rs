@@ -10,6 +10,36 @@ mod platform;
 mod simulator;
 pub use self::simulator::*;
 
+mod builder;
+pub use self::builder::*;
+
+mod backend;
+pub use self::backend::*;
+
+mod capabilities;
+pub use self::capabilities::*;
+
+mod glide;
+pub use self::glide::*;
+
+mod drag;
+pub use self::drag::*;
+
+mod click;
+pub use self::click::*;
+
+mod screen;
+pub use self::screen::*;
+
+mod gesture;
+pub use self::gesture::*;
+
+mod stylus;
+pub use self::stylus::*;
+
+mod gamepad;
+pub use self::gamepad::*;
+
 mod key;
 pub use self::key::*;
 
@@ -18,3 +48,17 @@ pub use self::button::*;
 
 mod error;
 pub use self::error::*;
+
+#[cfg(any(
+    target_os = "windows",
+    feature = "x11",
+    feature = "x11rb",
+    feature = "uinput",
+    feature = "mock"
+))]
+mod motion;
+
+#[cfg(feature = "mock")]
+mod recording;
+#[cfg(feature = "mock")]
+pub use self::recording::*;
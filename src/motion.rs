@@ -0,0 +1,35 @@
+//! Helpers shared by the backends that simulate pointer motion.
+
+use std::sync::{Mutex, PoisonError};
+
+/// Accumulates the fractional part of relative pointer motions.
+///
+/// Most backends can only move the pointer by a whole number of pixels. Without accumulating the
+/// remainders, moving the pointer by `0.4` pixels ten times would not move it at all.
+#[derive(Debug, Default)]
+pub(crate) struct SubpixelAccumulator {
+    /// The part of the requested motion that has not been sent yet.
+    remainder: Mutex<(f64, f64)>,
+}
+
+impl SubpixelAccumulator {
+    /// Adds (`dx`, `dy`) to the accumulated remainder, and returns the whole number of pixels by
+    /// which the pointer should be moved.
+    ///
+    /// Deltas that are not finite are ignored.
+    pub fn take(&self, dx: f64, dy: f64) -> (i32, i32) {
+        let finite = |d: f64| if d.is_finite() { d } else { 0.0 };
+
+        // The remainder is always left in a consistent state.
+        let mut remainder = self
+            .remainder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let x = remainder.0 + finite(dx);
+        let y = remainder.1 + finite(dy);
+        *remainder = (x.fract(), y.fract());
+
+        // Float to integer casts saturate.
+        (x.trunc() as i32, y.trunc() as i32)
+    }
+}
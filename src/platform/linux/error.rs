@@ -1,3 +1,15 @@
+// When no backend is enabled, this error has no variant and the formatters are never used.
+#![cfg_attr(
+    not(any(
+        feature = "x11",
+        feature = "x11rb",
+        feature = "wayland",
+        feature = "portal",
+        feature = "uinput"
+    )),
+    allow(unused_variables)
+)]
+
 use std::fmt;
 use std::fmt::{Debug, Display};
 
@@ -5,31 +17,63 @@ use std::fmt::{Debug, Display};
 pub enum Error {
     #[cfg(feature = "x11")]
     X11(super::x11::Error),
+    #[cfg(feature = "x11rb")]
+    X11rb(super::x11rb::Error),
+    #[cfg(feature = "wayland")]
+    Wayland(super::wayland::Error),
+    #[cfg(feature = "portal")]
+    Portal(super::portal::Error),
+    #[cfg(feature = "uinput")]
+    Uinput(super::uinput::Error),
 }
 
 impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(e) => Debug::fmt(e, f),
+            Self::X11(ref e) => Debug::fmt(e, f),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref e) => Debug::fmt(e, f),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref e) => Debug::fmt(e, f),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref e) => Debug::fmt(e, f),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref e) => Debug::fmt(e, f),
         }
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(e) => Display::fmt(e, f),
+            Self::X11(ref e) => Display::fmt(e, f),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref e) => Display::fmt(e, f),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref e) => Display::fmt(e, f),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref e) => Display::fmt(e, f),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref e) => Display::fmt(e, f),
         }
     }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(e) => Some(e),
+            Self::X11(ref e) => Some(e),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref e) => Some(e),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref e) => Some(e),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref e) => Some(e),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref e) => Some(e),
         }
     }
 }
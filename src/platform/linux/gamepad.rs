@@ -0,0 +1,41 @@
+use crate::{GamepadAxis, GamepadButton};
+
+/// A virtual gamepad on the Linux platform.
+///
+/// Only the `uinput` backend can create gamepads, meaning that this type is uninhabited when
+/// that backend is disabled.
+pub enum Gamepad {
+    #[cfg(feature = "uinput")]
+    Uinput(super::uinput::Gamepad),
+}
+
+impl Gamepad {
+    /// Presses a button.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn press(&self, button: GamepadButton) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref gamepad) => gamepad.press(button).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Releases a button.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn release(&self, button: GamepadButton) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref gamepad) => gamepad.release(button).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Sets the value of an analog axis, in the range of the axis on an Xbox controller.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn set_axis(&self, axis: GamepadAxis, value: i32) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref gamepad) => {
+                gamepad.set_axis(axis, value).map_err(super::Error::Uinput)
+            }
+        }
+    }
+}
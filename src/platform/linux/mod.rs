@@ -1,13 +1,47 @@
 //! The platform implementation of the `keysim` crate on the Linux operating system.
 
-#[cfg(not(feature = "x11"))]
-compile_error!("at the moment, only the X11 window manager is supported by `keysim`");
+// Without any backend, only the recording simulator of the `mock` feature can be used.
+#[cfg(not(any(
+    feature = "x11",
+    feature = "wayland",
+    feature = "portal",
+    feature = "uinput",
+    feature = "x11rb",
+    feature = "mock"
+)))]
+compile_error!(
+    "on Linux, at least one of the `x11`, `x11rb`, `wayland`, `portal`, `uinput` or `mock` features must be enabled"
+);
 
 #[cfg(feature = "x11")]
 pub mod x11;
 
+#[cfg(feature = "x11rb")]
+pub mod x11rb;
+
+#[cfg(feature = "wayland")]
+pub mod wayland;
+
+#[cfg(feature = "portal")]
+pub mod portal;
+
+#[cfg(feature = "uinput")]
+pub mod uinput;
+
+#[cfg(any(feature = "wayland", feature = "portal", feature = "x11rb"))]
+mod utils;
+
+#[cfg(any(feature = "x11", feature = "x11rb"))]
+mod xauth;
+
+#[cfg(any(feature = "x11", feature = "x11rb"))]
+mod xsettings;
+
 mod simulator;
 pub use self::simulator::*;
 
+mod gamepad;
+pub use self::gamepad::*;
+
 mod error;
 pub use self::error::*;
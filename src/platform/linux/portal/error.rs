@@ -0,0 +1,62 @@
+use std::fmt;
+use std::fmt::Display;
+
+use crate::{Button, Key};
+
+/// An error that may occur when interacting with the XDG desktop portal.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occured when connecting to the D-Bus session bus.
+    Connect(zbus::Error),
+    /// A call to the portal failed.
+    DBus(zbus::Error),
+    /// The user refused to start the remote desktop session.
+    Cancelled,
+    /// The portal behaved in an unexpected way.
+    Unexpected,
+    /// No monitor shared with the portal covers the position.
+    ///
+    /// This also happens when the portal does not support screen casting.
+    UnsupportedPosition(i32, i32),
+    /// The key is not supported.
+    UnsupportedKey(Key),
+    /// The button is not supported.
+    UnsupportedButton(Button),
+    /// The character is not supported.
+    UnsupportedChar(char),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Connect(e) => write!(f, "failed to connect to the session bus: {e}"),
+            Self::DBus(e) => write!(f, "a call to the desktop portal failed: {e}"),
+            Self::Cancelled => f.write_str("the remote desktop session was refused"),
+            Self::Unexpected => f.write_str("the desktop portal behaved in an unexpected way"),
+            Self::UnsupportedPosition(x, y) => {
+                write!(
+                    f,
+                    "no monitor shared with the desktop portal covers ({x}, {y})"
+                )
+            }
+            Self::UnsupportedKey(k) => {
+                write!(f, "the desktop portal does not support the '{k:?}' key")
+            }
+            Self::UnsupportedButton(b) => {
+                write!(f, "the desktop portal does not support the '{b:?}' button")
+            }
+            Self::UnsupportedChar(c) => {
+                write!(f, "the desktop portal does not support the {c:?} char")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Connect(e) | Self::DBus(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! This module provides the implementation of `keysim` when using the XDG desktop portal.
+//!
+//! Inputs are sent through the `org.freedesktop.portal.RemoteDesktop` interface, which is the
+//! only sanctioned way to inject inputs from a sandboxed application, and the only one available
+//! on some Wayland compositors (such as GNOME's and KDE's).
+//!
+//! Creating a remote desktop session requires the approval of the user. Once approved, the
+//! session may be restored without prompting the user again, using a restore token persisted in
+//! `$XDG_STATE_HOME/keysim/portal-restore-token`.
+
+mod simulator;
+pub use self::simulator::*;
+
+mod error;
+pub use self::error::*;
+
+mod sys;
+mod utils;
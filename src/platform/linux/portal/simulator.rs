@@ -0,0 +1,203 @@
+use std::cell::Cell;
+
+use super::sys::RemoteDesktop;
+use super::utils;
+use crate::platform::utils::{button_to_evdev, char_to_keysym, key_to_keysym};
+use crate::{Button, Capabilities, Fidelity, Key, Screen, UnicodeSupport};
+
+/// The distance scrolled by a single wheel click, in the units of `NotifyPointerAxis`.
+///
+/// This matches the value used by GNOME to convert wheel clicks into smooth scrolling.
+const SCROLL_DISTANCE: f64 = 10.0;
+
+/// The simulator used when going through the XDG desktop portal.
+pub struct Simulator {
+    /// The remote desktop session used to simulate inputs.
+    desktop: RemoteDesktop,
+    /// The position the pointer was last moved to, if it is known.
+    ///
+    /// The portal does not report the position of the pointer.
+    position: Cell<Option<(i32, i32)>>,
+}
+
+impl Simulator {
+    /// Creates a new [`Simulator`] instance.
+    ///
+    /// Unless a previous session can be restored, this function blocks until the user accepts or
+    /// refuses to share their keyboard and pointer.
+    pub fn new() -> Result<Self, super::Error> {
+        let restore_token = utils::load_restore_token();
+        let (desktop, restore_token) = RemoteDesktop::start(restore_token.as_deref())?;
+
+        if let Some(restore_token) = restore_token {
+            utils::store_restore_token(&restore_token);
+        }
+
+        Ok(Self {
+            desktop,
+            position: Cell::new(None),
+        })
+    }
+
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        // The compositor can only type the keysyms available in the active keyboard layout.
+        Capabilities::new("portal", Fidelity::Native, 5, UnicodeSupport::Layout)
+    }
+
+    /// Sends a key event.
+    ///
+    /// Scan codes are sent as evdev key codes, which the compositor interprets according to the
+    /// active keyboard layout.
+    fn key_event(&self, key: Key, press: bool) -> Result<(), super::Error> {
+        match key {
+            Key::Scancode(code) => self.desktop.notify_keyboard_keycode(code, press),
+            _ => {
+                let keysym = key_to_keysym(key).ok_or(super::Error::UnsupportedKey(key))?;
+                self.desktop.notify_keyboard_keysym(keysym, press)
+            }
+        }
+    }
+
+    /// Sends a fake key press event to the focused window.
+    pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
+        self.key_event(key, true)
+    }
+
+    /// Sends a fake key release event to the focused window.
+    pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
+        self.key_event(key, false)
+    }
+
+    /// Sends a fake keystroke event to the focused window.
+    pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
+        self.key_event(key, true)?;
+        self.key_event(key, false)
+    }
+
+    /// Sends a fake button press.
+    pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.desktop.notify_pointer_button(code, true)
+    }
+
+    /// Sends a fake button release.
+    pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.desktop.notify_pointer_button(code, false)
+    }
+
+    /// Sends a fake button click.
+    pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.desktop.notify_pointer_button(code, true)?;
+        self.desktop.notify_pointer_button(code, false)
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`) of the compositor's layout.
+    ///
+    /// This requires the portal to support screen casting, as absolute positions are relative to
+    /// the screen cast stream of a monitor.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        let streams = &self.desktop.streams;
+
+        // When the position of the monitors is unknown, only a single monitor can be addressed.
+        let stream = match streams.iter().find(|stream| stream.contains(x, y)) {
+            Some(stream) => stream,
+            None if streams.len() == 1 && streams[0].position.is_none() => &streams[0],
+            None => return Err(super::Error::UnsupportedPosition(x, y)),
+        };
+
+        let (sx, sy) = stream.position.unwrap_or_default();
+        self.desktop.notify_pointer_motion_absolute(
+            stream.node_id,
+            (x - sx) as f64,
+            (y - sy) as f64,
+        )?;
+
+        self.position.set(Some((x, y)));
+        Ok(())
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        if dy != 0 {
+            self.desktop.notify_pointer_axis_discrete(0, dy)?;
+        }
+        if dx != 0 {
+            self.desktop.notify_pointer_axis_discrete(1, dx)?;
+        }
+        Ok(())
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks, which may be fractional.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        if !dx.is_finite() || !dy.is_finite() || (dx == 0.0 && dy == 0.0) {
+            return Ok(());
+        }
+
+        self.desktop
+            .notify_pointer_axis(dx * SCROLL_DISTANCE, dy * SCROLL_DISTANCE)
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) relative to its current position.
+    ///
+    /// The portal accepts fractional deltas, which are accumulated by the compositor.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        if !dx.is_finite() || !dy.is_finite() {
+            return Ok(());
+        }
+
+        // Pointer acceleration makes the resulting position unpredictable.
+        self.position.set(None);
+        self.desktop.notify_pointer_motion(dx, dy)
+    }
+
+    /// Returns the position the mouse pointer was last moved to by
+    /// [`move_mouse_to`](Self::move_mouse_to), if it has not been moved by a relative motion
+    /// since.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        Ok(self.position.get())
+    }
+
+    /// Returns the monitors shared through the screen cast portal, in the compositor's layout.
+    ///
+    /// The monitors whose size is not reported by the portal are omitted. A single monitor whose
+    /// position is not reported is assumed to be at the origin of the layout.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        let streams = &self.desktop.streams;
+
+        Ok(streams
+            .iter()
+            .filter_map(|stream| {
+                let (width, height) = stream.size.filter(|&(w, h)| w > 0 && h > 0)?;
+                let (x, y) = match stream.position {
+                    Some(position) => position,
+                    None if streams.len() == 1 => (0, 0),
+                    None => return None,
+                };
+                Some(Screen::new(x, y, width as u32, height as u32))
+            })
+            .collect())
+    }
+
+    /// Sends a specific unicode code-point.
+    ///
+    /// The compositor is responsible for pressing the modifiers required to produce the
+    /// character, if any.
+    pub fn send_char(&self, c: char) -> Result<(), super::Error> {
+        let keysym = char_to_keysym(c).ok_or(super::Error::UnsupportedChar(c))?;
+        self.desktop.notify_keyboard_keysym(keysym, true)?;
+        self.desktop.notify_keyboard_keysym(keysym, false)
+    }
+
+    /// Sends a collection of characters.
+    pub fn send_chars(&self, mut it: impl Iterator<Item = char>) -> Result<(), super::Error> {
+        it.try_for_each(|c| self.send_char(c))
+    }
+
+    /// Sends a string.
+    pub fn send_str(&self, s: &str) -> Result<(), super::Error> {
+        self.send_chars(s.chars())
+    }
+}
@@ -0,0 +1,539 @@
+//! Wraps the calls to the XDG desktop portal into safe function calls associated to the
+//! [`RemoteDesktop`] type.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PATH: &str = "/org/freedesktop/portal/desktop";
+const REMOTE_DESKTOP_INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+const SCREEN_CAST_INTERFACE: &str = "org.freedesktop.portal.ScreenCast";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+const SESSION_INTERFACE: &str = "org.freedesktop.portal.Session";
+
+/// The device types that can be requested from the portal.
+const DEVICE_KEYBOARD: u32 = 1;
+const DEVICE_POINTER: u32 = 2;
+
+/// The source types that can be requested from the screen cast portal.
+const SOURCE_MONITOR: u32 = 1;
+
+/// Permissions persist until explicitly revoked.
+const PERSIST_MODE_PERSISTENT: u32 = 2;
+
+/// The options passed to the methods of the portal.
+type Options<'a> = HashMap<&'a str, Value<'a>>;
+
+/// The results returned by the requests made to the portal.
+type Results = HashMap<String, OwnedValue>;
+
+/// Returns whether `e` is the error returned by the bus when the called method or its interface
+/// is not implemented.
+fn is_unknown_method(e: &zbus::Error) -> bool {
+    matches!(
+        e,
+        zbus::Error::MethodError(name, ..) if matches!(
+            name.as_str(),
+            "org.freedesktop.DBus.Error.UnknownMethod"
+                | "org.freedesktop.DBus.Error.UnknownInterface"
+        )
+    )
+}
+
+/// A screen cast stream of a remote desktop session.
+///
+/// Absolute pointer motion events are relative to one of those streams.
+#[derive(Debug, Clone, Copy)]
+pub struct Stream {
+    /// The PipeWire node ID of the stream.
+    pub node_id: u32,
+    /// The position of the monitor in the compositor's layout, when known.
+    pub position: Option<(i32, i32)>,
+    /// The size of the monitor, when known.
+    pub size: Option<(i32, i32)>,
+}
+
+impl Stream {
+    /// Parses a stream, as returned in the `streams` result of the `Start` method.
+    fn parse(value: &Value) -> Option<Self> {
+        let Value::Structure(structure) = value else {
+            return None;
+        };
+        let [Value::U32(node_id), Value::Dict(properties)] = structure.fields() else {
+            return None;
+        };
+
+        let pair = |key: &str| -> Option<(i32, i32)> {
+            let value: Value = properties.get(&key).ok()??;
+            <(i32, i32)>::try_from(value).ok()
+        };
+
+        Some(Self {
+            node_id: *node_id,
+            position: pair("position"),
+            size: pair("size"),
+        })
+    }
+
+    /// Returns whether the stream covers the position (`x`, `y`) of the compositor's layout.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        match (self.position, self.size) {
+            (Some((sx, sy)), Some((w, h))) => {
+                (sx..sx + w).contains(&x) && (sy..sy + h).contains(&y)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A remote desktop session, opened through the XDG desktop portal.
+pub struct RemoteDesktop {
+    connection: Connection,
+    proxy: Proxy<'static>,
+
+    /// The handle of the session.
+    session: OwnedObjectPath,
+
+    /// The screen cast streams of the session, one per monitor.
+    ///
+    /// This is empty when the portal does not support screen casting.
+    pub streams: Vec<Stream>,
+
+    /// The number of requests made so far, used to generate unique request tokens.
+    requests: Cell<u32>,
+}
+
+impl RemoteDesktop {
+    /// Creates and starts a new remote desktop session with access to the keyboard and the
+    /// pointer.
+    ///
+    /// When the portal supports it, the monitors are also selected for screen casting. Absolute
+    /// pointer motion is only possible relative to a screen cast stream.
+    ///
+    /// When `restore_token` is provided, the portal attempts to restore the session it refers to
+    /// without prompting the user. On success, the token that can be used to restore the new
+    /// session is returned alongside it.
+    pub fn start(restore_token: Option<&str>) -> Result<(Self, Option<String>), super::Error> {
+        let connection = Connection::session().map_err(super::Error::Connect)?;
+        Self::start_with(connection, restore_token)
+    }
+
+    /// Like [`start`](Self::start), but talks to the portal through `connection`.
+    fn start_with(
+        connection: Connection,
+        restore_token: Option<&str>,
+    ) -> Result<(Self, Option<String>), super::Error> {
+        let proxy = Proxy::new(&connection, DESTINATION, PATH, REMOTE_DESKTOP_INTERFACE)
+            .map_err(super::Error::DBus)?;
+
+        let mut ret = Self {
+            connection,
+            proxy,
+            session: OwnedObjectPath::default(),
+            streams: Vec::new(),
+            requests: Cell::new(0),
+        };
+
+        let results = ret.request(|token, proxy| {
+            let options = Options::from([
+                ("handle_token", Value::from(token)),
+                ("session_handle_token", Value::from("keysim")),
+            ]);
+            proxy.call("CreateSession", &(options,))
+        })?;
+
+        // Even though it is an object path, the session handle is sent back as a string.
+        let session = results
+            .get("session_handle")
+            .and_then(|value| <&str>::try_from(value).ok())
+            .and_then(|handle| ObjectPath::try_from(handle).ok())
+            .ok_or(super::Error::Unexpected)?;
+        ret.session = session.into();
+
+        ret.request(|token, proxy| {
+            let mut options = Options::from([
+                ("handle_token", Value::from(token)),
+                ("types", Value::from(DEVICE_KEYBOARD | DEVICE_POINTER)),
+                ("persist_mode", Value::from(PERSIST_MODE_PERSISTENT)),
+            ]);
+            if let Some(restore_token) = restore_token {
+                options.insert("restore_token", Value::from(restore_token));
+            }
+            proxy.call("SelectDevices", &(&ret.session, options))
+        })?;
+
+        // Not all portals implement the screen cast interface. The session remains usable
+        // without it, except for absolute pointer motion. Any other failure, including the user
+        // refusing to share their monitors, aborts the session.
+        let screen_cast = Proxy::new(&ret.connection, DESTINATION, PATH, SCREEN_CAST_INTERFACE)
+            .map_err(super::Error::DBus)?;
+        let selected = ret.request(|token, _| {
+            let options = Options::from([
+                ("handle_token", Value::from(token)),
+                ("types", Value::from(SOURCE_MONITOR)),
+                ("multiple", Value::from(true)),
+            ]);
+            screen_cast.call("SelectSources", &(&ret.session, options))
+        });
+        match selected {
+            Ok(_) => (),
+            Err(super::Error::DBus(e)) if is_unknown_method(&e) => (),
+            Err(e) => return Err(e),
+        }
+
+        let results = ret.request(|token, proxy| {
+            let options = Options::from([("handle_token", Value::from(token))]);
+            proxy.call("Start", &(&ret.session, "", options))
+        })?;
+
+        ret.streams = results
+            .get("streams")
+            .and_then(|value| <&zbus::zvariant::Array>::try_from(value).ok())
+            .map(|streams| streams.iter().filter_map(Stream::parse).collect())
+            .unwrap_or_default();
+
+        let restore_token = results
+            .get("restore_token")
+            .and_then(|value| <&str>::try_from(value).ok())
+            .map(str::to_owned);
+
+        Ok((ret, restore_token))
+    }
+
+    /// Makes a request to the portal and waits for its response.
+    ///
+    /// `call` is given a unique token that it must pass as the `handle_token` option of the
+    /// method it calls.
+    fn request(
+        &self,
+        call: impl FnOnce(&str, &Proxy) -> zbus::Result<OwnedObjectPath>,
+    ) -> Result<Results, super::Error> {
+        let id = self.requests.get();
+        self.requests.set(id + 1);
+        let token = format!("keysim{id}");
+
+        // We need to subscribe to the response before making the request, otherwise we might
+        // miss it. The path of the request object is derived from our unique name and the token.
+        let sender = self
+            .connection
+            .unique_name()
+            .ok_or(super::Error::Unexpected)?
+            .trim_start_matches(':')
+            .replace('.', "_");
+        let path = format!("{PATH}/request/{sender}/{token}");
+
+        let request = Proxy::new(&self.connection, DESTINATION, path, REQUEST_INTERFACE)
+            .map_err(super::Error::DBus)?;
+        let mut responses = request
+            .receive_signal("Response")
+            .map_err(super::Error::DBus)?;
+
+        call(&token, &self.proxy).map_err(super::Error::DBus)?;
+
+        let message = responses.next().ok_or(super::Error::Unexpected)?;
+        let (response, results): (u32, Results) =
+            message.body().deserialize().map_err(super::Error::DBus)?;
+
+        match response {
+            0 => Ok(results),
+            1 => Err(super::Error::Cancelled),
+            _ => Err(super::Error::Unexpected),
+        }
+    }
+
+    /// Wraps the `NotifyKeyboardKeycode` method.
+    pub fn notify_keyboard_keycode(&self, keycode: u32, press: bool) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyKeyboardKeycode",
+                &(&self.session, Options::new(), keycode as i32, press as u32),
+            )
+            .map_err(super::Error::DBus)
+    }
+
+    /// Wraps the `NotifyKeyboardKeysym` method.
+    pub fn notify_keyboard_keysym(&self, keysym: u32, press: bool) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyKeyboardKeysym",
+                &(&self.session, Options::new(), keysym as i32, press as u32),
+            )
+            .map_err(super::Error::DBus)
+    }
+
+    /// Wraps the `NotifyPointerMotion` method.
+    pub fn notify_pointer_motion(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyPointerMotion",
+                &(&self.session, Options::new(), dx, dy),
+            )
+            .map_err(super::Error::DBus)
+    }
+
+    /// Wraps the `NotifyPointerMotionAbsolute` method.
+    ///
+    /// The position (`x`, `y`) is relative to the top-left corner of the stream `node_id`.
+    pub fn notify_pointer_motion_absolute(
+        &self,
+        node_id: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyPointerMotionAbsolute",
+                &(&self.session, Options::new(), node_id, x, y),
+            )
+            .map_err(super::Error::DBus)
+    }
+
+    /// Wraps the `NotifyPointerAxis` method.
+    pub fn notify_pointer_axis(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyPointerAxis",
+                &(&self.session, Options::new(), dx, dy),
+            )
+            .map_err(super::Error::DBus)
+    }
+
+    /// Wraps the `NotifyPointerAxisDiscrete` method.
+    ///
+    /// `axis` is `0` for vertical scrolling and `1` for horizontal scrolling.
+    pub fn notify_pointer_axis_discrete(&self, axis: u32, steps: i32) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyPointerAxisDiscrete",
+                &(&self.session, Options::new(), axis, steps),
+            )
+            .map_err(super::Error::DBus)
+    }
+
+    /// Wraps the `NotifyPointerButton` method.
+    pub fn notify_pointer_button(&self, button: u32, press: bool) -> Result<(), super::Error> {
+        self.proxy
+            .call::<_, _, ()>(
+                "NotifyPointerButton",
+                &(&self.session, Options::new(), button as i32, press as u32),
+            )
+            .map_err(super::Error::DBus)
+    }
+}
+
+impl Drop for RemoteDesktop {
+    fn drop(&mut self) {
+        // The session might not have been created if `start` failed early.
+        if self.session.as_str() == "/" {
+            return;
+        }
+
+        let _ = self.connection.call_method(
+            Some(DESTINATION),
+            &self.session,
+            Some(SESSION_INTERFACE),
+            "Close",
+            &(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+
+    use zbus::blocking::connection::Builder;
+    use zbus::message::Header;
+    use zbus::object_server::SignalEmitter;
+
+    use super::*;
+
+    /// A private bus, run by a `dbus-daemon` process for the duration of a test.
+    struct Bus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl Bus {
+        /// Starts a new bus.
+        fn start() -> Self {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .expect("failed to start `dbus-daemon`, which this test needs");
+
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+            let address = address.trim().to_owned();
+            Self { daemon, address }
+        }
+
+        /// Opens a new connection to the bus.
+        fn connect(&self) -> Builder<'_> {
+            Builder::address(self.address.as_str()).unwrap()
+        }
+    }
+
+    impl Drop for Bus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    /// Answers the request of `header` with `response` and `results`, like the portal does.
+    async fn respond(
+        emitter: &SignalEmitter<'_>,
+        header: &Header<'_>,
+        options: &HashMap<String, OwnedValue>,
+        response: u32,
+        results: Options<'_>,
+    ) -> zbus::fdo::Result<OwnedObjectPath> {
+        let token = options
+            .get("handle_token")
+            .and_then(|value| <&str>::try_from(value).ok())
+            .unwrap();
+        let unique_name = header.sender().unwrap().as_str();
+        let sender = unique_name.trim_start_matches(':').replace('.', "_");
+        let path = ObjectPath::try_from(format!("{PATH}/request/{sender}/{token}")).unwrap();
+
+        emitter
+            .connection()
+            .emit_signal(
+                Some(unique_name),
+                &path,
+                REQUEST_INTERFACE,
+                "Response",
+                &(response, results),
+            )
+            .await?;
+        Ok(path.into())
+    }
+
+    /// A fake implementation of the remote desktop portal.
+    struct MockRemoteDesktop;
+
+    #[zbus::interface(name = "org.freedesktop.portal.RemoteDesktop")]
+    impl MockRemoteDesktop {
+        async fn create_session(
+            &self,
+            options: HashMap<String, OwnedValue>,
+            #[zbus(header)] header: Header<'_>,
+            #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+        ) -> zbus::fdo::Result<OwnedObjectPath> {
+            let handle = format!("{PATH}/session/keysim");
+            let results = Options::from([("session_handle", Value::from(handle))]);
+            respond(&emitter, &header, &options, 0, results).await
+        }
+
+        async fn select_devices(
+            &self,
+            _session: OwnedObjectPath,
+            options: HashMap<String, OwnedValue>,
+            #[zbus(header)] header: Header<'_>,
+            #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+        ) -> zbus::fdo::Result<OwnedObjectPath> {
+            respond(&emitter, &header, &options, 0, Options::new()).await
+        }
+
+        async fn start(
+            &self,
+            _session: OwnedObjectPath,
+            _parent_window: String,
+            options: HashMap<String, OwnedValue>,
+            #[zbus(header)] header: Header<'_>,
+            #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+        ) -> zbus::fdo::Result<OwnedObjectPath> {
+            let properties = HashMap::from([
+                ("position", Value::from((1920, 0))),
+                ("size", Value::from((1280, 1024))),
+            ]);
+            let results = Options::from([
+                ("streams", Value::from(vec![(42u32, properties)])),
+                ("restore_token", Value::from("token")),
+            ]);
+            respond(&emitter, &header, &options, 0, results).await
+        }
+    }
+
+    /// A fake implementation of the screen cast portal, which answers `SelectSources` with
+    /// `response`.
+    struct MockScreenCast {
+        response: u32,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.portal.ScreenCast")]
+    impl MockScreenCast {
+        async fn select_sources(
+            &self,
+            _session: OwnedObjectPath,
+            options: HashMap<String, OwnedValue>,
+            #[zbus(header)] header: Header<'_>,
+            #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+        ) -> zbus::fdo::Result<OwnedObjectPath> {
+            respond(&emitter, &header, &options, self.response, Options::new()).await
+        }
+    }
+
+    /// Starts a session against a mock portal, which implements the screen cast interface when
+    /// `screen_cast` is set.
+    fn start(
+        screen_cast: Option<MockScreenCast>,
+    ) -> Result<(RemoteDesktop, Option<String>), super::super::Error> {
+        let bus = Bus::start();
+
+        let mut portal = bus
+            .connect()
+            .name(DESTINATION)
+            .unwrap()
+            .serve_at(PATH, MockRemoteDesktop)
+            .unwrap();
+        if let Some(screen_cast) = screen_cast {
+            portal = portal.serve_at(PATH, screen_cast).unwrap();
+        }
+        let _portal = portal.build().unwrap();
+
+        let connection = bus.connect().build().unwrap();
+        RemoteDesktop::start_with(connection, None)
+    }
+
+    #[test]
+    #[ignore = "needs `dbus-daemon`, run with `--ignored`"]
+    fn start_without_screen_cast() {
+        let result = start(None);
+
+        // The missing interface is not an error.
+        let (_remote, restore_token) = result.unwrap();
+        assert_eq!(restore_token.as_deref(), Some("token"));
+    }
+
+    #[test]
+    #[ignore = "needs `dbus-daemon`, run with `--ignored`"]
+    fn start_with_screen_cast() {
+        let result = start(Some(MockScreenCast { response: 0 }));
+
+        let (remote, restore_token) = result.unwrap();
+        assert_eq!(remote.streams.len(), 1);
+        assert_eq!(remote.streams[0].node_id, 42);
+        assert!(remote.streams[0].contains(1920, 0));
+        assert!(remote.streams[0].contains(3199, 1023));
+        assert!(!remote.streams[0].contains(0, 0));
+        assert_eq!(restore_token.as_deref(), Some("token"));
+    }
+
+    #[test]
+    #[ignore = "needs `dbus-daemon`, run with `--ignored`"]
+    fn refused_screen_cast_cancels_the_session() {
+        let result = start(Some(MockScreenCast { response: 1 }));
+
+        assert!(matches!(result, Err(super::super::Error::Cancelled)));
+    }
+}
@@ -0,0 +1,44 @@
+//! This module provides utility functions to persist the restore token of the portal session.
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Returns the path of the file in which the restore token is persisted.
+fn restore_token_path() -> Option<PathBuf> {
+    let state_home = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state"))
+        })?;
+
+    Some(state_home.join("keysim").join("portal-restore-token"))
+}
+
+/// Loads the restore token persisted by a previous session, if any.
+pub fn load_restore_token() -> Option<String> {
+    let token = fs::read_to_string(restore_token_path()?).ok()?;
+    let token = token.trim();
+
+    if token.is_empty() {
+        None
+    } else {
+        Some(token.to_owned())
+    }
+}
+
+/// Persists the restore token of the current session.
+///
+/// Failing to persist the token is not an error: the user will simply be prompted again the next
+/// time a session is created.
+pub fn store_restore_token(token: &str) {
+    let Some(path) = restore_token_path() else {
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    let _ = fs::write(path, token);
+}
@@ -1,103 +1,584 @@
-use crate::{Button, Key};
+// When no backend is enabled, the platform simulator cannot be created, and none of the
+// arguments below are used.
+#![cfg_attr(
+    not(any(
+        feature = "x11",
+        feature = "x11rb",
+        feature = "wayland",
+        feature = "portal",
+        feature = "uinput"
+    )),
+    allow(unused_variables)
+)]
+
+use std::time::Duration;
+
+#[cfg(any(feature = "x11", feature = "x11rb"))]
+use crate::X11Authority;
+use crate::{
+    Backend, Button, Capabilities, GamepadOptions, Key, Screen, SimulatorBuilder, StylusTool,
+};
 
 /// The simulator on the Linux platform.
 pub enum Simulator {
     #[cfg(feature = "x11")]
     X11(super::x11::Simulator),
+    #[cfg(feature = "x11rb")]
+    X11rb(super::x11rb::Simulator),
+    #[cfg(feature = "wayland")]
+    Wayland(super::wayland::Simulator),
+    #[cfg(feature = "portal")]
+    Portal(super::portal::Simulator),
+    #[cfg(feature = "uinput")]
+    Uinput(super::uinput::Simulator),
 }
 
 impl Simulator {
-    /// Creates a new [`Simulator`] for the current window manager.
-    pub fn new() -> Result<Self, super::Error> {
-        if let Some(kind) = std::env::var_os("XDG_SESSION_TYPE") {
-            #[cfg(feature = "x11")]
-            if kind == "x11" {
-                return Self::new_x11();
-            }
+    /// Returns the backends worth trying for the current session, in order of preference.
+    ///
+    /// The `XDG_SESSION_TYPE` environment variable is used to determine which backends are worth
+    /// trying. Only the backends that have been enabled are returned.
+    pub fn default_backends() -> Vec<Backend> {
+        let mut backends = match std::env::var_os("XDG_SESSION_TYPE") {
+            Some(kind) if kind == "x11" => vec![Backend::X11, Backend::X11rb],
+            // Most compositors do not support the virtual input protocols. Prefer uinput over
+            // XWayland, which cannot reach native Wayland windows.
+            Some(kind) if kind == "wayland" => vec![
+                Backend::Wayland,
+                Backend::Portal,
+                Backend::Uinput,
+                Backend::X11,
+                Backend::X11rb,
+            ],
+            _ => vec![
+                Backend::X11,
+                Backend::X11rb,
+                Backend::Wayland,
+                Backend::Portal,
+            ],
+        };
+
+        // XTEST and the virtual input protocols are of no use outside of a graphical session,
+        // but uinput always works given the right permissions.
+        if !backends.contains(&Backend::Uinput) {
+            backends.push(Backend::Uinput);
         }
 
-        // If we were not able to determine which window manager to use, default to X11 if enabled.
-        #[cfg(feature = "x11")]
-        Self::new_x11()
+        backends.retain(|backend| backend.is_enabled());
+        backends
+    }
+
+    /// Creates a new [`Simulator`] instance using `backend`, configured by `builder`.
+    ///
+    /// Returns `None` if `backend` has not been enabled.
+    #[cfg_attr(
+        not(any(feature = "x11", feature = "x11rb", feature = "uinput")),
+        allow(unused_variables)
+    )]
+    pub fn with_backend(
+        backend: Backend,
+        builder: &SimulatorBuilder,
+    ) -> Option<Result<Self, super::Error>> {
+        match backend {
+            #[cfg(feature = "x11")]
+            Backend::X11 => Some(Self::new_x11(
+                builder.x11_display.as_deref(),
+                builder.x11_authority.as_ref(),
+            )),
+            #[cfg(feature = "x11rb")]
+            Backend::X11rb => Some(Self::new_x11rb(
+                builder.x11_display.as_deref(),
+                builder.x11_authority.as_ref(),
+            )),
+            #[cfg(feature = "wayland")]
+            Backend::Wayland => Some(Self::new_wayland()),
+            #[cfg(feature = "portal")]
+            Backend::Portal => Some(Self::new_portal()),
+            #[cfg(feature = "uinput")]
+            Backend::Uinput => Some(Self::new_uinput(builder.uinput_screen_size)),
+            _ => None,
+        }
     }
 
     /// Creates a new [`Simulator`] instance for the X11 window manager.
+    ///
+    /// When `display` or `authority` is `None`, the `DISPLAY` and `XAUTHORITY` environment
+    /// variables are used instead.
     #[cfg(feature = "x11")]
-    pub fn new_x11() -> Result<Self, super::Error> {
-        super::x11::Simulator::new()
+    pub fn new_x11(
+        display: Option<&str>,
+        authority: Option<&X11Authority>,
+    ) -> Result<Self, super::Error> {
+        super::x11::Simulator::new(display, authority)
             .map(Self::X11)
             .map_err(super::Error::X11)
     }
 
+    /// Creates a new [`Simulator`] instance for the X11 window manager, using the pure-Rust
+    /// `x11rb` implementation of the protocol.
+    #[cfg(feature = "x11rb")]
+    pub fn new_x11rb(
+        display: Option<&str>,
+        authority: Option<&X11Authority>,
+    ) -> Result<Self, super::Error> {
+        super::x11rb::Simulator::new(display, authority)
+            .map(Self::X11rb)
+            .map_err(super::Error::X11rb)
+    }
+
+    /// Creates a new [`Simulator`] instance for a Wayland compositor.
+    #[cfg(feature = "wayland")]
+    pub fn new_wayland() -> Result<Self, super::Error> {
+        super::wayland::Simulator::new()
+            .map(Self::Wayland)
+            .map_err(super::Error::Wayland)
+    }
+
+    /// Creates a new [`Simulator`] instance that goes through the XDG desktop portal.
+    #[cfg(feature = "portal")]
+    pub fn new_portal() -> Result<Self, super::Error> {
+        super::portal::Simulator::new()
+            .map(Self::Portal)
+            .map_err(super::Error::Portal)
+    }
+
+    /// Creates a new [`Simulator`] instance backed by a `uinput` virtual device.
+    ///
+    /// `screen_size` is the size of the desktop, used to map absolute positions.
+    #[cfg(feature = "uinput")]
+    pub fn new_uinput(screen_size: Option<(u32, u32)>) -> Result<Self, super::Error> {
+        super::uinput::Simulator::new(screen_size)
+            .map(Self::Uinput)
+            .map_err(super::Error::Uinput)
+    }
+
+    /// Creates a new [`Simulator`] instance that writes raw `uinput` events to `file`.
+    #[cfg(feature = "uinput")]
+    pub fn new_uinput_file(file: std::fs::File) -> Self {
+        Self::Uinput(super::uinput::Simulator::from_file(file))
+    }
+
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.capabilities(),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.capabilities(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.capabilities(),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.capabilities(),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.capabilities(),
+        }
+    }
+
     /// Simulates a key press event.
     pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.press_key(key).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.press_key(key).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.press_key(key).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.press_key(key).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.press_key(key).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.press_key(key).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates a key release event.
     pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.release_key(key).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.release_key(key).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.release_key(key).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.release_key(key).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.release_key(key).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.release_key(key).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates a keystroke event.
     pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.send_key(key).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.send_key(key).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.send_key(key).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.send_key(key).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.send_key(key).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.send_key(key).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates a button press event.
     pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.press_button(button).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.press_button(button).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.press_button(button).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.press_button(button).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.press_button(button).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.press_button(button).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates a key release event.
     pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.release_button(button).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.release_button(button).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.release_button(button).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.release_button(button).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.release_button(button).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.release_button(button).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates a keystroke event.
     pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
-        match self {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.send_button(button).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.send_button(button).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.send_button(button).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.send_button(button).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.send_button(button).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Simulates a mouse motion to an absolute position.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.move_mouse_to(x, y).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.move_mouse_to(x, y).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.move_mouse_to(x, y).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.move_mouse_to(x, y).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.move_mouse_to(x, y).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Simulates wheel clicks.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.scroll(dx, dy).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.scroll(dx, dy).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.scroll(dx, dy).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.scroll(dx, dy).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.scroll(dx, dy).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Simulates a smooth scroll.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.scroll_smooth(dx, dy).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.scroll_smooth(dx, dy).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.scroll_smooth(dx, dy).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.scroll_smooth(dx, dy).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.scroll_smooth(dx, dy).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Simulates a relative mouse motion.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.move_mouse_by(dx, dy).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.move_mouse_by(dx, dy).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.move_mouse_by(dx, dy).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.move_mouse_by(dx, dy).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.move_mouse_by(dx, dy).map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Returns the position of the mouse pointer, if it is known.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.mouse_position().map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.mouse_position().map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.mouse_position().map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.mouse_position().map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.mouse_position().map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Returns the monitors that make up the desktop.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(ref sim) => sim.screens().map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.screens().map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.screens().map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.screens().map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.screens().map_err(super::Error::Uinput),
+        }
+    }
+
+    /// Puts a finger identified by `slot` on the screen at the position (`x`, `y`).
+    ///
+    /// Returns `None` if the backend cannot simulate touch inputs.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn touch_down(&self, slot: u32, x: i32, y: i32) -> Option<Result<(), super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => Some(sim.touch_down(slot, x, y).map_err(super::Error::Uinput)),
+        }
+    }
+
+    /// Moves the finger identified by `slot` to the position (`x`, `y`).
+    ///
+    /// Returns `None` if the backend cannot simulate touch inputs.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn touch_move(&self, slot: u32, x: i32, y: i32) -> Option<Result<(), super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => Some(sim.touch_move(slot, x, y).map_err(super::Error::Uinput)),
+        }
+    }
+
+    /// Lifts the finger identified by `slot` from the screen.
+    ///
+    /// Returns `None` if the backend cannot simulate touch inputs.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn touch_up(&self, slot: u32) -> Option<Result<(), super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => Some(sim.touch_up(slot).map_err(super::Error::Uinput)),
+        }
+    }
+
+    /// Puts the fingers of a gesture on the touchpad at the positions `contacts`, lifting the
+    /// other ones.
+    ///
+    /// Returns `None` if the backend cannot simulate touchpad gestures.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn touchpad_frame(&self, contacts: &[(i32, i32)]) -> Option<Result<(), super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => {
+                Some(sim.touchpad_frame(contacts).map_err(super::Error::Uinput))
+            }
+        }
+    }
+
+    /// Moves the stylus, bringing `tool` close to the surface if needed.
+    ///
+    /// Returns `None` if the backend cannot simulate a stylus.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn stylus(
+        &self,
+        tool: StylusTool,
+        x: i32,
+        y: i32,
+        pressure: u16,
+        tilt: (i8, i8),
+    ) -> Option<Result<(), super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => Some(
+                sim.stylus(tool, x, y, pressure, tilt)
+                    .map_err(super::Error::Uinput),
+            ),
+        }
+    }
+
+    /// Moves the stylus away from the surface.
+    ///
+    /// Returns `None` if the backend cannot simulate a stylus.
+    pub fn stylus_leave(&self) -> Option<Result<(), super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => Some(sim.stylus_leave().map_err(super::Error::Uinput)),
+        }
+    }
+
+    /// Creates a new virtual gamepad identified by `options`.
+    ///
+    /// Returns `None` if the backend cannot create gamepads.
+    #[cfg_attr(not(feature = "uinput"), allow(unused_variables))]
+    pub fn create_gamepad(
+        &self,
+        options: &GamepadOptions,
+    ) -> Option<Result<super::Gamepad, super::Error>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Self::X11(_) => None,
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(_) => None,
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => Some(
+                sim.create_gamepad(options)
+                    .map(super::Gamepad::Uinput)
+                    .map_err(super::Error::Uinput),
+            ),
+        }
+    }
+
+    /// Returns the double-click time of the desktop, if it is known.
+    pub fn double_click_time(&self) -> Option<Duration> {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.send_button(button).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.double_click_time(),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.double_click_time(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(_) => None,
+            #[cfg(feature = "portal")]
+            Self::Portal(_) => None,
+            #[cfg(feature = "uinput")]
+            Self::Uinput(_) => None,
         }
     }
 
     /// Simulates a character being typed.
     pub fn send_char(&self, c: char) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.send_char(c).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.send_char(c).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.send_char(c).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.send_char(c).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.send_char(c).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.send_char(c).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates characters being typed.
     pub fn send_chars(&self, it: impl Iterator<Item = char>) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.send_chars(it).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.send_chars(it).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.send_chars(it).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.send_chars(it).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.send_chars(it).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.send_chars(it).map_err(super::Error::Uinput),
         }
     }
 
     /// Simulates characters being typed.
     pub fn send_str(&self, s: &str) -> Result<(), super::Error> {
-        match self {
+        match *self {
             #[cfg(feature = "x11")]
-            Self::X11(sim) => sim.send_str(s).map_err(super::Error::X11),
+            Self::X11(ref sim) => sim.send_str(s).map_err(super::Error::X11),
+            #[cfg(feature = "x11rb")]
+            Self::X11rb(ref sim) => sim.send_str(s).map_err(super::Error::X11rb),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(ref sim) => sim.send_str(s).map_err(super::Error::Wayland),
+            #[cfg(feature = "portal")]
+            Self::Portal(ref sim) => sim.send_str(s).map_err(super::Error::Portal),
+            #[cfg(feature = "uinput")]
+            Self::Uinput(ref sim) => sim.send_str(s).map_err(super::Error::Uinput),
         }
     }
 }
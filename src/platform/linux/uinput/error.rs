@@ -0,0 +1,53 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+
+use crate::{Button, Key};
+
+/// An error that may occur when simulating inputs through the `uinput` kernel module.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occured when creating the virtual input device.
+    ///
+    /// This usually means that `/dev/uinput` does not exist, or that the current user is not
+    /// allowed to write to it.
+    CreateDevice(io::Error),
+    /// An error occured when writing events to the virtual input device.
+    Write(io::Error),
+    /// The size of the screen is unknown, making absolute pointer motion impossible.
+    UnknownScreenSize,
+    /// The key is not supported.
+    UnsupportedKey(Key),
+    /// The button is not supported.
+    UnsupportedButton(Button),
+    /// The character is not supported.
+    UnsupportedChar(char),
+    /// The touch slot exceeds the number of contacts the virtual touchscreen can track.
+    UnsupportedSlot(u32),
+    /// The touch slot is not in contact with the screen.
+    InactiveSlot(u32),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CreateDevice(e) => write!(f, "failed to create a uinput device: {e}"),
+            Self::Write(e) => write!(f, "failed to write to the uinput device: {e}"),
+            Self::UnknownScreenSize => f.write_str("the size of the screen is unknown"),
+            Self::UnsupportedKey(k) => write!(f, "uinput does not support the '{k:?}' key"),
+            Self::UnsupportedButton(b) => write!(f, "uinput does not support the '{b:?}' button"),
+            Self::UnsupportedChar(c) => write!(f, "uinput does not support the {c:?} char"),
+            Self::UnsupportedSlot(s) => write!(f, "uinput does not support the touch slot {s}"),
+            Self::InactiveSlot(s) => write!(f, "the touch slot {s} is not touching the screen"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CreateDevice(e) | Self::Write(e) => Some(e),
+            _ => None,
+        }
+    }
+}
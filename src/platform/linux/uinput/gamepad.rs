@@ -0,0 +1,80 @@
+use std::sync::{Mutex, PoisonError};
+
+use super::sys::{self, Device};
+use super::utils;
+use crate::{gamepad, GamepadAxis, GamepadButton, GamepadOptions};
+
+/// A virtual gamepad created through the `uinput` kernel module.
+pub struct Gamepad {
+    /// The virtual device.
+    device: Device,
+    /// Whether the up, down, left and right directions of the D-pad are pressed.
+    dpad: Mutex<[bool; 4]>,
+}
+
+impl Gamepad {
+    /// Creates a new virtual gamepad identified by `options`.
+    pub(super) fn new(options: &GamepadOptions) -> Result<Self, super::Error> {
+        let device = Device::create_gamepad(
+            options.name.as_bytes(),
+            options.vendor,
+            options.product,
+            gamepad::STICK_MAX,
+            gamepad::TRIGGER_MAX,
+        )?;
+
+        Ok(Self::from_device(device))
+    }
+
+    /// Creates a new [`Gamepad`] that sends its events to `device`.
+    pub(super) fn from_device(device: Device) -> Self {
+        Self {
+            device,
+            dpad: Mutex::default(),
+        }
+    }
+
+    /// Presses or releases a button.
+    fn button(&self, button: GamepadButton, press: bool) -> Result<(), super::Error> {
+        if let Some(code) = utils::gamepad_button_to_evdev(button) {
+            self.device.emit(sys::EV_KEY, code, press as i32)?;
+            return self.device.synchronize();
+        }
+
+        let mut dpad = self.dpad.lock().unwrap_or_else(PoisonError::into_inner);
+        let (index, code) = match button {
+            GamepadButton::DPadUp => (0, sys::ABS_HAT0Y),
+            GamepadButton::DPadDown => (1, sys::ABS_HAT0Y),
+            GamepadButton::DPadLeft => (2, sys::ABS_HAT0X),
+            // Only `DPadRight` is left, the other buttons have been handled above.
+            _ => (3, sys::ABS_HAT0X),
+        };
+        dpad[index] = press;
+
+        // Opposite directions cancel each other out.
+        let [up, down, left, right] = *dpad;
+        let value = match code {
+            sys::ABS_HAT0Y => down as i32 - up as i32,
+            _ => right as i32 - left as i32,
+        };
+        self.device.emit(sys::EV_ABS, code, value)?;
+        self.device.synchronize()
+    }
+
+    /// Presses a button.
+    pub fn press(&self, button: GamepadButton) -> Result<(), super::Error> {
+        self.button(button, true)
+    }
+
+    /// Releases a button.
+    pub fn release(&self, button: GamepadButton) -> Result<(), super::Error> {
+        self.button(button, false)
+    }
+
+    /// Sets the value of an analog axis, in the range of the axis on an Xbox controller.
+    pub fn set_axis(&self, axis: GamepadAxis, value: i32) -> Result<(), super::Error> {
+        let code = utils::gamepad_axis_to_evdev(axis);
+        self.device.emit(sys::EV_ABS, code, value)?;
+        self.device.synchronize()
+    }
+}
@@ -0,0 +1,17 @@
+//! This module provides the implementation of `keysim` when using the `uinput` kernel module.
+//!
+//! Rather than talking to a window manager, this backend creates a virtual input device through
+//! `/dev/uinput` and writes raw evdev events to it. Those events go through the regular kernel
+//! input stack, meaning that they work on X11, on Wayland, and even on a bare virtual console.
+
+mod simulator;
+pub use self::simulator::*;
+
+mod error;
+pub use self::error::*;
+
+mod gamepad;
+pub use self::gamepad::*;
+
+mod sys;
+mod utils;
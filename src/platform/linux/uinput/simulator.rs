@@ -0,0 +1,855 @@
+use std::fs::File;
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+
+use super::sys::{self, Device};
+use super::{utils, Gamepad};
+use crate::gesture;
+use crate::motion::SubpixelAccumulator;
+use crate::{
+    stylus, Button, Capabilities, Fidelity, GamepadOptions, Key, Screen, StylusTool, UnicodeSupport,
+};
+
+/// The simulator used when using the `uinput` kernel module.
+pub struct Simulator {
+    /// The virtual device that's being used to simulate inputs.
+    device: Device,
+    /// The virtual device that's being used to move the pointer to absolute positions.
+    ///
+    /// This is `None` when the size of the screen is unknown.
+    pointer: Option<Pointer>,
+    /// The virtual touchscreen, mapped to the same area as `pointer`.
+    ///
+    /// It is only created the first time it is used. It is boxed because it is quite large, and
+    /// would otherwise bloat the platform simulator.
+    touchscreen: OnceLock<Box<TouchDevice>>,
+    /// The virtual touchpad used to perform gestures.
+    ///
+    /// It is only created the first time it is used.
+    touchpad: OnceLock<Box<TouchDevice>>,
+    /// The virtual pen tablet, mapped to the same area as `pointer`.
+    ///
+    /// It is only created the first time it is used.
+    tablet: OnceLock<Box<Tablet>>,
+    /// The size of the desktop, in pixels, if it is known.
+    ///
+    /// This is `None` when writing to a file, as the positions are not clamped in that case.
+    screen_size: Option<(i32, i32)>,
+    /// The fractional part of the relative motions that have not been sent yet.
+    motion: SubpixelAccumulator,
+    /// The fractional part of the smooth scrolls that have not been sent yet, in 120ths of a
+    /// wheel click.
+    scroll: SubpixelAccumulator,
+    /// The part of the high-resolution wheel events that has not been sent as legacy wheel
+    /// events yet.
+    wheel_remainder: Mutex<(i32, i32)>,
+    /// The position the pointer was last moved to, if it is known.
+    position: Mutex<Option<(i32, i32)>>,
+}
+
+/// The size of the virtual touchpad, in hundredths of a millimetre.
+const TOUCHPAD_SIZE: (i32, i32) = (
+    gesture::TOUCHPAD_HALF_SIZE.0 * 2,
+    gesture::TOUCHPAD_HALF_SIZE.1 * 2,
+);
+/// The resolution of the virtual touchpad, in units per millimetre.
+const TOUCHPAD_RESOLUTION: i32 = 100;
+
+/// A virtual absolute pointing device.
+struct Pointer {
+    device: Device,
+    /// The size of the area the device is mapped to, in pixels.
+    width: i32,
+    height: i32,
+}
+
+impl Pointer {
+    /// Clamps the position (`x`, `y`) to the area the device is mapped to.
+    fn clamp(&self, x: i32, y: i32) -> (i32, i32) {
+        (x.clamp(0, self.width - 1), y.clamp(0, self.height - 1))
+    }
+}
+
+/// A virtual multi-touch device.
+struct TouchDevice {
+    /// The device, and the size of its surface.
+    surface: Pointer,
+    /// The contacts of the device.
+    touches: Mutex<Touches>,
+}
+
+impl TouchDevice {
+    fn new(surface: Pointer) -> Box<Self> {
+        Box::new(Self {
+            surface,
+            touches: Mutex::default(),
+        })
+    }
+
+    /// Locks the contacts of the device.
+    fn touches(&self) -> MutexGuard<'_, Touches> {
+        self.touches.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A virtual pen tablet.
+struct Tablet {
+    /// The device, and the size of the area it is mapped to.
+    surface: Pointer,
+    /// The tool that is close to the surface, and whether it touches it.
+    tool: Mutex<Option<(StylusTool, bool)>>,
+}
+
+impl Tablet {
+    fn new(surface: Pointer) -> Box<Self> {
+        Box::new(Self {
+            surface,
+            tool: Mutex::default(),
+        })
+    }
+
+    /// Locks the state of the tool.
+    fn tool(&self) -> MutexGuard<'_, Option<(StylusTool, bool)>> {
+        self.tool.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Returns the `BTN_TOOL_*` code of `tool`.
+fn tool_code(tool: StylusTool) -> u16 {
+    match tool {
+        StylusTool::Pen => sys::BTN_TOOL_PEN,
+        StylusTool::Eraser => sys::BTN_TOOL_RUBBER,
+    }
+}
+
+/// The contacts of a virtual multi-touch device.
+#[derive(Default)]
+struct Touches {
+    /// The tracking ID of the contact of each slot, if the slot is touching the screen.
+    slots: [Option<i32>; sys::TOUCH_SLOTS as usize],
+    /// The tracking ID given to the next contact.
+    next_tracking_id: i32,
+}
+
+impl Touches {
+    /// Returns whether the contact of `slot` is the oldest one, which also drives the
+    /// single-touch axes of the device.
+    fn is_oldest(&self, slot: u32) -> bool {
+        let oldest = (0..sys::TOUCH_SLOTS).min_by_key(|&s| {
+            self.slots[s as usize]
+                .map(|id| (id - self.next_tracking_id).rem_euclid(sys::MAX_TRACKING_ID + 1))
+                .unwrap_or(i32::MAX)
+        });
+        oldest == Some(slot)
+    }
+}
+
+impl Simulator {
+    /// Creates a new [`Simulator`] instance.
+    ///
+    /// `screen_size` is the size of the desktop, in pixels, used to map absolute positions. When
+    /// it is `None`, the size of the first framebuffer is used instead, if any.
+    ///
+    /// Note that the virtual keyboard and mouse are created by this function, and that it might
+    /// take a few milliseconds before the rest of the system notices them. Events sent during
+    /// that time may be lost. The same goes for the virtual touchscreen, touchpad and pen
+    /// tablet, which are only created the first time they are used.
+    pub fn new(screen_size: Option<(u32, u32)>) -> Result<Self, super::Error> {
+        let screen_size = screen_size
+            .or_else(utils::framebuffer_size)
+            .filter(|&(width, height)| width > 0 && height > 0)
+            .map(|(width, height)| {
+                let width = width.min(i32::MAX as u32) as i32;
+                let height = height.min(i32::MAX as u32) as i32;
+                (width, height)
+            });
+
+        let pointer = screen_size
+            .map(|(width, height)| {
+                Ok(Pointer {
+                    device: Device::create_absolute(width, height)?,
+                    width,
+                    height,
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            device: Device::create()?,
+            pointer,
+            touchscreen: OnceLock::new(),
+            tablet: OnceLock::new(),
+            touchpad: OnceLock::new(),
+            screen_size,
+            motion: SubpixelAccumulator::default(),
+            scroll: SubpixelAccumulator::default(),
+            wheel_remainder: Mutex::default(),
+            position: Mutex::default(),
+        })
+    }
+
+    /// Creates a new [`Simulator`] instance that writes raw `input_event` structures to `file`.
+    ///
+    /// The file is assumed to refer to a virtual device that has already been created. No
+    /// `ioctl` request is issued on it, meaning that any file descriptor (such as one end of a
+    /// pipe) may be used to inspect the events produced by the simulator.
+    ///
+    /// Absolute motion events are written to the same file, and are not clamped to the size of
+    /// the screen.
+    pub fn from_file(file: File) -> Self {
+        let pointer = file.try_clone().ok().map(|file| Pointer {
+            device: Device::from_file(file),
+            width: i32::MAX,
+            height: i32::MAX,
+        });
+
+        Self {
+            pointer,
+            touchscreen: OnceLock::new(),
+            tablet: OnceLock::new(),
+            touchpad: OnceLock::new(),
+            device: Device::from_file(file),
+            screen_size: None,
+            motion: SubpixelAccumulator::default(),
+            scroll: SubpixelAccumulator::default(),
+            wheel_remainder: Mutex::default(),
+            position: Mutex::default(),
+        }
+    }
+
+    /// Sends a key event, followed by a synchronization event.
+    fn key_event(&self, code: u16, press: bool) -> Result<(), super::Error> {
+        self.device.emit(sys::EV_KEY, code, press as i32)?;
+        self.device.synchronize()
+    }
+
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        let mut ret = Capabilities::new("uinput", Fidelity::Native, 5, UnicodeSupport::Ascii);
+        ret.inputs.pointer_motion = self.pointer.is_some();
+        ret.inputs.touch = self.pointer.is_some();
+        ret.inputs.gestures = true;
+        ret.inputs.stylus = self.pointer.is_some();
+        ret.inputs.gamepad = true;
+        ret
+    }
+
+    /// Sends a fake key press event.
+    pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
+        let code = utils::key_to_evdev(key).ok_or(super::Error::UnsupportedKey(key))?;
+        self.key_event(code, true)
+    }
+
+    /// Sends a fake key release event.
+    pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
+        let code = utils::key_to_evdev(key).ok_or(super::Error::UnsupportedKey(key))?;
+        self.key_event(code, false)
+    }
+
+    /// Sends a fake keystroke event.
+    pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
+        let code = utils::key_to_evdev(key).ok_or(super::Error::UnsupportedKey(key))?;
+        self.key_event(code, true)?;
+        self.key_event(code, false)
+    }
+
+    /// Sends a fake button press.
+    pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = utils::button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.key_event(code, true)
+    }
+
+    /// Sends a fake button release.
+    pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = utils::button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.key_event(code, false)
+    }
+
+    /// Sends a fake button click.
+    pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = utils::button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.key_event(code, true)?;
+        self.key_event(code, false)
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`) of the desktop.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        let pointer = self
+            .pointer
+            .as_ref()
+            .ok_or(super::Error::UnknownScreenSize)?;
+
+        let (x, y) = pointer.clamp(x, y);
+        pointer.device.emit(sys::EV_ABS, sys::ABS_X, x)?;
+        pointer.device.emit(sys::EV_ABS, sys::ABS_Y, y)?;
+        pointer.device.synchronize()?;
+
+        self.set_position(Some((x, y)));
+        Ok(())
+    }
+
+    /// Remembers the position of the mouse pointer.
+    fn set_position(&self, position: Option<(i32, i32)>) {
+        *self.position.lock().unwrap_or_else(PoisonError::into_inner) = position;
+    }
+
+    /// Returns the position the mouse pointer was last moved to by
+    /// [`move_mouse_to`](Self::move_mouse_to), if it has not been moved by a relative motion
+    /// since.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        Ok(*self.position.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        self.wheel(
+            dx.saturating_mul(sys::WHEEL_HI_RES_CLICK),
+            dy.saturating_mul(sys::WHEEL_HI_RES_CLICK),
+        )
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks, which may be fractional.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let click = sys::WHEEL_HI_RES_CLICK as f64;
+        let (dx, dy) = self.scroll.take(dx * click, dy * click);
+        self.wheel(dx, dy)
+    }
+
+    /// Sends high-resolution wheel events of (`dx`, `dy`) 120ths of a wheel click.
+    ///
+    /// Applications that do not support high-resolution scrolling read the legacy wheel events,
+    /// which are only sent once the high-resolution events amount to a whole wheel click.
+    fn wheel(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        if dx == 0 && dy == 0 {
+            return Ok(());
+        }
+
+        let (clicks_x, clicks_y) = {
+            // The remainder is always left in a consistent state.
+            let mut remainder = self
+                .wheel_remainder
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let x = remainder.0.saturating_add(dx);
+            let y = remainder.1.saturating_add(dy);
+            *remainder = (x % sys::WHEEL_HI_RES_CLICK, y % sys::WHEEL_HI_RES_CLICK);
+            (x / sys::WHEEL_HI_RES_CLICK, y / sys::WHEEL_HI_RES_CLICK)
+        };
+
+        // The vertical wheel is positive when scrolling up.
+        let events = [
+            (sys::REL_WHEEL_HI_RES, dy.saturating_neg()),
+            (sys::REL_WHEEL, clicks_y.saturating_neg()),
+            (sys::REL_HWHEEL_HI_RES, dx),
+            (sys::REL_HWHEEL, clicks_x),
+        ];
+        for (code, value) in events {
+            if value != 0 {
+                self.device.emit(sys::EV_REL, code, value)?;
+            }
+        }
+        self.device.synchronize()
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) relative to its current position.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let (dx, dy) = self.motion.take(dx, dy);
+        if dx == 0 && dy == 0 {
+            return Ok(());
+        }
+
+        // Pointer acceleration makes the resulting position unpredictable.
+        self.set_position(None);
+
+        self.device.emit(sys::EV_REL, sys::REL_X, dx)?;
+        self.device.emit(sys::EV_REL, sys::REL_Y, dy)?;
+        self.device.synchronize()
+    }
+
+    /// Returns the desktop the absolute pointer is mapped to, as a single monitor.
+    ///
+    /// No monitor is returned when the size of the desktop is unknown, or when the simulator
+    /// writes to a file.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        Ok(self
+            .screen_size
+            .map(|(width, height)| Screen::new(0, 0, width as u32, height as u32))
+            .into_iter()
+            .collect())
+    }
+
+    /// Returns the device stored in `cell`, creating it with `create` if it does not exist yet.
+    ///
+    /// When two threads race to create the device, only one of the devices is kept.
+    fn get_or_create<T>(
+        cell: &OnceLock<Box<T>>,
+        create: impl FnOnce() -> Result<Box<T>, super::Error>,
+    ) -> Result<&T, super::Error> {
+        if let Some(device) = cell.get() {
+            return Ok(device);
+        }
+
+        let device = create()?;
+        Ok(cell.get_or_init(|| device))
+    }
+
+    /// Returns the size of the area that `pointer` is mapped to.
+    fn screen_area(&self) -> Result<(i32, i32), super::Error> {
+        self.pointer
+            .as_ref()
+            .map(|pointer| (pointer.width, pointer.height))
+            .ok_or(super::Error::UnknownScreenSize)
+    }
+
+    /// Creates a virtual device with a surface of `width` by `height` with `create`.
+    ///
+    /// When the simulator writes to a file, the device writes to the same file instead.
+    fn create_surface(
+        &self,
+        (width, height): (i32, i32),
+        create: impl FnOnce(i32, i32) -> Result<Device, super::Error>,
+    ) -> Result<Pointer, super::Error> {
+        let device = match self.device.try_clone_file() {
+            Some(device) => device?,
+            None => create(width, height)?,
+        };
+
+        Ok(Pointer {
+            device,
+            width,
+            height,
+        })
+    }
+
+    /// Returns the virtual touchscreen, creating it if needed, and checks that `slot` can be
+    /// used with it.
+    fn touchscreen(&self, slot: u32) -> Result<&TouchDevice, super::Error> {
+        if slot >= sys::TOUCH_SLOTS {
+            return Err(super::Error::UnsupportedSlot(slot));
+        }
+
+        Self::get_or_create(&self.touchscreen, || {
+            self.create_surface(self.screen_area()?, Device::create_touchscreen)
+                .map(TouchDevice::new)
+        })
+    }
+
+    /// Puts a finger identified by `slot` on the screen at the position (`x`, `y`).
+    ///
+    /// If the slot was already touching the screen, its previous contact ends.
+    pub fn touch_down(&self, slot: u32, x: i32, y: i32) -> Result<(), super::Error> {
+        let touchscreen = self.touchscreen(slot)?;
+        let (x, y) = touchscreen.surface.clamp(x, y);
+        let device = &touchscreen.surface.device;
+
+        let mut touches = touchscreen.touches();
+        let first = touches.slots.iter().all(Option::is_none);
+        let tracking_id = touches.next_tracking_id;
+        touches.next_tracking_id = (tracking_id + 1) & sys::MAX_TRACKING_ID;
+        touches.slots[slot as usize] = Some(tracking_id);
+
+        device.emit(sys::EV_ABS, sys::ABS_MT_SLOT, slot as i32)?;
+        device.emit(sys::EV_ABS, sys::ABS_MT_TRACKING_ID, tracking_id)?;
+        device.emit(sys::EV_ABS, sys::ABS_MT_POSITION_X, x)?;
+        device.emit(sys::EV_ABS, sys::ABS_MT_POSITION_Y, y)?;
+        if first {
+            device.emit(sys::EV_KEY, sys::BTN_TOUCH, 1)?;
+        }
+        if touches.is_oldest(slot) {
+            device.emit(sys::EV_ABS, sys::ABS_X, x)?;
+            device.emit(sys::EV_ABS, sys::ABS_Y, y)?;
+        }
+        device.synchronize()
+    }
+
+    /// Moves the finger identified by `slot` to the position (`x`, `y`).
+    pub fn touch_move(&self, slot: u32, x: i32, y: i32) -> Result<(), super::Error> {
+        let touchscreen = self.touchscreen(slot)?;
+        let (x, y) = touchscreen.surface.clamp(x, y);
+        let device = &touchscreen.surface.device;
+
+        let touches = touchscreen.touches();
+        if touches.slots[slot as usize].is_none() {
+            return Err(super::Error::InactiveSlot(slot));
+        }
+
+        device.emit(sys::EV_ABS, sys::ABS_MT_SLOT, slot as i32)?;
+        device.emit(sys::EV_ABS, sys::ABS_MT_POSITION_X, x)?;
+        device.emit(sys::EV_ABS, sys::ABS_MT_POSITION_Y, y)?;
+        if touches.is_oldest(slot) {
+            device.emit(sys::EV_ABS, sys::ABS_X, x)?;
+            device.emit(sys::EV_ABS, sys::ABS_Y, y)?;
+        }
+        device.synchronize()
+    }
+
+    /// Lifts the finger identified by `slot` from the screen.
+    ///
+    /// Nothing happens if the slot is not touching the screen.
+    pub fn touch_up(&self, slot: u32) -> Result<(), super::Error> {
+        if slot >= sys::TOUCH_SLOTS {
+            return Err(super::Error::UnsupportedSlot(slot));
+        }
+
+        // No finger can touch a touchscreen that has not been created yet.
+        let Some(touchscreen) = self.touchscreen.get() else {
+            return Ok(());
+        };
+        let device = &touchscreen.surface.device;
+
+        let mut touches = touchscreen.touches();
+        if touches.slots[slot as usize].take().is_none() {
+            return Ok(());
+        }
+        let last = touches.slots.iter().all(Option::is_none);
+
+        device.emit(sys::EV_ABS, sys::ABS_MT_SLOT, slot as i32)?;
+        device.emit(sys::EV_ABS, sys::ABS_MT_TRACKING_ID, -1)?;
+        if last {
+            device.emit(sys::EV_KEY, sys::BTN_TOUCH, 0)?;
+        }
+        device.synchronize()
+    }
+
+    /// Puts the fingers of a gesture on the virtual touchpad at the positions `contacts`,
+    /// lifting the other ones.
+    ///
+    /// The positions are expressed in hundredths of a millimetre, relative to the center of the
+    /// touchpad. The touchpad is created the first time a finger is put on it.
+    pub fn touchpad_frame(&self, contacts: &[(i32, i32)]) -> Result<(), super::Error> {
+        // No finger can touch a touchpad that has not been created yet.
+        if contacts.is_empty() && self.touchpad.get().is_none() {
+            return Ok(());
+        }
+
+        let touchpad = Self::get_or_create(&self.touchpad, || {
+            let create =
+                |width, height| Device::create_touchpad(width, height, TOUCHPAD_RESOLUTION);
+            self.create_surface(TOUCHPAD_SIZE, create)
+                .map(TouchDevice::new)
+        })?;
+
+        Self::send_touchpad_frame(touchpad, contacts)
+    }
+
+    /// Sends the events of [`touchpad_frame`](Self::touchpad_frame).
+    fn send_touchpad_frame(
+        touchpad: &TouchDevice,
+        contacts: &[(i32, i32)],
+    ) -> Result<(), super::Error> {
+        if contacts.len() > sys::TOUCHPAD_SLOTS as usize {
+            return Err(super::Error::UnsupportedSlot(sys::TOUCHPAD_SLOTS));
+        }
+
+        let device = &touchpad.surface.device;
+        let (half_width, half_height) = gesture::TOUCHPAD_HALF_SIZE;
+
+        let mut touches = touchpad.touches();
+        let previous = touches.slots.iter().filter(|s| s.is_some()).count();
+
+        for (slot, &(x, y)) in contacts.iter().enumerate() {
+            let (x, y) = touchpad.surface.clamp(x + half_width, y + half_height);
+
+            device.emit(sys::EV_ABS, sys::ABS_MT_SLOT, slot as i32)?;
+            if touches.slots[slot].is_none() {
+                let tracking_id = touches.next_tracking_id;
+                touches.next_tracking_id = (tracking_id + 1) & sys::MAX_TRACKING_ID;
+                touches.slots[slot] = Some(tracking_id);
+                device.emit(sys::EV_ABS, sys::ABS_MT_TRACKING_ID, tracking_id)?;
+            }
+            device.emit(sys::EV_ABS, sys::ABS_MT_POSITION_X, x)?;
+            device.emit(sys::EV_ABS, sys::ABS_MT_POSITION_Y, y)?;
+            if slot == 0 {
+                device.emit(sys::EV_ABS, sys::ABS_X, x)?;
+                device.emit(sys::EV_ABS, sys::ABS_Y, y)?;
+            }
+        }
+
+        for slot in contacts.len()..sys::TOUCHPAD_SLOTS as usize {
+            if touches.slots[slot].take().is_some() {
+                device.emit(sys::EV_ABS, sys::ABS_MT_SLOT, slot as i32)?;
+                device.emit(sys::EV_ABS, sys::ABS_MT_TRACKING_ID, -1)?;
+            }
+        }
+
+        // The number of fingers touching the pad is reported through the `BTN_TOOL_*` codes,
+        // which is what lets the compositor tell a swipe from a scroll or a pointer motion.
+        if previous != contacts.len() {
+            if previous == 0 || contacts.is_empty() {
+                device.emit(sys::EV_KEY, sys::BTN_TOUCH, !contacts.is_empty() as i32)?;
+            }
+            if let Some(i) = previous.checked_sub(1) {
+                device.emit(sys::EV_KEY, sys::BTN_TOOL_FINGERS[i], 0)?;
+            }
+            if let Some(i) = contacts.len().checked_sub(1) {
+                device.emit(sys::EV_KEY, sys::BTN_TOOL_FINGERS[i], 1)?;
+            }
+        }
+
+        device.synchronize()
+    }
+
+    /// Moves the stylus, bringing `tool` close to the surface if needed.
+    ///
+    /// If another tool was close to the surface, it is moved away first. `pressure` ranges from
+    /// `0` to `4095`, and the tilt is expressed in degrees.
+    pub fn stylus(
+        &self,
+        tool: StylusTool,
+        x: i32,
+        y: i32,
+        pressure: u16,
+        (tilt_x, tilt_y): (i8, i8),
+    ) -> Result<(), super::Error> {
+        let tablet = Self::get_or_create(&self.tablet, || {
+            let create = |width, height| {
+                Device::create_tablet(
+                    width,
+                    height,
+                    stylus::MAX_PRESSURE as i32,
+                    stylus::MAX_TILT as i32,
+                )
+            };
+            self.create_surface(self.screen_area()?, create)
+                .map(Tablet::new)
+        })?;
+        let (x, y) = tablet.surface.clamp(x, y);
+        let device = &tablet.surface.device;
+
+        let mut state = tablet.tool();
+        if let Some((previous, touching)) = *state {
+            if previous != tool {
+                Self::send_stylus_leave(device, previous, touching)?;
+                *state = None;
+            }
+        }
+
+        device.emit(sys::EV_ABS, sys::ABS_X, x)?;
+        device.emit(sys::EV_ABS, sys::ABS_Y, y)?;
+        device.emit(sys::EV_ABS, sys::ABS_PRESSURE, pressure as i32)?;
+        device.emit(sys::EV_ABS, sys::ABS_TILT_X, tilt_x as i32)?;
+        device.emit(sys::EV_ABS, sys::ABS_TILT_Y, tilt_y as i32)?;
+
+        let touching = pressure > 0;
+        if state.is_none() {
+            device.emit(sys::EV_KEY, tool_code(tool), 1)?;
+        }
+        if state.is_some_and(|(_, t)| t) != touching {
+            device.emit(sys::EV_KEY, sys::BTN_TOUCH, touching as i32)?;
+        }
+        *state = Some((tool, touching));
+
+        device.synchronize()
+    }
+
+    /// Moves the stylus away from the surface.
+    ///
+    /// Nothing happens if the stylus is not close to the surface.
+    pub fn stylus_leave(&self) -> Result<(), super::Error> {
+        // No tool can be close to a tablet that has not been created yet.
+        let Some(tablet) = self.tablet.get() else {
+            return Ok(());
+        };
+
+        match tablet.tool().take() {
+            Some((tool, touching)) => {
+                Self::send_stylus_leave(&tablet.surface.device, tool, touching)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Lifts `tool` if it is `touching` the surface, then moves it away.
+    fn send_stylus_leave(
+        device: &Device,
+        tool: StylusTool,
+        touching: bool,
+    ) -> Result<(), super::Error> {
+        // Tablets report a null pressure when the tool leaves the surface.
+        if touching {
+            device.emit(sys::EV_ABS, sys::ABS_PRESSURE, 0)?;
+            device.emit(sys::EV_KEY, sys::BTN_TOUCH, 0)?;
+        }
+        device.emit(sys::EV_KEY, tool_code(tool), 0)?;
+        device.synchronize()
+    }
+
+    /// Creates a new virtual gamepad identified by `options`.
+    ///
+    /// When the simulator writes to a file, the events of the gamepad are written to the same
+    /// file.
+    pub fn create_gamepad(&self, options: &GamepadOptions) -> Result<Gamepad, super::Error> {
+        match self.device.try_clone_file() {
+            Some(device) => device.map(Gamepad::from_device),
+            None => Gamepad::new(options),
+        }
+    }
+
+    /// Sends a specific unicode code-point.
+    pub fn send_char(&self, c: char) -> Result<(), super::Error> {
+        let (code, shift) = utils::char_to_evdev(c).ok_or(super::Error::UnsupportedChar(c))?;
+
+        if shift {
+            self.key_event(sys::KEY_LEFTSHIFT, true)?;
+        }
+
+        let mut result = self
+            .key_event(code, true)
+            .and_then(|()| self.key_event(code, false));
+
+        if shift {
+            // Shift must not remain held, even when the key could not be sent.
+            let released = self.key_event(sys::KEY_LEFTSHIFT, false);
+            result = result.and(released);
+        }
+
+        result
+    }
+
+    /// Sends a collection of characters.
+    pub fn send_chars(&self, mut it: impl Iterator<Item = char>) -> Result<(), super::Error> {
+        it.try_for_each(|c| self.send_char(c))
+    }
+
+    /// Sends a string.
+    pub fn send_str(&self, s: &str) -> Result<(), super::Error> {
+        self.send_chars(s.chars())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::os::fd::OwnedFd;
+
+    use super::*;
+
+    /// Runs `f` on a simulator that writes to a pipe, and returns the `(type, code, value)` of
+    /// the events it wrote.
+    fn record(f: impl FnOnce(&Simulator)) -> Vec<(u16, u16, i32)> {
+        let (mut reader, writer) = std::io::pipe().unwrap();
+
+        let simulator = Simulator::from_file(File::from(OwnedFd::from(writer)));
+        f(&simulator);
+        drop(simulator);
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).unwrap();
+
+        let size = std::mem::size_of::<libc::input_event>();
+        assert_eq!(bytes.len() % size, 0);
+        bytes
+            .chunks(size)
+            .map(|chunk| {
+                // Safety:
+                //  `chunk` holds exactly one `input_event`, which is made only of primitive types.
+                let event: libc::input_event =
+                    unsafe { std::ptr::read_unaligned(chunk.as_ptr() as *const _) };
+                (event.type_, event.code, event.value)
+            })
+            .collect()
+    }
+
+    const SYN: (u16, u16, i32) = (sys::EV_SYN, sys::SYN_REPORT, 0);
+
+    #[test]
+    fn send_key_presses_and_releases() {
+        let events = record(|s| s.send_key(Key::A).unwrap());
+        assert_eq!(
+            events,
+            [
+                (sys::EV_KEY, sys::KEY_A, 1),
+                SYN,
+                (sys::EV_KEY, sys::KEY_A, 0),
+                SYN,
+            ]
+        );
+    }
+
+    #[test]
+    fn send_button_clicks() {
+        let events = record(|s| s.send_button(Button::Left).unwrap());
+        assert_eq!(
+            events,
+            [
+                (sys::EV_KEY, sys::BTN_LEFT, 1),
+                SYN,
+                (sys::EV_KEY, sys::BTN_LEFT, 0),
+                SYN,
+            ]
+        );
+    }
+
+    #[test]
+    fn send_char_without_shift() {
+        let events = record(|s| s.send_char('a').unwrap());
+        assert_eq!(
+            events,
+            [
+                (sys::EV_KEY, sys::KEY_A, 1),
+                SYN,
+                (sys::EV_KEY, sys::KEY_A, 0),
+                SYN,
+            ]
+        );
+    }
+
+    #[test]
+    fn send_char_wraps_in_shift() {
+        let events = record(|s| s.send_char('!').unwrap());
+        assert_eq!(
+            events,
+            [
+                (sys::EV_KEY, sys::KEY_LEFTSHIFT, 1),
+                SYN,
+                (sys::EV_KEY, sys::KEY_1, 1),
+                SYN,
+                (sys::EV_KEY, sys::KEY_1, 0),
+                SYN,
+                (sys::EV_KEY, sys::KEY_LEFTSHIFT, 0),
+                SYN,
+            ]
+        );
+    }
+
+    #[test]
+    fn touchscreen_is_created_on_first_use() {
+        record(|s| {
+            assert!(s.touchscreen.get().is_none());
+            s.touch_up(0).unwrap();
+            assert!(s.touchscreen.get().is_none());
+            s.touch_down(0, 10, 20).unwrap();
+            assert!(s.touchscreen.get().is_some());
+        });
+    }
+
+    #[test]
+    fn touchpad_is_created_on_first_use() {
+        record(|s| {
+            s.touchpad_frame(&[]).unwrap();
+            assert!(s.touchpad.get().is_none());
+            s.touchpad_frame(&[(0, 0)]).unwrap();
+            assert!(s.touchpad.get().is_some());
+        });
+    }
+
+    #[test]
+    fn tablet_is_created_on_first_use() {
+        record(|s| {
+            s.stylus_leave().unwrap();
+            assert!(s.tablet.get().is_none());
+            s.stylus(StylusTool::Pen, 10, 20, 0, (0, 0)).unwrap();
+            assert!(s.tablet.get().is_some());
+        });
+    }
+
+    #[test]
+    fn unsupported_chars_write_nothing() {
+        let events = record(|s| {
+            assert!(matches!(
+                s.send_char('é'),
+                Err(super::super::Error::UnsupportedChar('é'))
+            ));
+        });
+        assert!(events.is_empty());
+    }
+}
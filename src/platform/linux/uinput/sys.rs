@@ -0,0 +1,642 @@
+//! Wraps the `uinput` kernel interface into safe function calls associated to the [`Device`] type.
+//!
+//! The constants defined in this module are taken from `linux/input-event-codes.h` and
+//! `linux/uinput.h`.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::os::raw::c_int;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+
+pub const EV_SYN: u16 = 0x00;
+pub const EV_KEY: u16 = 0x01;
+pub const EV_REL: u16 = 0x02;
+pub const EV_ABS: u16 = 0x03;
+
+pub const SYN_REPORT: u16 = 0x00;
+
+pub const REL_X: u16 = 0x00;
+pub const REL_Y: u16 = 0x01;
+pub const REL_HWHEEL: u16 = 0x06;
+pub const REL_WHEEL: u16 = 0x08;
+pub const REL_WHEEL_HI_RES: u16 = 0x0b;
+pub const REL_HWHEEL_HI_RES: u16 = 0x0c;
+
+/// The value of `REL_WHEEL_HI_RES` and `REL_HWHEEL_HI_RES` that amounts to one wheel click.
+pub const WHEEL_HI_RES_CLICK: i32 = 120;
+
+pub const ABS_X: u16 = 0x00;
+pub const ABS_Y: u16 = 0x01;
+pub const ABS_Z: u16 = 0x02;
+pub const ABS_RX: u16 = 0x03;
+pub const ABS_RY: u16 = 0x04;
+pub const ABS_RZ: u16 = 0x05;
+pub const ABS_HAT0X: u16 = 0x10;
+pub const ABS_HAT0Y: u16 = 0x11;
+pub const ABS_PRESSURE: u16 = 0x18;
+pub const ABS_TILT_X: u16 = 0x1a;
+pub const ABS_TILT_Y: u16 = 0x1b;
+pub const ABS_MT_SLOT: u16 = 0x2f;
+pub const ABS_MT_POSITION_X: u16 = 0x35;
+pub const ABS_MT_POSITION_Y: u16 = 0x36;
+pub const ABS_MT_TRACKING_ID: u16 = 0x39;
+
+/// The number of contacts the virtual touchscreen can track at once.
+pub const TOUCH_SLOTS: u32 = 10;
+/// The highest tracking ID of a contact. Tracking IDs wrap around after this value.
+pub const MAX_TRACKING_ID: i32 = 0xffff;
+
+/// The number of contacts the virtual touchpad can track at once.
+pub const TOUCHPAD_SLOTS: u32 = 5;
+
+pub const INPUT_PROP_POINTER: u16 = 0x00;
+pub const INPUT_PROP_DIRECT: u16 = 0x01;
+
+pub const KEY_ESC: u16 = 1;
+pub const KEY_1: u16 = 2;
+pub const KEY_2: u16 = 3;
+pub const KEY_3: u16 = 4;
+pub const KEY_4: u16 = 5;
+pub const KEY_5: u16 = 6;
+pub const KEY_6: u16 = 7;
+pub const KEY_7: u16 = 8;
+pub const KEY_8: u16 = 9;
+pub const KEY_9: u16 = 10;
+pub const KEY_0: u16 = 11;
+pub const KEY_MINUS: u16 = 12;
+pub const KEY_EQUAL: u16 = 13;
+pub const KEY_BACKSPACE: u16 = 14;
+pub const KEY_TAB: u16 = 15;
+pub const KEY_Q: u16 = 16;
+pub const KEY_W: u16 = 17;
+pub const KEY_E: u16 = 18;
+pub const KEY_R: u16 = 19;
+pub const KEY_T: u16 = 20;
+pub const KEY_Y: u16 = 21;
+pub const KEY_U: u16 = 22;
+pub const KEY_I: u16 = 23;
+pub const KEY_O: u16 = 24;
+pub const KEY_P: u16 = 25;
+pub const KEY_LEFTBRACE: u16 = 26;
+pub const KEY_RIGHTBRACE: u16 = 27;
+pub const KEY_ENTER: u16 = 28;
+pub const KEY_LEFTCTRL: u16 = 29;
+pub const KEY_A: u16 = 30;
+pub const KEY_S: u16 = 31;
+pub const KEY_D: u16 = 32;
+pub const KEY_F: u16 = 33;
+pub const KEY_G: u16 = 34;
+pub const KEY_H: u16 = 35;
+pub const KEY_J: u16 = 36;
+pub const KEY_K: u16 = 37;
+pub const KEY_L: u16 = 38;
+pub const KEY_SEMICOLON: u16 = 39;
+pub const KEY_APOSTROPHE: u16 = 40;
+pub const KEY_GRAVE: u16 = 41;
+pub const KEY_LEFTSHIFT: u16 = 42;
+pub const KEY_BACKSLASH: u16 = 43;
+pub const KEY_Z: u16 = 44;
+pub const KEY_X: u16 = 45;
+pub const KEY_C: u16 = 46;
+pub const KEY_V: u16 = 47;
+pub const KEY_B: u16 = 48;
+pub const KEY_N: u16 = 49;
+pub const KEY_M: u16 = 50;
+pub const KEY_COMMA: u16 = 51;
+pub const KEY_DOT: u16 = 52;
+pub const KEY_SLASH: u16 = 53;
+pub const KEY_RIGHTSHIFT: u16 = 54;
+pub const KEY_KPASTERISK: u16 = 55;
+pub const KEY_LEFTALT: u16 = 56;
+pub const KEY_SPACE: u16 = 57;
+pub const KEY_CAPSLOCK: u16 = 58;
+pub const KEY_F1: u16 = 59;
+pub const KEY_F2: u16 = 60;
+pub const KEY_F3: u16 = 61;
+pub const KEY_F4: u16 = 62;
+pub const KEY_F5: u16 = 63;
+pub const KEY_F6: u16 = 64;
+pub const KEY_F7: u16 = 65;
+pub const KEY_F8: u16 = 66;
+pub const KEY_F9: u16 = 67;
+pub const KEY_F10: u16 = 68;
+pub const KEY_NUMLOCK: u16 = 69;
+pub const KEY_SCROLLLOCK: u16 = 70;
+pub const KEY_KP7: u16 = 71;
+pub const KEY_KP8: u16 = 72;
+pub const KEY_KP9: u16 = 73;
+pub const KEY_KPMINUS: u16 = 74;
+pub const KEY_KP4: u16 = 75;
+pub const KEY_KP5: u16 = 76;
+pub const KEY_KP6: u16 = 77;
+pub const KEY_KPPLUS: u16 = 78;
+pub const KEY_KP1: u16 = 79;
+pub const KEY_KP2: u16 = 80;
+pub const KEY_KP3: u16 = 81;
+pub const KEY_KP0: u16 = 82;
+pub const KEY_KPDOT: u16 = 83;
+pub const KEY_F11: u16 = 87;
+pub const KEY_F12: u16 = 88;
+pub const KEY_KPENTER: u16 = 96;
+pub const KEY_RIGHTCTRL: u16 = 97;
+pub const KEY_KPSLASH: u16 = 98;
+pub const KEY_SYSRQ: u16 = 99;
+pub const KEY_RIGHTALT: u16 = 100;
+pub const KEY_HOME: u16 = 102;
+pub const KEY_UP: u16 = 103;
+pub const KEY_PAGEUP: u16 = 104;
+pub const KEY_LEFT: u16 = 105;
+pub const KEY_RIGHT: u16 = 106;
+pub const KEY_END: u16 = 107;
+pub const KEY_DOWN: u16 = 108;
+pub const KEY_PAGEDOWN: u16 = 109;
+pub const KEY_INSERT: u16 = 110;
+pub const KEY_DELETE: u16 = 111;
+pub const KEY_MUTE: u16 = 113;
+pub const KEY_VOLUMEDOWN: u16 = 114;
+pub const KEY_VOLUMEUP: u16 = 115;
+pub const KEY_KPEQUAL: u16 = 117;
+pub const KEY_PAUSE: u16 = 119;
+pub const KEY_LEFTMETA: u16 = 125;
+pub const KEY_RIGHTMETA: u16 = 126;
+pub const KEY_COMPOSE: u16 = 127;
+pub const KEY_CALC: u16 = 140;
+pub const KEY_MAIL: u16 = 155;
+pub const KEY_BACK: u16 = 158;
+pub const KEY_FORWARD: u16 = 159;
+pub const KEY_NEXTSONG: u16 = 163;
+pub const KEY_PREVIOUSSONG: u16 = 165;
+pub const KEY_STOPCD: u16 = 166;
+pub const KEY_REFRESH: u16 = 173;
+pub const KEY_F13: u16 = 183;
+pub const KEY_F14: u16 = 184;
+pub const KEY_F15: u16 = 185;
+pub const KEY_F16: u16 = 186;
+pub const KEY_F17: u16 = 187;
+pub const KEY_F18: u16 = 188;
+pub const KEY_F19: u16 = 189;
+pub const KEY_F20: u16 = 190;
+pub const KEY_F21: u16 = 191;
+pub const KEY_F22: u16 = 192;
+pub const KEY_F23: u16 = 193;
+pub const KEY_F24: u16 = 194;
+pub const KEY_PLAYCD: u16 = 200;
+pub const KEY_PAUSECD: u16 = 201;
+pub const KEY_BRIGHTNESSDOWN: u16 = 224;
+pub const KEY_BRIGHTNESSUP: u16 = 225;
+pub const KEY_OK: u16 = 0x160;
+
+pub const BTN_MISC: u16 = 0x100;
+pub const BTN_LEFT: u16 = 0x110;
+pub const BTN_RIGHT: u16 = 0x111;
+pub const BTN_MIDDLE: u16 = 0x112;
+pub const BTN_SIDE: u16 = 0x113;
+pub const BTN_TASK: u16 = 0x117;
+pub const BTN_A: u16 = 0x130;
+pub const BTN_B: u16 = 0x131;
+pub const BTN_X: u16 = 0x133;
+pub const BTN_Y: u16 = 0x134;
+pub const BTN_TL: u16 = 0x136;
+pub const BTN_TR: u16 = 0x137;
+pub const BTN_SELECT: u16 = 0x13a;
+pub const BTN_START: u16 = 0x13b;
+pub const BTN_MODE: u16 = 0x13c;
+pub const BTN_THUMBL: u16 = 0x13d;
+pub const BTN_THUMBR: u16 = 0x13e;
+pub const BTN_TOOL_PEN: u16 = 0x140;
+pub const BTN_TOOL_RUBBER: u16 = 0x141;
+pub const BTN_TOOL_FINGER: u16 = 0x145;
+pub const BTN_TOOL_QUINTTAP: u16 = 0x148;
+pub const BTN_TOUCH: u16 = 0x14a;
+pub const BTN_TOOL_DOUBLETAP: u16 = 0x14d;
+pub const BTN_TOOL_TRIPLETAP: u16 = 0x14e;
+pub const BTN_TOOL_QUADTAP: u16 = 0x14f;
+
+/// The `BTN_TOOL_*` codes that report the number of fingers touching a touchpad, from one to
+/// five fingers.
+pub const BTN_TOOL_FINGERS: [u16; 5] = [
+    BTN_TOOL_FINGER,
+    BTN_TOOL_DOUBLETAP,
+    BTN_TOOL_TRIPLETAP,
+    BTN_TOOL_QUADTAP,
+    BTN_TOOL_QUINTTAP,
+];
+pub const BTN_TRIGGER_HAPPY: u16 = 0x2c0;
+
+pub const BUS_USB: u16 = 0x03;
+pub const BUS_VIRTUAL: u16 = 0x06;
+
+const UINPUT_IOCTL_BASE: u32 = b'U' as u32;
+
+const UI_DEV_CREATE: libc::Ioctl = libc::_IO(UINPUT_IOCTL_BASE, 1);
+const UI_DEV_DESTROY: libc::Ioctl = libc::_IO(UINPUT_IOCTL_BASE, 2);
+const UI_DEV_SETUP: libc::Ioctl = libc::_IOW::<libc::uinput_setup>(UINPUT_IOCTL_BASE, 3);
+const UI_ABS_SETUP: libc::Ioctl = libc::_IOW::<libc::uinput_abs_setup>(UINPUT_IOCTL_BASE, 4);
+const UI_SET_EVBIT: libc::Ioctl = libc::_IOW::<c_int>(UINPUT_IOCTL_BASE, 100);
+const UI_SET_KEYBIT: libc::Ioctl = libc::_IOW::<c_int>(UINPUT_IOCTL_BASE, 101);
+const UI_SET_RELBIT: libc::Ioctl = libc::_IOW::<c_int>(UINPUT_IOCTL_BASE, 102);
+const UI_SET_ABSBIT: libc::Ioctl = libc::_IOW::<c_int>(UINPUT_IOCTL_BASE, 103);
+const UI_SET_PROPBIT: libc::Ioctl = libc::_IOW::<c_int>(UINPUT_IOCTL_BASE, 110);
+
+/// The paths at which the `uinput` device node may be found.
+const UINPUT_PATHS: [&str; 2] = ["/dev/uinput", "/dev/input/uinput"];
+
+/// The name of the virtual keyboard and mouse created by `keysim`.
+const DEVICE_NAME: &[u8] = b"keysim virtual input device";
+/// The name of the virtual absolute pointer created by `keysim`.
+const POINTER_NAME: &[u8] = b"keysim virtual absolute pointer";
+/// The name of the virtual touchscreen created by `keysim`.
+const TOUCHSCREEN_NAME: &[u8] = b"keysim virtual touchscreen";
+/// The name of the virtual touchpad created by `keysim`.
+const TOUCHPAD_NAME: &[u8] = b"keysim virtual touchpad";
+/// The name of the virtual pen tablet created by `keysim`.
+const TABLET_NAME: &[u8] = b"keysim virtual tablet";
+
+/// A virtual input device.
+pub struct Device {
+    file: File,
+
+    /// Whether the `UI_DEV_CREATE` request has been issued on `file`.
+    ///
+    /// When this is `true`, the device must be destroyed when the [`Device`] is dropped.
+    created: bool,
+}
+
+impl Device {
+    /// Opens the `uinput` device node.
+    fn open() -> Result<Self, super::Error> {
+        let mut last_error = None;
+
+        for path in UINPUT_PATHS {
+            match OpenOptions::new()
+                .write(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(path)
+            {
+                Ok(file) => {
+                    return Ok(Self {
+                        file,
+                        created: false,
+                    })
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        // `UINPUT_PATHS` is not empty, we know that `last_error` was set.
+        Err(super::Error::CreateDevice(last_error.unwrap()))
+    }
+
+    /// Creates a new virtual keyboard and mouse through the `uinput` kernel module.
+    pub fn create() -> Result<Self, super::Error> {
+        let mut ret = Self::open()?;
+
+        ret.ioctl(UI_SET_EVBIT, EV_KEY as c_int)?;
+        for code in (KEY_ESC..BTN_MISC).chain(KEY_OK..BTN_TRIGGER_HAPPY) {
+            ret.ioctl(UI_SET_KEYBIT, code as c_int)?;
+        }
+        for code in BTN_LEFT..=BTN_TASK {
+            ret.ioctl(UI_SET_KEYBIT, code as c_int)?;
+        }
+
+        // Without relative axes, the device would not be recognized as a mouse and its buttons
+        // would be ignored.
+        ret.ioctl(UI_SET_EVBIT, EV_REL as c_int)?;
+        ret.ioctl(UI_SET_RELBIT, REL_X as c_int)?;
+        ret.ioctl(UI_SET_RELBIT, REL_Y as c_int)?;
+        ret.ioctl(UI_SET_RELBIT, REL_HWHEEL as c_int)?;
+        ret.ioctl(UI_SET_RELBIT, REL_WHEEL as c_int)?;
+        ret.ioctl(UI_SET_RELBIT, REL_WHEEL_HI_RES as c_int)?;
+        ret.ioctl(UI_SET_RELBIT, REL_HWHEEL_HI_RES as c_int)?;
+
+        ret.finish(DEVICE_NAME, 0x5678)?;
+        Ok(ret)
+    }
+
+    /// Creates a new virtual absolute pointer through the `uinput` kernel module.
+    ///
+    /// The range of its axes is `0..width` and `0..height`. The compositor maps that range onto
+    /// the whole desktop, meaning that positions are expressed in pixels when `width` and
+    /// `height` match the size of the desktop.
+    ///
+    /// Absolute axes cannot be mixed with the relative axes of the main device without confusing
+    /// the rest of the system, hence the separate device.
+    pub fn create_absolute(width: i32, height: i32) -> Result<Self, super::Error> {
+        let mut ret = Self::open()?;
+
+        // Without a button, the device would not be recognized as a pointer.
+        ret.ioctl(UI_SET_EVBIT, EV_KEY as c_int)?;
+        ret.ioctl(UI_SET_KEYBIT, BTN_LEFT as c_int)?;
+
+        ret.ioctl(UI_SET_EVBIT, EV_ABS as c_int)?;
+        ret.ioctl(UI_SET_ABSBIT, ABS_X as c_int)?;
+        ret.ioctl(UI_SET_ABSBIT, ABS_Y as c_int)?;
+        ret.abs_setup(ABS_X, 0..=width - 1, 0)?;
+        ret.abs_setup(ABS_Y, 0..=height - 1, 0)?;
+
+        ret.finish(POINTER_NAME, 0x5679)?;
+        Ok(ret)
+    }
+
+    /// Creates a new virtual multi-touch screen through the `uinput` kernel module.
+    ///
+    /// The device uses the type B multi-touch protocol, and the range of its axes is `0..width`
+    /// and `0..height`, like the one of [`create_absolute`](Self::create_absolute).
+    pub fn create_touchscreen(width: i32, height: i32) -> Result<Self, super::Error> {
+        let mut ret = Self::open()?;
+
+        // Touchscreens report positions on the screen itself, unlike touchpads.
+        ret.ioctl(UI_SET_PROPBIT, INPUT_PROP_DIRECT as c_int)?;
+
+        ret.ioctl(UI_SET_EVBIT, EV_KEY as c_int)?;
+        ret.ioctl(UI_SET_KEYBIT, BTN_TOUCH as c_int)?;
+
+        // The single-touch axes are still read by some consumers.
+        ret.ioctl(UI_SET_EVBIT, EV_ABS as c_int)?;
+        for (code, maximum) in [
+            (ABS_X, width - 1),
+            (ABS_Y, height - 1),
+            (ABS_MT_SLOT, TOUCH_SLOTS as i32 - 1),
+            (ABS_MT_TRACKING_ID, MAX_TRACKING_ID),
+            (ABS_MT_POSITION_X, width - 1),
+            (ABS_MT_POSITION_Y, height - 1),
+        ] {
+            ret.ioctl(UI_SET_ABSBIT, code as c_int)?;
+            ret.abs_setup(code, 0..=maximum, 0)?;
+        }
+
+        ret.finish(TOUCHSCREEN_NAME, 0x567a)?;
+        Ok(ret)
+    }
+
+    /// Creates a new virtual multi-touch touchpad through the `uinput` kernel module.
+    ///
+    /// The device uses the type B multi-touch protocol, and the range of its axes is `0..width`
+    /// and `0..height`, with `resolution` units per millimetre. Compositors need the physical
+    /// size of touchpads to recognize gestures.
+    pub fn create_touchpad(width: i32, height: i32, resolution: i32) -> Result<Self, super::Error> {
+        let mut ret = Self::open()?;
+
+        // Touchpads move the pointer relative to its current position, unlike touchscreens.
+        ret.ioctl(UI_SET_PROPBIT, INPUT_PROP_POINTER as c_int)?;
+
+        ret.ioctl(UI_SET_EVBIT, EV_KEY as c_int)?;
+        ret.ioctl(UI_SET_KEYBIT, BTN_LEFT as c_int)?;
+        ret.ioctl(UI_SET_KEYBIT, BTN_TOUCH as c_int)?;
+        for code in BTN_TOOL_FINGERS {
+            ret.ioctl(UI_SET_KEYBIT, code as c_int)?;
+        }
+
+        ret.ioctl(UI_SET_EVBIT, EV_ABS as c_int)?;
+        for (code, maximum, resolution) in [
+            (ABS_X, width - 1, resolution),
+            (ABS_Y, height - 1, resolution),
+            (ABS_MT_SLOT, TOUCHPAD_SLOTS as i32 - 1, 0),
+            (ABS_MT_TRACKING_ID, MAX_TRACKING_ID, 0),
+            (ABS_MT_POSITION_X, width - 1, resolution),
+            (ABS_MT_POSITION_Y, height - 1, resolution),
+        ] {
+            ret.ioctl(UI_SET_ABSBIT, code as c_int)?;
+            ret.abs_setup(code, 0..=maximum, resolution)?;
+        }
+
+        ret.finish(TOUCHPAD_NAME, 0x567b)?;
+        Ok(ret)
+    }
+
+    /// Creates a new virtual pen tablet through the `uinput` kernel module.
+    ///
+    /// The range of its position axes is `0..width` and `0..height`, like the one of
+    /// [`create_absolute`](Self::create_absolute). Pressure ranges from `0` to `max_pressure`,
+    /// and tilt from `-max_tilt` to `max_tilt` degrees.
+    pub fn create_tablet(
+        width: i32,
+        height: i32,
+        max_pressure: i32,
+        max_tilt: i32,
+    ) -> Result<Self, super::Error> {
+        let mut ret = Self::open()?;
+
+        // The tablet is mapped onto the screen, like a pen display.
+        ret.ioctl(UI_SET_PROPBIT, INPUT_PROP_DIRECT as c_int)?;
+
+        ret.ioctl(UI_SET_EVBIT, EV_KEY as c_int)?;
+        for code in [BTN_TOUCH, BTN_TOOL_PEN, BTN_TOOL_RUBBER] {
+            ret.ioctl(UI_SET_KEYBIT, code as c_int)?;
+        }
+
+        // `libinput` ignores tablets whose position axes have no resolution. Assume a density
+        // of about 100 pixels per inch. Tilt is expressed in degrees, which is about 57 units
+        // per radian.
+        ret.ioctl(UI_SET_EVBIT, EV_ABS as c_int)?;
+        for (code, range, resolution) in [
+            (ABS_X, 0..=width - 1, 4),
+            (ABS_Y, 0..=height - 1, 4),
+            (ABS_PRESSURE, 0..=max_pressure, 0),
+            (ABS_TILT_X, -max_tilt..=max_tilt, 57),
+            (ABS_TILT_Y, -max_tilt..=max_tilt, 57),
+        ] {
+            ret.ioctl(UI_SET_ABSBIT, code as c_int)?;
+            ret.abs_setup(code, range, resolution)?;
+        }
+
+        ret.finish(TABLET_NAME, 0x567c)?;
+        Ok(ret)
+    }
+
+    /// Creates a new virtual gamepad through the `uinput` kernel module.
+    ///
+    /// The device reports the same buttons and axes as the `xpad` driver does for an Xbox 360
+    /// controller: sticks range from `-stick_max - 1` to `stick_max`, triggers from `0` to
+    /// `trigger_max`, and the D-pad is a hat switch. It claims to be a USB device, because games
+    /// identify controllers by their bus type along with their vendor and product IDs.
+    pub fn create_gamepad(
+        name: &[u8],
+        vendor: u16,
+        product: u16,
+        stick_max: i32,
+        trigger_max: i32,
+    ) -> Result<Self, super::Error> {
+        let mut ret = Self::open()?;
+
+        ret.ioctl(UI_SET_EVBIT, EV_KEY as c_int)?;
+        for code in [
+            BTN_A, BTN_B, BTN_X, BTN_Y, BTN_TL, BTN_TR, BTN_SELECT, BTN_START, BTN_MODE,
+            BTN_THUMBL, BTN_THUMBR,
+        ] {
+            ret.ioctl(UI_SET_KEYBIT, code as c_int)?;
+        }
+
+        ret.ioctl(UI_SET_EVBIT, EV_ABS as c_int)?;
+        for (code, range) in [
+            (ABS_X, -stick_max - 1..=stick_max),
+            (ABS_Y, -stick_max - 1..=stick_max),
+            (ABS_RX, -stick_max - 1..=stick_max),
+            (ABS_RY, -stick_max - 1..=stick_max),
+            (ABS_Z, 0..=trigger_max),
+            (ABS_RZ, 0..=trigger_max),
+            (ABS_HAT0X, -1..=1),
+            (ABS_HAT0Y, -1..=1),
+        ] {
+            ret.ioctl(UI_SET_ABSBIT, code as c_int)?;
+            ret.abs_setup(code, range, 0)?;
+        }
+
+        ret.finish_as(name, BUS_USB, vendor, product, 0x0114)?;
+        Ok(ret)
+    }
+
+    /// Creates a [`Device`] that writes its events to `file` as-is.
+    ///
+    /// No request is issued on `file`, which is assumed to refer to a device that has already
+    /// been set up. This allows pointing the simulator at a pipe or a regular file in order to
+    /// inspect the events it produces without needing access to real input devices.
+    pub fn from_file(file: File) -> Self {
+        Self {
+            file,
+            created: false,
+        }
+    }
+
+    /// Sets the range of the absolute axis `code`.
+    ///
+    /// `resolution` is the number of units per millimetre (or per radian, for angles), or `0` if
+    /// it is unknown.
+    fn abs_setup(
+        &self,
+        code: u16,
+        range: RangeInclusive<i32>,
+        resolution: i32,
+    ) -> Result<(), super::Error> {
+        // Safety:
+        //  `uinput_abs_setup` is made only of primitive types.
+        let mut setup: libc::uinput_abs_setup = unsafe { std::mem::zeroed() };
+        setup.code = code;
+        setup.absinfo.minimum = *range.start();
+        setup.absinfo.maximum = *range.end();
+        setup.absinfo.resolution = resolution;
+
+        // Safety:
+        //  `setup` is a valid `uinput_abs_setup` instance, which is what `UI_ABS_SETUP` expects.
+        let status = unsafe { libc::ioctl(self.file.as_raw_fd(), UI_ABS_SETUP, &setup) };
+        if status < 0 {
+            return Err(super::Error::CreateDevice(std::io::Error::last_os_error()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns a [`Device`] that writes to the same file, if this one was created by
+    /// [`from_file`](Self::from_file).
+    pub fn try_clone_file(&self) -> Option<Result<Self, super::Error>> {
+        if self.created {
+            return None;
+        }
+
+        Some(
+            self.file
+                .try_clone()
+                .map(Self::from_file)
+                .map_err(super::Error::CreateDevice),
+        )
+    }
+
+    /// Names the device whose capabilities have been configured, and creates it.
+    fn finish(&mut self, name: &[u8], product: u16) -> Result<(), super::Error> {
+        self.finish_as(name, BUS_VIRTUAL, 0x1234, product, 1)
+    }
+
+    /// Like [`finish`](Self::finish), but with a specific identity.
+    ///
+    /// `name` is truncated to fit the buffer of the kernel, including its terminating nul byte.
+    fn finish_as(
+        &mut self,
+        name: &[u8],
+        bustype: u16,
+        vendor: u16,
+        product: u16,
+        version: u16,
+    ) -> Result<(), super::Error> {
+        // Safety:
+        //  `uinput_setup` is made only of primitive types.
+        let mut setup: libc::uinput_setup = unsafe { std::mem::zeroed() };
+        setup.id.bustype = bustype;
+        setup.id.vendor = vendor;
+        setup.id.product = product;
+        setup.id.version = version;
+        let capacity = setup.name.len() - 1;
+        for (dst, &src) in setup.name.iter_mut().zip(&name[..name.len().min(capacity)]) {
+            *dst = src as _;
+        }
+
+        // Safety:
+        //  `setup` is a valid `uinput_setup` instance, which is what `UI_DEV_SETUP` expects.
+        let status = unsafe { libc::ioctl(self.file.as_raw_fd(), UI_DEV_SETUP, &setup) };
+        if status < 0 {
+            return Err(super::Error::CreateDevice(std::io::Error::last_os_error()));
+        }
+
+        // Safety:
+        //  `UI_DEV_CREATE` takes no argument.
+        let status = unsafe { libc::ioctl(self.file.as_raw_fd(), UI_DEV_CREATE) };
+        if status < 0 {
+            return Err(super::Error::CreateDevice(std::io::Error::last_os_error()));
+        }
+
+        self.created = true;
+        Ok(())
+    }
+
+    /// Issues an `ioctl` request that takes an integer argument.
+    fn ioctl(&self, request: libc::Ioctl, arg: c_int) -> Result<(), super::Error> {
+        // Safety:
+        //  All the requests used with this function expect an integer argument.
+        let status = unsafe { libc::ioctl(self.file.as_raw_fd(), request, arg) };
+
+        if status < 0 {
+            Err(super::Error::CreateDevice(std::io::Error::last_os_error()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes a single input event to the device.
+    pub fn emit(&self, type_: u16, code: u16, value: i32) -> Result<(), super::Error> {
+        // Safety:
+        //  `input_event` is made only of primitive types. A zeroed timestamp lets the kernel fill
+        //  it in.
+        let mut event: libc::input_event = unsafe { std::mem::zeroed() };
+        event.type_ = type_;
+        event.code = code;
+        event.value = value;
+
+        // Safety:
+        //  `input_event` has no padding bytes and the slice does not outlive `event`.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &event as *const libc::input_event as *const u8,
+                std::mem::size_of::<libc::input_event>(),
+            )
+        };
+
+        (&self.file).write_all(bytes).map_err(super::Error::Write)
+    }
+
+    /// Writes a `SYN_REPORT` event, marking the end of a group of events.
+    #[inline]
+    pub fn synchronize(&self) -> Result<(), super::Error> {
+        self.emit(EV_SYN, SYN_REPORT, 0)
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        if self.created {
+            // Safety:
+            //  `UI_DEV_DESTROY` takes no argument.
+            unsafe { libc::ioctl(self.file.as_raw_fd(), UI_DEV_DESTROY) };
+        }
+    }
+}
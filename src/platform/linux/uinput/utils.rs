@@ -0,0 +1,345 @@
+//! This module provides utility functions to work with evdev event codes.
+
+use super::sys;
+use crate::{Button, GamepadAxis, GamepadButton, Key};
+
+/// Converts a [`Key`] into an evdev `KEY_*` code.
+pub fn key_to_evdev(key: Key) -> Option<u16> {
+    match key {
+        Key::A => Some(sys::KEY_A),
+        Key::B => Some(sys::KEY_B),
+        Key::C => Some(sys::KEY_C),
+        Key::D => Some(sys::KEY_D),
+        Key::E => Some(sys::KEY_E),
+        Key::F => Some(sys::KEY_F),
+        Key::G => Some(sys::KEY_G),
+        Key::H => Some(sys::KEY_H),
+        Key::I => Some(sys::KEY_I),
+        Key::J => Some(sys::KEY_J),
+        Key::K => Some(sys::KEY_K),
+        Key::L => Some(sys::KEY_L),
+        Key::M => Some(sys::KEY_M),
+        Key::N => Some(sys::KEY_N),
+        Key::O => Some(sys::KEY_O),
+        Key::P => Some(sys::KEY_P),
+        Key::Q => Some(sys::KEY_Q),
+        Key::R => Some(sys::KEY_R),
+        Key::S => Some(sys::KEY_S),
+        Key::T => Some(sys::KEY_T),
+        Key::U => Some(sys::KEY_U),
+        Key::V => Some(sys::KEY_V),
+        Key::W => Some(sys::KEY_W),
+        Key::X => Some(sys::KEY_X),
+        Key::Y => Some(sys::KEY_Y),
+        Key::Z => Some(sys::KEY_Z),
+        Key::F1 => Some(sys::KEY_F1),
+        Key::F2 => Some(sys::KEY_F2),
+        Key::F3 => Some(sys::KEY_F3),
+        Key::F4 => Some(sys::KEY_F4),
+        Key::F5 => Some(sys::KEY_F5),
+        Key::F6 => Some(sys::KEY_F6),
+        Key::F7 => Some(sys::KEY_F7),
+        Key::F8 => Some(sys::KEY_F8),
+        Key::F9 => Some(sys::KEY_F9),
+        Key::F10 => Some(sys::KEY_F10),
+        Key::F11 => Some(sys::KEY_F11),
+        Key::F12 => Some(sys::KEY_F12),
+        Key::F13 => Some(sys::KEY_F13),
+        Key::F14 => Some(sys::KEY_F14),
+        Key::F15 => Some(sys::KEY_F15),
+        Key::F16 => Some(sys::KEY_F16),
+        Key::F17 => Some(sys::KEY_F17),
+        Key::F18 => Some(sys::KEY_F18),
+        Key::F19 => Some(sys::KEY_F19),
+        Key::F20 => Some(sys::KEY_F20),
+        Key::F21 => Some(sys::KEY_F21),
+        Key::F22 => Some(sys::KEY_F22),
+        Key::F23 => Some(sys::KEY_F23),
+        Key::F24 => Some(sys::KEY_F24),
+        Key::Zero => Some(sys::KEY_0),
+        Key::One => Some(sys::KEY_1),
+        Key::Two => Some(sys::KEY_2),
+        Key::Three => Some(sys::KEY_3),
+        Key::Four => Some(sys::KEY_4),
+        Key::Five => Some(sys::KEY_5),
+        Key::Six => Some(sys::KEY_6),
+        Key::Seven => Some(sys::KEY_7),
+        Key::Eight => Some(sys::KEY_8),
+        Key::Nine => Some(sys::KEY_9),
+        Key::Minus => Some(sys::KEY_MINUS),
+        Key::Equal => Some(sys::KEY_EQUAL),
+        Key::BracketLeft => Some(sys::KEY_LEFTBRACE),
+        Key::BracketRight => Some(sys::KEY_RIGHTBRACE),
+        Key::Semicolon => Some(sys::KEY_SEMICOLON),
+        Key::Quote => Some(sys::KEY_APOSTROPHE),
+        Key::Comma => Some(sys::KEY_COMMA),
+        Key::Period => Some(sys::KEY_DOT),
+        Key::Slash => Some(sys::KEY_SLASH),
+        Key::Backslash => Some(sys::KEY_BACKSLASH),
+        Key::Grave => Some(sys::KEY_GRAVE),
+        Key::Escape => Some(sys::KEY_ESC),
+        Key::Tab => Some(sys::KEY_TAB),
+        Key::CapsLock => Some(sys::KEY_CAPSLOCK),
+        Key::LeftShift => Some(sys::KEY_LEFTSHIFT),
+        Key::LeftControl => Some(sys::KEY_LEFTCTRL),
+        Key::LeftAlt => Some(sys::KEY_LEFTALT),
+        Key::LeftMeta => Some(sys::KEY_LEFTMETA),
+        Key::Space => Some(sys::KEY_SPACE),
+        Key::RightMeta => Some(sys::KEY_RIGHTMETA),
+        Key::RightAlt => Some(sys::KEY_RIGHTALT),
+        Key::RightControl => Some(sys::KEY_RIGHTCTRL),
+        Key::RightShift => Some(sys::KEY_RIGHTSHIFT),
+        Key::Enter => Some(sys::KEY_ENTER),
+        Key::Backspace => Some(sys::KEY_BACKSPACE),
+        Key::PrintScreen => Some(sys::KEY_SYSRQ),
+        Key::ScrollLock => Some(sys::KEY_SCROLLLOCK),
+        Key::Pause => Some(sys::KEY_PAUSE),
+        Key::ContextMenu => Some(sys::KEY_COMPOSE),
+        Key::Insert => Some(sys::KEY_INSERT),
+        Key::Delete => Some(sys::KEY_DELETE),
+        Key::Home => Some(sys::KEY_HOME),
+        Key::End => Some(sys::KEY_END),
+        Key::PageUp => Some(sys::KEY_PAGEUP),
+        Key::PageDown => Some(sys::KEY_PAGEDOWN),
+        Key::ArrowUp => Some(sys::KEY_UP),
+        Key::ArrowDown => Some(sys::KEY_DOWN),
+        Key::ArrowLeft => Some(sys::KEY_LEFT),
+        Key::ArrowRight => Some(sys::KEY_RIGHT),
+        Key::NumLock => Some(sys::KEY_NUMLOCK),
+        Key::NumpadEqual => Some(sys::KEY_KPEQUAL),
+        Key::NumpadDivide => Some(sys::KEY_KPSLASH),
+        Key::NumpadMultiply => Some(sys::KEY_KPASTERISK),
+        Key::NumpadSubtract => Some(sys::KEY_KPMINUS),
+        Key::NumpadAdd => Some(sys::KEY_KPPLUS),
+        Key::NumpadEnter => Some(sys::KEY_KPENTER),
+        Key::NumpadDecimal => Some(sys::KEY_KPDOT),
+        Key::Numpad0 => Some(sys::KEY_KP0),
+        Key::Numpad1 => Some(sys::KEY_KP1),
+        Key::Numpad2 => Some(sys::KEY_KP2),
+        Key::Numpad3 => Some(sys::KEY_KP3),
+        Key::Numpad4 => Some(sys::KEY_KP4),
+        Key::Numpad5 => Some(sys::KEY_KP5),
+        Key::Numpad6 => Some(sys::KEY_KP6),
+        Key::Numpad7 => Some(sys::KEY_KP7),
+        Key::Numpad8 => Some(sys::KEY_KP8),
+        Key::Numpad9 => Some(sys::KEY_KP9),
+        Key::VolumeUp => Some(sys::KEY_VOLUMEUP),
+        Key::VolumeDown => Some(sys::KEY_VOLUMEDOWN),
+        Key::VolumeMute => Some(sys::KEY_MUTE),
+        Key::MediaNext => Some(sys::KEY_NEXTSONG),
+        Key::MediaPrevious => Some(sys::KEY_PREVIOUSSONG),
+        Key::MediaStop => Some(sys::KEY_STOPCD),
+        Key::MediaPause => Some(sys::KEY_PAUSECD),
+        Key::MediaPlay => Some(sys::KEY_PLAYCD),
+        Key::BrowserBack => Some(sys::KEY_BACK),
+        Key::BrowserForward => Some(sys::KEY_FORWARD),
+        Key::BrowserRefresh => Some(sys::KEY_REFRESH),
+        Key::BrightnessUp => Some(sys::KEY_BRIGHTNESSUP),
+        Key::BrightnessDown => Some(sys::KEY_BRIGHTNESSDOWN),
+        Key::LaunchMail => Some(sys::KEY_MAIL),
+        Key::LaunchCalculator => Some(sys::KEY_CALC),
+        Key::Keysym(_) => None,
+        // Only the keys enabled on the virtual keyboard can be sent.
+        Key::Scancode(code) => u16::try_from(code).ok().filter(|code| {
+            (sys::KEY_ESC..sys::BTN_MISC).contains(code)
+                || (sys::KEY_OK..sys::BTN_TRIGGER_HAPPY).contains(code)
+        }),
+    }
+}
+
+/// Converts a [`Button`] into an evdev `BTN_*` code.
+///
+/// Extra buttons are mapped onto `BTN_SIDE`, `BTN_EXTRA`, `BTN_FORWARD`, `BTN_BACK` and
+/// `BTN_TASK`, in that order.
+pub fn button_to_evdev(button: Button) -> Option<u16> {
+    match button {
+        Button::Left => Some(sys::BTN_LEFT),
+        Button::Middle => Some(sys::BTN_MIDDLE),
+        Button::Right => Some(sys::BTN_RIGHT),
+        Button::Extra(n) => {
+            let code = sys::BTN_SIDE.checked_add(n as u16)?;
+            (code <= sys::BTN_TASK).then_some(code)
+        }
+    }
+}
+
+/// Converts a [`GamepadButton`] into an evdev `BTN_*` code.
+///
+/// Returns `None` for the directions of the D-pad, which are reported through the `ABS_HAT0X`
+/// and `ABS_HAT0Y` axes.
+pub fn gamepad_button_to_evdev(button: GamepadButton) -> Option<u16> {
+    match button {
+        GamepadButton::A => Some(sys::BTN_A),
+        GamepadButton::B => Some(sys::BTN_B),
+        GamepadButton::X => Some(sys::BTN_X),
+        GamepadButton::Y => Some(sys::BTN_Y),
+        GamepadButton::LeftBumper => Some(sys::BTN_TL),
+        GamepadButton::RightBumper => Some(sys::BTN_TR),
+        GamepadButton::Back => Some(sys::BTN_SELECT),
+        GamepadButton::Start => Some(sys::BTN_START),
+        GamepadButton::Guide => Some(sys::BTN_MODE),
+        GamepadButton::LeftStick => Some(sys::BTN_THUMBL),
+        GamepadButton::RightStick => Some(sys::BTN_THUMBR),
+        GamepadButton::DPadUp
+        | GamepadButton::DPadDown
+        | GamepadButton::DPadLeft
+        | GamepadButton::DPadRight => None,
+    }
+}
+
+/// Converts a [`GamepadAxis`] into an evdev `ABS_*` code.
+pub fn gamepad_axis_to_evdev(axis: GamepadAxis) -> u16 {
+    match axis {
+        GamepadAxis::LeftStickX => sys::ABS_X,
+        GamepadAxis::LeftStickY => sys::ABS_Y,
+        GamepadAxis::RightStickX => sys::ABS_RX,
+        GamepadAxis::RightStickY => sys::ABS_RY,
+        GamepadAxis::LeftTrigger => sys::ABS_Z,
+        GamepadAxis::RightTrigger => sys::ABS_RZ,
+    }
+}
+
+/// Converts the given character into an evdev `KEY_*` code, if possible.
+///
+/// Because evdev codes refer to physical keys, this function assumes a US QWERTY layout. The
+/// `bool` indicates whether the shift key should be pressed.
+pub fn char_to_evdev(c: char) -> Option<(u16, bool)> {
+    match c {
+        'a' => Some((sys::KEY_A, false)),
+        'b' => Some((sys::KEY_B, false)),
+        'c' => Some((sys::KEY_C, false)),
+        'd' => Some((sys::KEY_D, false)),
+        'e' => Some((sys::KEY_E, false)),
+        'f' => Some((sys::KEY_F, false)),
+        'g' => Some((sys::KEY_G, false)),
+        'h' => Some((sys::KEY_H, false)),
+        'i' => Some((sys::KEY_I, false)),
+        'j' => Some((sys::KEY_J, false)),
+        'k' => Some((sys::KEY_K, false)),
+        'l' => Some((sys::KEY_L, false)),
+        'm' => Some((sys::KEY_M, false)),
+        'n' => Some((sys::KEY_N, false)),
+        'o' => Some((sys::KEY_O, false)),
+        'p' => Some((sys::KEY_P, false)),
+        'q' => Some((sys::KEY_Q, false)),
+        'r' => Some((sys::KEY_R, false)),
+        's' => Some((sys::KEY_S, false)),
+        't' => Some((sys::KEY_T, false)),
+        'u' => Some((sys::KEY_U, false)),
+        'v' => Some((sys::KEY_V, false)),
+        'w' => Some((sys::KEY_W, false)),
+        'x' => Some((sys::KEY_X, false)),
+        'y' => Some((sys::KEY_Y, false)),
+        'z' => Some((sys::KEY_Z, false)),
+        'A' => Some((sys::KEY_A, true)),
+        'B' => Some((sys::KEY_B, true)),
+        'C' => Some((sys::KEY_C, true)),
+        'D' => Some((sys::KEY_D, true)),
+        'E' => Some((sys::KEY_E, true)),
+        'F' => Some((sys::KEY_F, true)),
+        'G' => Some((sys::KEY_G, true)),
+        'H' => Some((sys::KEY_H, true)),
+        'I' => Some((sys::KEY_I, true)),
+        'J' => Some((sys::KEY_J, true)),
+        'K' => Some((sys::KEY_K, true)),
+        'L' => Some((sys::KEY_L, true)),
+        'M' => Some((sys::KEY_M, true)),
+        'N' => Some((sys::KEY_N, true)),
+        'O' => Some((sys::KEY_O, true)),
+        'P' => Some((sys::KEY_P, true)),
+        'Q' => Some((sys::KEY_Q, true)),
+        'R' => Some((sys::KEY_R, true)),
+        'S' => Some((sys::KEY_S, true)),
+        'T' => Some((sys::KEY_T, true)),
+        'U' => Some((sys::KEY_U, true)),
+        'V' => Some((sys::KEY_V, true)),
+        'W' => Some((sys::KEY_W, true)),
+        'X' => Some((sys::KEY_X, true)),
+        'Y' => Some((sys::KEY_Y, true)),
+        'Z' => Some((sys::KEY_Z, true)),
+        '0' => Some((sys::KEY_0, false)),
+        '1' => Some((sys::KEY_1, false)),
+        '2' => Some((sys::KEY_2, false)),
+        '3' => Some((sys::KEY_3, false)),
+        '4' => Some((sys::KEY_4, false)),
+        '5' => Some((sys::KEY_5, false)),
+        '6' => Some((sys::KEY_6, false)),
+        '7' => Some((sys::KEY_7, false)),
+        '8' => Some((sys::KEY_8, false)),
+        '9' => Some((sys::KEY_9, false)),
+        ' ' => Some((sys::KEY_SPACE, false)),
+        '!' => Some((sys::KEY_1, true)),
+        '@' => Some((sys::KEY_2, true)),
+        '#' => Some((sys::KEY_3, true)),
+        '$' => Some((sys::KEY_4, true)),
+        '%' => Some((sys::KEY_5, true)),
+        '^' => Some((sys::KEY_6, true)),
+        '&' => Some((sys::KEY_7, true)),
+        '*' => Some((sys::KEY_8, true)),
+        '(' => Some((sys::KEY_9, true)),
+        ')' => Some((sys::KEY_0, true)),
+        '-' => Some((sys::KEY_MINUS, false)),
+        '_' => Some((sys::KEY_MINUS, true)),
+        '=' => Some((sys::KEY_EQUAL, false)),
+        '+' => Some((sys::KEY_EQUAL, true)),
+        '[' => Some((sys::KEY_LEFTBRACE, false)),
+        '{' => Some((sys::KEY_LEFTBRACE, true)),
+        ']' => Some((sys::KEY_RIGHTBRACE, false)),
+        '}' => Some((sys::KEY_RIGHTBRACE, true)),
+        ';' => Some((sys::KEY_SEMICOLON, false)),
+        ':' => Some((sys::KEY_SEMICOLON, true)),
+        '\'' => Some((sys::KEY_APOSTROPHE, false)),
+        '"' => Some((sys::KEY_APOSTROPHE, true)),
+        '`' => Some((sys::KEY_GRAVE, false)),
+        '~' => Some((sys::KEY_GRAVE, true)),
+        '\\' => Some((sys::KEY_BACKSLASH, false)),
+        '|' => Some((sys::KEY_BACKSLASH, true)),
+        ',' => Some((sys::KEY_COMMA, false)),
+        '<' => Some((sys::KEY_COMMA, true)),
+        '.' => Some((sys::KEY_DOT, false)),
+        '>' => Some((sys::KEY_DOT, true)),
+        '/' => Some((sys::KEY_SLASH, false)),
+        '?' => Some((sys::KEY_SLASH, true)),
+        '\n' => Some((sys::KEY_ENTER, false)),
+        '\t' => Some((sys::KEY_TAB, false)),
+        _ => None,
+    }
+}
+
+/// Returns the size of the first framebuffer, which usually matches the resolution of the
+/// primary monitor.
+pub fn framebuffer_size() -> Option<(u32, u32)> {
+    let size = std::fs::read_to_string("/sys/class/graphics/fb0/virtual_size").ok()?;
+    let (width, height) = size.trim().split_once(',')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::NAMED_KEYS;
+
+    #[test]
+    fn every_named_key_has_a_distinct_evdev_code() {
+        let mut codes = Vec::new();
+        for &key in NAMED_KEYS {
+            let code = key_to_evdev(key).unwrap_or_else(|| panic!("{key:?} has no evdev code"));
+            assert!(!codes.contains(&code), "{key:?} shares its evdev code");
+            codes.push(code);
+        }
+    }
+
+    #[test]
+    fn keysyms_are_not_supported() {
+        // The keysym of the **a** key.
+        assert_eq!(key_to_evdev(Key::Keysym(0x61)), None);
+    }
+
+    #[test]
+    fn scancodes_are_limited_to_the_enabled_keys() {
+        assert_eq!(key_to_evdev(Key::Scancode(30)), Some(sys::KEY_A));
+        assert_eq!(key_to_evdev(Key::Scancode(0)), None);
+        assert_eq!(key_to_evdev(Key::Scancode(sys::BTN_LEFT as u32)), None);
+        assert_eq!(key_to_evdev(Key::Scancode(0x1_0000)), None);
+    }
+}
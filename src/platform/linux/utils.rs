@@ -0,0 +1,220 @@
+//! This module provides utility functions shared by the backends that describe keys as keysyms
+//! and buttons as evdev codes.
+
+use xkeysym::key;
+
+#[cfg(any(feature = "wayland", feature = "portal"))]
+use crate::Button;
+use crate::Key;
+
+/// The `BTN_*` evdev codes, as defined in `linux/input-event-codes.h`.
+#[cfg(any(feature = "wayland", feature = "portal"))]
+const BTN_LEFT: u32 = 0x110;
+#[cfg(any(feature = "wayland", feature = "portal"))]
+const BTN_RIGHT: u32 = 0x111;
+#[cfg(any(feature = "wayland", feature = "portal"))]
+const BTN_MIDDLE: u32 = 0x112;
+#[cfg(any(feature = "wayland", feature = "portal"))]
+const BTN_SIDE: u32 = 0x113;
+#[cfg(any(feature = "wayland", feature = "portal"))]
+const BTN_TASK: u32 = 0x117;
+
+/// Converts a [`Key`] into a keysym.
+pub fn key_to_keysym(key: Key) -> Option<u32> {
+    match key {
+        Key::A => Some(key::A),
+        Key::B => Some(key::B),
+        Key::C => Some(key::C),
+        Key::D => Some(key::D),
+        Key::E => Some(key::E),
+        Key::F => Some(key::F),
+        Key::G => Some(key::G),
+        Key::H => Some(key::H),
+        Key::I => Some(key::I),
+        Key::J => Some(key::J),
+        Key::K => Some(key::K),
+        Key::L => Some(key::L),
+        Key::M => Some(key::M),
+        Key::N => Some(key::N),
+        Key::O => Some(key::O),
+        Key::P => Some(key::P),
+        Key::Q => Some(key::Q),
+        Key::R => Some(key::R),
+        Key::S => Some(key::S),
+        Key::T => Some(key::T),
+        Key::U => Some(key::U),
+        Key::V => Some(key::V),
+        Key::W => Some(key::W),
+        Key::X => Some(key::X),
+        Key::Y => Some(key::Y),
+        Key::Z => Some(key::Z),
+        Key::F1 => Some(key::F1),
+        Key::F2 => Some(key::F2),
+        Key::F3 => Some(key::F3),
+        Key::F4 => Some(key::F4),
+        Key::F5 => Some(key::F5),
+        Key::F6 => Some(key::F6),
+        Key::F7 => Some(key::F7),
+        Key::F8 => Some(key::F8),
+        Key::F9 => Some(key::F9),
+        Key::F10 => Some(key::F10),
+        Key::F11 => Some(key::F11),
+        Key::F12 => Some(key::F12),
+        Key::F13 => Some(key::F13),
+        Key::F14 => Some(key::F14),
+        Key::F15 => Some(key::F15),
+        Key::F16 => Some(key::F16),
+        Key::F17 => Some(key::F17),
+        Key::F18 => Some(key::F18),
+        Key::F19 => Some(key::F19),
+        Key::F20 => Some(key::F20),
+        Key::F21 => Some(key::F21),
+        Key::F22 => Some(key::F22),
+        Key::F23 => Some(key::F23),
+        Key::F24 => Some(key::F24),
+        Key::Zero => Some(key::_0),
+        Key::One => Some(key::_1),
+        Key::Two => Some(key::_2),
+        Key::Three => Some(key::_3),
+        Key::Four => Some(key::_4),
+        Key::Five => Some(key::_5),
+        Key::Six => Some(key::_6),
+        Key::Seven => Some(key::_7),
+        Key::Eight => Some(key::_8),
+        Key::Nine => Some(key::_9),
+        Key::Minus => Some(key::minus),
+        Key::Equal => Some(key::equal),
+        Key::BracketLeft => Some(key::bracketleft),
+        Key::BracketRight => Some(key::bracketright),
+        Key::Semicolon => Some(key::semicolon),
+        Key::Quote => Some(key::apostrophe),
+        Key::Comma => Some(key::comma),
+        Key::Period => Some(key::period),
+        Key::Slash => Some(key::slash),
+        Key::Backslash => Some(key::backslash),
+        Key::Grave => Some(key::grave),
+        Key::Escape => Some(key::Escape),
+        Key::Tab => Some(key::Tab),
+        Key::CapsLock => Some(key::Caps_Lock),
+        Key::LeftShift => Some(key::Shift_L),
+        Key::LeftControl => Some(key::Control_L),
+        Key::LeftAlt => Some(key::Alt_L),
+        Key::LeftMeta => Some(key::Meta_L),
+        Key::Space => Some(key::space),
+        Key::RightMeta => Some(key::Meta_R),
+        Key::RightAlt => Some(key::Alt_R),
+        Key::RightControl => Some(key::Control_R),
+        Key::RightShift => Some(key::Shift_R),
+        Key::Enter => Some(key::Return),
+        Key::Backspace => Some(key::BackSpace),
+        Key::PrintScreen => Some(key::Print),
+        Key::ScrollLock => Some(key::Scroll_Lock),
+        Key::Pause => Some(key::Pause),
+        Key::ContextMenu => Some(key::Menu),
+        Key::Insert => Some(key::Insert),
+        Key::Delete => Some(key::Delete),
+        Key::Home => Some(key::Home),
+        Key::End => Some(key::End),
+        Key::PageUp => Some(key::Page_Up),
+        Key::PageDown => Some(key::Page_Down),
+        Key::ArrowUp => Some(key::Up),
+        Key::ArrowDown => Some(key::Down),
+        Key::ArrowLeft => Some(key::Left),
+        Key::ArrowRight => Some(key::Right),
+        Key::NumLock => Some(key::Num_Lock),
+        Key::NumpadEqual => Some(key::KP_Equal),
+        Key::NumpadDivide => Some(key::KP_Divide),
+        Key::NumpadMultiply => Some(key::KP_Multiply),
+        Key::NumpadSubtract => Some(key::KP_Subtract),
+        Key::NumpadAdd => Some(key::KP_Add),
+        Key::NumpadEnter => Some(key::KP_Enter),
+        Key::NumpadDecimal => Some(key::KP_Decimal),
+        Key::Numpad0 => Some(key::KP_0),
+        Key::Numpad1 => Some(key::KP_1),
+        Key::Numpad2 => Some(key::KP_2),
+        Key::Numpad3 => Some(key::KP_3),
+        Key::Numpad4 => Some(key::KP_4),
+        Key::Numpad5 => Some(key::KP_5),
+        Key::Numpad6 => Some(key::KP_6),
+        Key::Numpad7 => Some(key::KP_7),
+        Key::Numpad8 => Some(key::KP_8),
+        Key::Numpad9 => Some(key::KP_9),
+        Key::VolumeUp => Some(key::XF86_AudioRaiseVolume),
+        Key::VolumeDown => Some(key::XF86_AudioLowerVolume),
+        Key::VolumeMute => Some(key::XF86_AudioMute),
+        Key::MediaNext => Some(key::XF86_AudioNext),
+        Key::MediaPrevious => Some(key::XF86_AudioPrev),
+        Key::MediaStop => Some(key::XF86_AudioStop),
+        Key::MediaPause => Some(key::XF86_AudioPause),
+        Key::MediaPlay => Some(key::XF86_AudioPlay),
+        Key::BrowserBack => Some(key::XF86_Back),
+        Key::BrowserForward => Some(key::XF86_Forward),
+        Key::BrowserRefresh => Some(key::XF86_Reload),
+        Key::BrightnessUp => Some(key::XF86_MonBrightnessUp),
+        Key::BrightnessDown => Some(key::XF86_MonBrightnessDown),
+        Key::LaunchMail => Some(key::XF86_Mail),
+        Key::LaunchCalculator => Some(key::XF86_Calculator),
+        Key::Keysym(keysym) => Some(keysym),
+        Key::Scancode(_) => None,
+    }
+}
+
+/// Converts a [`Button`] into an evdev `BTN_*` code.
+#[cfg(any(feature = "wayland", feature = "portal"))]
+pub fn button_to_evdev(button: Button) -> Option<u32> {
+    match button {
+        Button::Left => Some(BTN_LEFT),
+        Button::Middle => Some(BTN_MIDDLE),
+        Button::Right => Some(BTN_RIGHT),
+        Button::Extra(n) => {
+            let code = BTN_SIDE + n as u32;
+            (code <= BTN_TASK).then_some(code)
+        }
+    }
+}
+
+/// Converts the given character into a keysym, if possible.
+pub fn char_to_keysym(c: char) -> Option<u32> {
+    // `xkb_utf32_to_keysym` maps the line feed character to the `Linefeed` keysym, which most
+    // applications ignore.
+    if c == '\n' {
+        return Some(key::Return);
+    }
+
+    match xkeysym::Keysym::from_char(c).raw() {
+        key::NoSymbol => None,
+        keysym => Some(keysym),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::NAMED_KEYS;
+
+    #[test]
+    fn every_named_key_has_a_distinct_keysym() {
+        let mut keysyms = Vec::new();
+        for &key in NAMED_KEYS {
+            let keysym = key_to_keysym(key).unwrap_or_else(|| panic!("{key:?} has no keysym"));
+            assert_ne!(keysym, key::NoSymbol, "{key:?} has no keysym");
+            assert!(!keysyms.contains(&keysym), "{key:?} shares its keysym");
+            keysyms.push(keysym);
+        }
+    }
+
+    #[test]
+    fn keysyms_are_passed_through() {
+        assert_eq!(
+            key_to_keysym(Key::Keysym(key::XF86_Search)),
+            Some(key::XF86_Search)
+        );
+    }
+
+    /// Scan codes have no keysym, which is why the Wayland backend rejects them. The portal and
+    /// x11rb backends send them without going through this function.
+    #[test]
+    fn scancodes_have_no_keysym() {
+        assert_eq!(key_to_keysym(Key::Scancode(30)), None);
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+
+use wayland_client::backend::WaylandError;
+use wayland_client::globals::GlobalError;
+use wayland_client::{ConnectError, DispatchError};
+
+use crate::{Button, Key};
+
+/// An error that may occur when interacting with a Wayland compositor.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occured when connecting to the Wayland compositor.
+    Connect(ConnectError),
+    /// An error occured when listing the globals advertised by the compositor.
+    Registry(GlobalError),
+    /// The compositor does not advertise a global required by the simulator.
+    ///
+    /// The inner value is the name of the missing interface.
+    MissingGlobal(&'static str),
+    /// The connection with the compositor failed.
+    Connection(WaylandError),
+    /// An event sent by the compositor could not be dispatched.
+    Dispatch(DispatchError),
+    /// An error occured when creating the file used to share the keymap with the compositor.
+    Keymap(io::Error),
+    /// The keymap of the virtual keyboard is full of held keys, and cannot bind new keysyms.
+    KeymapFull,
+    /// The compositor did not advertise any output, making absolute pointer motion impossible.
+    NoOutput,
+    /// The key is not supported.
+    UnsupportedKey(Key),
+    /// The button is not supported.
+    UnsupportedButton(Button),
+    /// The character is not supported.
+    UnsupportedChar(char),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Connect(e) => write!(f, "failed to connect to the Wayland compositor: {e}"),
+            Self::Registry(e) => write!(f, "failed to list the Wayland globals: {e}"),
+            Self::MissingGlobal(name) => {
+                write!(f, "the Wayland compositor does not support '{name}'")
+            }
+            Self::Connection(e) => write!(f, "the connection with the compositor failed: {e}"),
+            Self::Dispatch(e) => write!(f, "failed to dispatch a Wayland event: {e}"),
+            Self::Keymap(e) => write!(f, "failed to create the keymap file: {e}"),
+            Self::KeymapFull => f.write_str("too many keys are held to bind new keysyms"),
+            Self::NoOutput => f.write_str("the Wayland compositor has no output"),
+            Self::UnsupportedKey(k) => write!(f, "the '{k:?}' key is not supported on Wayland"),
+            Self::UnsupportedButton(b) => {
+                write!(f, "the '{b:?}' button is not supported on Wayland")
+            }
+            Self::UnsupportedChar(c) => write!(f, "the {c:?} char is not supported on Wayland"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Connect(e) => Some(e),
+            Self::Registry(e) => Some(e),
+            Self::Connection(e) => Some(e),
+            Self::Dispatch(e) => Some(e),
+            Self::Keymap(e) => Some(e),
+            _ => None,
+        }
+    }
+}
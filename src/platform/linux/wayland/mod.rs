@@ -0,0 +1,14 @@
+//! This module provides the implementation of `keysim` when using a Wayland compositor.
+//!
+//! Keyboard events are sent through the `zwp_virtual_keyboard_v1` protocol, and pointer events
+//! through the `zwlr_virtual_pointer_v1` protocol. Because the virtual keyboard uses its own
+//! keymap, any character can be typed, regardless of the layout configured by the user.
+
+mod simulator;
+pub use self::simulator::*;
+
+mod error;
+pub use self::error::*;
+
+mod sys;
+mod utils;
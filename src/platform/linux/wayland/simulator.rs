@@ -0,0 +1,512 @@
+use std::cell::{Cell, RefCell};
+use std::os::unix::io::AsFd;
+use std::time::Instant;
+
+use wayland_client::globals::{registry_queue_init, BindError, GlobalListContents};
+use wayland_client::protocol::wl_keyboard::{KeyState, KeymapFormat};
+use wayland_client::protocol::wl_output::{self, Transform, WlOutput};
+use wayland_client::protocol::wl_pointer::{Axis, AxisSource, ButtonState};
+use wayland_client::protocol::wl_registry::WlRegistry;
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::{delegate_noop, Connection, Dispatch, Proxy, QueueHandle, WEnum};
+use wayland_protocols_misc::zwp_virtual_keyboard_v1::client::zwp_virtual_keyboard_manager_v1::ZwpVirtualKeyboardManagerV1;
+use wayland_protocols_misc::zwp_virtual_keyboard_v1::client::zwp_virtual_keyboard_v1::ZwpVirtualKeyboardV1;
+use wayland_protocols_wlr::virtual_pointer::v1::client::zwlr_virtual_pointer_manager_v1::ZwlrVirtualPointerManagerV1;
+use wayland_protocols_wlr::virtual_pointer::v1::client::zwlr_virtual_pointer_v1::ZwlrVirtualPointerV1;
+
+use super::{sys, utils};
+use crate::platform::utils::{button_to_evdev, char_to_keysym, key_to_keysym};
+use crate::{Button, Capabilities, Fidelity, Key, Screen, UnicodeSupport};
+
+/// The maximum number of keysyms that can be bound in the keymap of the virtual keyboard.
+///
+/// Clients running through XWayland cannot see keycodes above 255.
+const MAX_KEYSYMS: usize = 255 - 8;
+
+/// The simulator used when using a Wayland compositor.
+pub struct Simulator {
+    /// The connection with the compositor.
+    connection: Connection,
+    /// The virtual keyboard used to simulate keystrokes.
+    keyboard: ZwpVirtualKeyboardV1,
+    /// The virtual pointer used to simulate button presses.
+    pointer: ZwlrVirtualPointerV1,
+
+    /// The keysyms bound in the keymap that was last uploaded to the compositor.
+    ///
+    /// The `n`th keysym of this list is bound to the evdev keycode `n + 1`.
+    keysyms: RefCell<Vec<u32>>,
+    /// The keysyms pressed with [`press_key`](Self::press_key) and not released yet.
+    ///
+    /// They must remain bound to the same keycode until they are released.
+    held: RefCell<Vec<u32>>,
+
+    /// The area covered by each output of the compositor, when the simulator was created.
+    outputs: Vec<utils::Rect>,
+    /// The area covered by the outputs of the compositor, when the simulator was created.
+    ///
+    /// This is `None` if the compositor had no output.
+    layout: Option<utils::Rect>,
+    /// The position the pointer was last moved to, if it is known.
+    ///
+    /// The compositor does not report the position of the pointer to its clients.
+    position: Cell<Option<(i32, i32)>>,
+
+    /// The instant at which the simulator was created. Event timestamps are relative to it.
+    epoch: Instant,
+}
+
+impl Simulator {
+    /// Creates a new [`Simulator`] instance.
+    pub fn new() -> Result<Self, super::Error> {
+        let connection = Connection::connect_to_env().map_err(super::Error::Connect)?;
+        let (globals, mut queue) =
+            registry_queue_init::<State>(&connection).map_err(super::Error::Registry)?;
+        let qh = queue.handle();
+
+        let seat: WlSeat = globals
+            .bind(&qh, 1..=1, ())
+            .map_err(missing_global::<WlSeat>)?;
+        let keyboard_manager: ZwpVirtualKeyboardManagerV1 = globals
+            .bind(&qh, 1..=1, ())
+            .map_err(missing_global::<ZwpVirtualKeyboardManagerV1>)?;
+        let pointer_manager: ZwlrVirtualPointerManagerV1 = globals
+            .bind(&qh, 1..=1, ())
+            .map_err(missing_global::<ZwlrVirtualPointerManagerV1>)?;
+
+        let keyboard = keyboard_manager.create_virtual_keyboard(&seat, &qh, ());
+        let pointer = pointer_manager.create_virtual_pointer(Some(&seat), &qh, ());
+
+        // The outputs are needed to map absolute positions onto the layout of the compositor.
+        let outputs: Vec<WlOutput> = globals.contents().with_list(|list| {
+            list.iter()
+                .filter(|global| global.interface == WlOutput::interface().name)
+                .enumerate()
+                .map(|(index, global)| {
+                    globals
+                        .registry()
+                        .bind(global.name, global.version.min(3), &qh, index)
+                })
+                .collect()
+        });
+
+        // Make sure that the compositor accepted our requests, and receive the description of
+        // the outputs. The keymap of the virtual keyboard is only uploaded once a key needs to be
+        // sent.
+        let mut state = State {
+            outputs: vec![Output::default(); outputs.len()],
+        };
+        queue
+            .roundtrip(&mut state)
+            .map_err(super::Error::Dispatch)?;
+
+        for output in outputs {
+            if output.version() >= 3 {
+                output.release();
+            }
+        }
+
+        let outputs: Vec<utils::Rect> = state
+            .outputs
+            .iter()
+            .map(Output::logical)
+            .filter(|r| r.width > 0 && r.height > 0)
+            .collect();
+
+        Ok(Self {
+            connection,
+            keyboard,
+            pointer,
+            keysyms: RefCell::new(Vec::new()),
+            held: RefCell::new(Vec::new()),
+            layout: utils::bounding_box(outputs.iter().copied()),
+            outputs,
+            position: Cell::new(None),
+            epoch: Instant::now(),
+        })
+    }
+
+    /// Returns the timestamp of an event sent now, in milliseconds.
+    fn time(&self) -> u32 {
+        self.epoch.elapsed().as_millis() as u32
+    }
+
+    /// Uploads a keymap binding `keysyms` to the compositor.
+    fn upload_keymap(&self, keysyms: &[u32]) -> Result<(), super::Error> {
+        let keymap = utils::make_keymap(keysyms);
+        let file = sys::keymap_file(&keymap)?;
+
+        self.keyboard.keymap(
+            KeymapFormat::XkbV1 as u32,
+            file.as_fd(),
+            keymap.len() as u32 + 1,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the evdev keycodes bound to `keysyms`, uploading a new keymap if some of them are
+    /// not bound yet.
+    ///
+    /// `keysyms` must not contain more than [`MAX_KEYSYMS`] distinct keysyms. Fewer can be bound
+    /// while keys are held.
+    fn keycodes(&self, keysyms: &[u32]) -> Result<Vec<u32>, super::Error> {
+        let mut bound = self.keysyms.borrow_mut();
+
+        // Only remember the new bindings once the compositor has been told about them.
+        let mut next = bound.clone();
+        let changed = utils::bind_keysyms(&mut next, keysyms, &self.held.borrow(), MAX_KEYSYMS)
+            .ok_or(super::Error::KeymapFull)?;
+        if changed {
+            self.upload_keymap(&next)?;
+            *bound = next;
+        }
+
+        Ok(keysyms
+            .iter()
+            .map(|keysym| {
+                // All the keysyms have been bound above.
+                let index = bound.iter().position(|k| k == keysym).unwrap();
+                index as u32 + 1
+            })
+            .collect())
+    }
+
+    /// Sends a key event.
+    fn key_event(&self, keycode: u32, press: bool) {
+        let state = match press {
+            true => KeyState::Pressed,
+            false => KeyState::Released,
+        };
+
+        self.keyboard.key(self.time(), keycode, state as u32);
+    }
+
+    /// Sends a button event.
+    fn button_event(&self, button: u32, press: bool) {
+        let state = match press {
+            true => ButtonState::Pressed,
+            false => ButtonState::Released,
+        };
+
+        self.pointer.button(self.time(), button, state);
+        self.pointer.frame();
+    }
+
+    /// Wraps the [`Connection::flush`] function.
+    fn flush(&self) -> Result<(), super::Error> {
+        self.connection.flush().map_err(super::Error::Connection)
+    }
+
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        // Any keysym can be bound in the keymap of the virtual keyboard.
+        Capabilities::new("wayland", Fidelity::Native, 5, UnicodeSupport::Full)
+    }
+
+    /// Sends a fake key press event to the focused surface.
+    pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
+        let keysym = key_to_keysym(key).ok_or(super::Error::UnsupportedKey(key))?;
+        let keycodes = self.keycodes(&[keysym])?;
+        self.key_event(keycodes[0], true);
+        self.held.borrow_mut().push(keysym);
+        self.flush()
+    }
+
+    /// Sends a fake key release event to the focused surface.
+    pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
+        let keysym = key_to_keysym(key).ok_or(super::Error::UnsupportedKey(key))?;
+        let keycodes = self.keycodes(&[keysym])?;
+        self.key_event(keycodes[0], false);
+
+        let mut held = self.held.borrow_mut();
+        if let Some(index) = held.iter().position(|&k| k == keysym) {
+            held.swap_remove(index);
+        }
+
+        self.flush()
+    }
+
+    /// Sends a fake keystroke event to the focused surface.
+    pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
+        let keysym = key_to_keysym(key).ok_or(super::Error::UnsupportedKey(key))?;
+        let keycodes = self.keycodes(&[keysym])?;
+        self.key_event(keycodes[0], true);
+        self.key_event(keycodes[0], false);
+        self.flush()
+    }
+
+    /// Sends a fake button press to the focused surface.
+    pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.button_event(code, true);
+        self.flush()
+    }
+
+    /// Sends a fake button release to the focused surface.
+    pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.button_event(code, false);
+        self.flush()
+    }
+
+    /// Sends a fake button click to the focused surface.
+    pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
+        let code = button_to_evdev(button).ok_or(super::Error::UnsupportedButton(button))?;
+        self.button_event(code, true);
+        self.button_event(code, false);
+        self.flush()
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`) of the compositor's layout.
+    ///
+    /// The layout of the outputs is only retrieved when the simulator is created.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        let layout = self.layout.ok_or(super::Error::NoOutput)?;
+
+        // The position is relative to the top-left corner of the layout, and must fall within
+        // its extents.
+        let x = (x - layout.x).clamp(0, layout.width - 1);
+        let y = (y - layout.y).clamp(0, layout.height - 1);
+
+        self.pointer.motion_absolute(
+            self.time(),
+            x as u32,
+            y as u32,
+            layout.width as u32,
+            layout.height as u32,
+        );
+        self.pointer.frame();
+        self.flush()?;
+
+        self.position.set(Some((x + layout.x, y + layout.y)));
+        Ok(())
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        let time = self.time();
+
+        self.pointer.axis_source(AxisSource::Wheel);
+        for (axis, clicks) in [(Axis::VerticalScroll, dy), (Axis::HorizontalScroll, dx)] {
+            if clicks != 0 {
+                let value = clicks as f64 * utils::SCROLL_DISTANCE;
+                self.pointer.axis_discrete(time, axis, value, clicks);
+            }
+        }
+        self.pointer.frame();
+        self.flush()
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks, which may be fractional.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        if !dx.is_finite() || !dy.is_finite() {
+            return Ok(());
+        }
+
+        let time = self.time();
+
+        // Without a discrete value, clients scroll by the exact distance.
+        self.pointer.axis_source(AxisSource::Wheel);
+        for (axis, clicks) in [(Axis::VerticalScroll, dy), (Axis::HorizontalScroll, dx)] {
+            if clicks != 0.0 {
+                self.pointer
+                    .axis(time, axis, clicks * utils::SCROLL_DISTANCE);
+            }
+        }
+        self.pointer.frame();
+        self.flush()
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) relative to its current position.
+    ///
+    /// The protocol accepts fractional deltas, which are accumulated by the compositor.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        if !dx.is_finite() || !dy.is_finite() {
+            return Ok(());
+        }
+
+        self.pointer.motion(self.time(), dx, dy);
+        self.pointer.frame();
+
+        // Pointer acceleration makes the resulting position unpredictable.
+        self.position.set(None);
+        self.flush()
+    }
+
+    /// Returns the position the mouse pointer was last moved to by
+    /// [`move_mouse_to`](Self::move_mouse_to), if it has not been moved by a relative motion
+    /// since.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        Ok(self.position.get())
+    }
+
+    /// Returns the area covered by each output of the compositor, in its logical coordinate
+    /// space.
+    ///
+    /// The layout of the outputs is only retrieved when the simulator is created.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        Ok(self
+            .outputs
+            .iter()
+            .map(|r| Screen::new(r.x, r.y, r.width as u32, r.height as u32))
+            .collect())
+    }
+
+    /// Sends a specific unicode code-point.
+    pub fn send_char(&self, c: char) -> Result<(), super::Error> {
+        self.send_chars(std::iter::once(c))
+    }
+
+    /// Sends a collection of characters.
+    ///
+    /// The keymap of the virtual keyboard is updated at most once for the whole collection.
+    pub fn send_chars(&self, it: impl Iterator<Item = char>) -> Result<(), super::Error> {
+        let keysyms = it
+            .map(|c| char_to_keysym(c).ok_or(super::Error::UnsupportedChar(c)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for chunk in keysyms.chunks(MAX_KEYSYMS) {
+            let keycodes = self.keycodes(chunk)?;
+
+            for keycode in keycodes {
+                self.key_event(keycode, true);
+                self.key_event(keycode, false);
+            }
+        }
+
+        self.flush()
+    }
+
+    /// Sends a string.
+    pub fn send_str(&self, s: &str) -> Result<(), super::Error> {
+        self.send_chars(s.chars())
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        self.keyboard.destroy();
+        self.pointer.destroy();
+        let _ = self.connection.flush();
+    }
+}
+
+/// Converts a [`BindError`] into an error naming the interface `I`.
+fn missing_global<I: Proxy>(_: BindError) -> super::Error {
+    super::Error::MissingGlobal(I::interface().name)
+}
+
+/// The state used to dispatch the events sent by the compositor.
+///
+/// Only the description of the outputs is relevant to the simulator.
+struct State {
+    /// The outputs advertised by the compositor, indexed by the user data of their proxy.
+    outputs: Vec<Output>,
+}
+
+/// The description of an output.
+#[derive(Clone, Copy)]
+struct Output {
+    /// The position of the output in the compositor's layout.
+    x: i32,
+    y: i32,
+    /// The size of the current mode of the output, in physical pixels.
+    width: i32,
+    height: i32,
+    /// The scaling factor of the output.
+    scale: i32,
+    /// Whether the output is rotated by a quarter turn.
+    rotated: bool,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            scale: 1,
+            rotated: false,
+        }
+    }
+}
+
+impl Output {
+    /// Returns the area covered by the output in the compositor's layout.
+    fn logical(&self) -> utils::Rect {
+        let scale = self.scale.max(1);
+        let (width, height) = match self.rotated {
+            true => (self.height, self.width),
+            false => (self.width, self.height),
+        };
+
+        utils::Rect {
+            x: self.x,
+            y: self.y,
+            width: width / scale,
+            height: height / scale,
+        }
+    }
+}
+
+impl Dispatch<WlOutput, usize> for State {
+    fn event(
+        state: &mut Self,
+        _: &WlOutput,
+        event: wl_output::Event,
+        &index: &usize,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let output = &mut state.outputs[index];
+
+        match event {
+            wl_output::Event::Geometry {
+                x, y, transform, ..
+            } => {
+                output.x = x;
+                output.y = y;
+                output.rotated = matches!(
+                    transform,
+                    WEnum::Value(
+                        Transform::_90
+                            | Transform::_270
+                            | Transform::Flipped90
+                            | Transform::Flipped270
+                    )
+                );
+            }
+            wl_output::Event::Mode {
+                flags: WEnum::Value(flags),
+                width,
+                height,
+                ..
+            } if flags.contains(wl_output::Mode::Current) => {
+                output.width = width;
+                output.height = height;
+            }
+            wl_output::Event::Scale { factor } => output.scale = factor,
+            _ => (),
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(State: ignore WlSeat);
+delegate_noop!(State: ZwpVirtualKeyboardManagerV1);
+delegate_noop!(State: ZwpVirtualKeyboardV1);
+delegate_noop!(State: ZwlrVirtualPointerManagerV1);
+delegate_noop!(State: ZwlrVirtualPointerV1);
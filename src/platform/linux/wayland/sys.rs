@@ -0,0 +1,28 @@
+//! Wraps the calls to `libc` required by the Wayland backend.
+
+use std::fs::File;
+use std::io::Write;
+use std::os::unix::io::FromRawFd;
+
+/// Creates an anonymous file containing `contents`, followed by a nul byte.
+///
+/// The returned file can be shared with the compositor to upload a keymap.
+pub fn keymap_file(contents: &str) -> Result<File, super::Error> {
+    // Safety:
+    //  The name is a valid nul-terminated string.
+    let fd = unsafe { libc::memfd_create(c"keysim-keymap".as_ptr(), libc::MFD_CLOEXEC) };
+
+    if fd < 0 {
+        return Err(super::Error::Keymap(std::io::Error::last_os_error()));
+    }
+
+    // Safety:
+    //  `memfd_create` returned a valid file descriptor, which we now own.
+    let mut file = unsafe { File::from_raw_fd(fd) };
+
+    file.write_all(contents.as_bytes())
+        .and_then(|()| file.write_all(&[0]))
+        .map_err(super::Error::Keymap)?;
+
+    Ok(file)
+}
@@ -0,0 +1,205 @@
+//! This module provides utility functions to work with the Wayland protocols.
+
+use xkeysym::key;
+
+/// The distance scrolled by a single wheel click, in the units of `wl_pointer.axis`.
+///
+/// This matches the value reported by `libinput` for most mice.
+pub const SCROLL_DISTANCE: f64 = 15.0;
+
+/// Returns the name of the real modifier that `keysym` should be bound to, if any.
+fn keysym_modifier(keysym: u32) -> Option<&'static str> {
+    match keysym {
+        key::Shift_L | key::Shift_R => Some("Shift"),
+        key::Caps_Lock => Some("Lock"),
+        key::Control_L | key::Control_R => Some("Control"),
+        key::Alt_L | key::Alt_R | key::Meta_L | key::Meta_R => Some("Mod1"),
+        key::Num_Lock => Some("Mod2"),
+        key::Super_L | key::Super_R => Some("Mod4"),
+        _ => None,
+    }
+}
+
+/// Generates an XKB keymap in which the `n`th keysym of `keysyms` is bound to the evdev keycode
+/// `n + 1`.
+pub fn make_keymap(keysyms: &[u32]) -> String {
+    let mut keycodes = String::new();
+    let mut symbols = String::new();
+
+    for (i, &keysym) in keysyms.iter().enumerate() {
+        keycodes.push_str(&format!("    <K{}> = {};\n", i + 1, i + 9));
+        symbols.push_str(&format!("    key <K{}> {{ [ {keysym:#x} ] }};\n", i + 1));
+
+        if let Some(modifier) = keysym_modifier(keysym) {
+            symbols.push_str(&format!(
+                "    modifier_map {modifier} {{ <K{}> }};\n",
+                i + 1
+            ));
+        }
+    }
+
+    format!(
+        "xkb_keymap {{\n\
+         xkb_keycodes \"keysim\" {{\n    minimum = 8;\n    maximum = {};\n{keycodes}}};\n\
+         xkb_types \"keysim\" {{ include \"complete\" }};\n\
+         xkb_compatibility \"keysim\" {{ include \"complete\" }};\n\
+         xkb_symbols \"keysim\" {{\n{symbols}}};\n\
+         }};\n",
+        keysyms.len() + 8,
+    )
+}
+
+/// Binds the `needed` keysyms in `bound`, the list of the keysyms bound in the keymap of the
+/// virtual keyboard, which can hold at most `capacity` keysyms.
+///
+/// Once the keymap is full, the keysyms that are neither needed nor `held` are replaced in place,
+/// so that the keysyms that remain bound keep their keycode. This matters for the held keys,
+/// which must be released through the keycode they were pressed with.
+///
+/// Returns whether `bound` changed, or `None` if the keysyms do not fit in the keymap.
+pub fn bind_keysyms(
+    bound: &mut Vec<u32>,
+    needed: &[u32],
+    held: &[u32],
+    capacity: usize,
+) -> Option<bool> {
+    let mut missing = Vec::new();
+    for &keysym in needed {
+        if !bound.contains(&keysym) && !missing.contains(&keysym) {
+            missing.push(keysym);
+        }
+    }
+
+    if missing.is_empty() {
+        return Some(false);
+    }
+
+    let free = capacity.saturating_sub(bound.len());
+    let evictable = bound
+        .iter()
+        .filter(|k| !needed.contains(k) && !held.contains(k))
+        .count();
+    if missing.len() > free + evictable {
+        return None;
+    }
+
+    // Append while there is room, and only evict keysyms once the keymap is full.
+    let mut missing = missing.into_iter();
+    bound.extend(missing.by_ref().take(free));
+    for slot in bound.iter_mut() {
+        if needed.contains(slot) || held.contains(slot) {
+            continue;
+        }
+        match missing.next() {
+            Some(keysym) => *slot = keysym,
+            None => break,
+        }
+    }
+
+    Some(true)
+}
+
+/// A rectangle in the logical coordinate space of the compositor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Returns the smallest rectangle containing all the non-empty `rects`.
+pub fn bounding_box(rects: impl IntoIterator<Item = Rect>) -> Option<Rect> {
+    rects
+        .into_iter()
+        .filter(|r| r.width > 0 && r.height > 0)
+        .reduce(|a, b| {
+            let x = a.x.min(b.x);
+            let y = a.y.min(b.y);
+            Rect {
+                x,
+                y,
+                width: (a.x + a.width).max(b.x + b.width) - x,
+                height: (a.y + a.height).max(b.y + b.height) - y,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keymap_binds_keysyms_in_order() {
+        let keymap = make_keymap(&[key::a, key::Shift_L, 0x100_20ac]);
+
+        assert!(keymap.contains("minimum = 8;\n    maximum = 11;\n"));
+        assert!(keymap.contains("<K1> = 9;\n    <K2> = 10;\n    <K3> = 11;\n"));
+        assert!(keymap.contains("key <K1> { [ 0x61 ] };"));
+        assert!(keymap.contains("key <K2> { [ 0xffe1 ] };"));
+        assert!(keymap.contains("key <K3> { [ 0x10020ac ] };"));
+        assert!(!keymap.contains("<K4>"));
+    }
+
+    #[test]
+    fn keymap_maps_modifiers() {
+        let keymap = make_keymap(&[key::a, key::Shift_L, key::Control_R, key::Super_L]);
+
+        assert!(keymap.contains("modifier_map Shift { <K2> };"));
+        assert!(keymap.contains("modifier_map Control { <K3> };"));
+        assert!(keymap.contains("modifier_map Mod4 { <K4> };"));
+        assert_eq!(keymap.matches("modifier_map").count(), 3);
+    }
+
+    #[test]
+    fn empty_keymap_is_well_formed() {
+        let keymap = make_keymap(&[]);
+
+        assert!(keymap.contains("maximum = 8;"));
+        assert!(keymap.starts_with("xkb_keymap {\n"));
+        assert!(keymap.ends_with("};\n"));
+        assert_eq!(keymap.matches('{').count(), keymap.matches('}').count());
+    }
+
+    #[test]
+    fn bound_keysyms_are_reused() {
+        let mut bound = vec![1, 2, 3];
+        assert_eq!(bind_keysyms(&mut bound, &[3, 1], &[], 4), Some(false));
+        assert_eq!(bound, [1, 2, 3]);
+    }
+
+    #[test]
+    fn missing_keysyms_are_appended_once() {
+        let mut bound = vec![1, 2];
+        assert_eq!(bind_keysyms(&mut bound, &[4, 2, 4, 5], &[], 8), Some(true));
+        assert_eq!(bound, [1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn full_keymaps_are_rebound_in_place() {
+        let mut bound = vec![1, 2, 3, 4];
+        assert_eq!(bind_keysyms(&mut bound, &[5, 3, 6], &[], 4), Some(true));
+        assert_eq!(bound, [5, 6, 3, 4]);
+    }
+
+    #[test]
+    fn free_slots_are_used_before_evicting() {
+        let mut bound = vec![1, 2, 3];
+        assert_eq!(bind_keysyms(&mut bound, &[5, 6], &[], 4), Some(true));
+        assert_eq!(bound, [6, 2, 3, 5]);
+    }
+
+    #[test]
+    fn held_keysyms_keep_their_keycode() {
+        let mut bound = vec![1, 2, 3, 4];
+        assert_eq!(bind_keysyms(&mut bound, &[5, 6], &[1, 3], 4), Some(true));
+        assert_eq!(bound, [1, 5, 3, 6]);
+    }
+
+    #[test]
+    fn held_keysyms_are_never_evicted() {
+        let mut bound = vec![1, 2, 3, 4];
+        assert_eq!(bind_keysyms(&mut bound, &[5, 6], &[1, 2, 3], 4), None);
+        assert_eq!(bound, [1, 2, 3, 4]);
+    }
+}
@@ -1,17 +1,22 @@
 use std::fmt;
 use std::fmt::Display;
+use std::io;
 
-use crate::Key;
+use crate::{Button, Key};
 
 /// An error that may occur when interacting with the X11 window manager.
 #[derive(Debug)]
 pub enum Error {
     /// An error occured when opening an X11 display instance.
     OpenDisplay,
+    /// The credentials used to connect to the X server could not be read.
+    Authority(io::Error),
     /// The X11 server behaved in an unexpected way.
     Unexpected,
     /// The key is not supported.
     UnsupportedKey(Key),
+    /// The button is not supported.
+    UnsupportedButton(Button),
     /// The character is not supported.
     UnsupportedChar(char),
 }
@@ -20,11 +25,22 @@ impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::OpenDisplay => f.write_str("failed to open a connection with the X server"),
+            Self::Authority(e) => write!(f, "failed to read the X authority: {e}"),
             Self::Unexpected => f.write_str("the X server behaved in an unexpected way"),
             Self::UnsupportedKey(k) => write!(f, "the X server does not support the '{k:?}' key"),
+            Self::UnsupportedButton(b) => {
+                write!(f, "the X server does not support the '{b:?}' button")
+            }
             Self::UnsupportedChar(c) => write!(f, "the X server does not support the {c:?} char"),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Authority(e) => Some(e),
+            _ => None,
+        }
+    }
+}
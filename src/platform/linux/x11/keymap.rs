@@ -0,0 +1,244 @@
+//! Describes which keys produce which keysyms, according to the keyboard mapping of the X
+//! server.
+
+use std::collections::{HashMap, VecDeque};
+use std::os::raw::c_uint;
+
+use x11::xlib;
+
+/// A level of a key, which produces a keysym.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyLevel {
+    /// The keycode of the key.
+    pub keycode: xlib::KeyCode,
+    /// The group (layout) in which the key produces the keysym, from `0` to `3`.
+    pub group: u8,
+    /// The number of groups of the key.
+    ///
+    /// When the active group exceeds it, the key wraps around to its first groups.
+    pub groups: u8,
+    /// The modifiers that must be held to select the level.
+    pub mods: c_uint,
+}
+
+impl KeyLevel {
+    /// Returns whether the level can be reached while `group` is active.
+    fn is_reachable(&self, group: u8) -> bool {
+        self.group == group % self.groups.max(1)
+    }
+}
+
+/// The keyboard group (layout) in use.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Group {
+    /// The group that the keys currently produce.
+    pub effective: u8,
+    /// The group locked by the user, which must be restored after switching to another group.
+    pub locked: u8,
+}
+
+/// Maps keysyms to the keys that produce them.
+pub struct Keymap {
+    /// The levels that produce each keysym, in order of preference.
+    keysyms: HashMap<xlib::KeySym, Vec<KeyLevel>>,
+    /// A key bound to each of the eight modifiers, if any.
+    modifiers: [Option<xlib::KeyCode>; 8],
+}
+
+impl Keymap {
+    /// Creates a new [`Keymap`] from the levels of all the keys of the keyboard, and the keys
+    /// bound to each modifier.
+    ///
+    /// The levels that require the **Lock** modifier are ignored, because pressing **Caps Lock**
+    /// would toggle it rather than hold it.
+    pub fn new(
+        levels: impl IntoIterator<Item = (xlib::KeySym, KeyLevel)>,
+        modifiers: [Option<xlib::KeyCode>; 8],
+    ) -> Self {
+        let mut keysyms: HashMap<xlib::KeySym, Vec<KeyLevel>> = HashMap::new();
+        for (keysym, level) in levels {
+            if keysym != 0 && level.mods & xlib::LockMask == 0 {
+                keysyms.entry(keysym).or_default().push(level);
+            }
+        }
+
+        // Prefer the levels that need fewer modifiers, then the lower groups.
+        for levels in keysyms.values_mut() {
+            levels.sort_by_key(|l| (l.mods.count_ones(), l.group, l.keycode));
+        }
+
+        Self { keysyms, modifiers }
+    }
+
+    /// Returns a level that produces `keysym`, preferring the ones that can be reached without
+    /// leaving `group`.
+    pub fn lookup(&self, keysym: xlib::KeySym, group: u8) -> Option<KeyLevel> {
+        let levels = self.keysyms.get(&keysym)?;
+        levels
+            .iter()
+            .find(|level| level.is_reachable(group))
+            .or_else(|| levels.first())
+            .copied()
+    }
+
+    /// Returns the keys that must be held to activate the modifiers `mods`.
+    ///
+    /// Returns `None` if one of the modifiers is not bound to any key.
+    pub fn modifier_keys(&self, mods: c_uint) -> Option<Vec<xlib::KeyCode>> {
+        (0..8)
+            .filter(|&index| mods & (1 << index) != 0)
+            .map(|index| self.modifiers[index])
+            .collect()
+    }
+}
+
+/// The greatest number of keycodes that may be bound to characters missing from the keyboard
+/// mapping at the same time.
+const MAX_SCRATCH_KEYS: usize = 8;
+
+/// A spare keycode, and the keysym it is bound to.
+struct ScratchKey {
+    keycode: xlib::KeyCode,
+    /// The keysym bound to the keycode, or `0` if it is not bound yet.
+    keysym: xlib::KeySym,
+    /// Whether the keycode has been used since the last call to [`ScratchKeys::unpin`].
+    pinned: bool,
+}
+
+/// Spare keycodes, bound to the keysyms that no key produces.
+///
+/// When more keysyms are needed than there are spare keycodes, the keycode that was used the
+/// least recently is bound again, unless it is pinned. Keycodes are pinned when they are
+/// acquired, because clients may still translate the events that used them.
+pub struct ScratchKeys {
+    /// The spare keycodes, from the least recently used to the most recently used.
+    keys: VecDeque<ScratchKey>,
+}
+
+impl ScratchKeys {
+    /// Creates a new [`ScratchKeys`] from keycodes that are not bound to any keysym.
+    pub fn new(unused: Vec<xlib::KeyCode>) -> Self {
+        // The last keycodes are the least likely to be used by a physical key.
+        let keys = unused
+            .into_iter()
+            .rev()
+            .take(MAX_SCRATCH_KEYS)
+            .map(|keycode| ScratchKey {
+                keycode,
+                keysym: 0,
+                pinned: false,
+            })
+            .collect();
+
+        Self { keys }
+    }
+
+    /// Returns a keycode bound to `keysym`, and pins it.
+    ///
+    /// The `bool` indicates whether the keycode must be bound to `keysym` first. Returns `None`
+    /// if all the spare keycodes are pinned to other keysyms.
+    pub fn acquire(&mut self, keysym: xlib::KeySym) -> Option<(xlib::KeyCode, bool)> {
+        let (index, rebind) = match self.keys.iter().position(|k| k.keysym == keysym) {
+            Some(index) => (index, false),
+            None => (self.keys.iter().position(|k| !k.pinned)?, true),
+        };
+
+        let key = self.keys.remove(index)?;
+        self.keys.push_back(ScratchKey {
+            keysym,
+            pinned: true,
+            ..key
+        });
+        Some((key.keycode, rebind))
+    }
+
+    /// Unpins all the keycodes, allowing them to be bound to other keysyms.
+    pub fn unpin(&mut self) {
+        for key in &mut self.keys {
+            key.pinned = false;
+        }
+    }
+
+    /// Returns the keycodes that are currently bound to a keysym.
+    pub fn bound(&self) -> impl Iterator<Item = xlib::KeyCode> + '_ {
+        self.keys
+            .iter()
+            .filter(|k| k.keysym != 0)
+            .map(|k| k.keycode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scratch_keys_are_reused() {
+        let mut scratch = ScratchKeys::new(vec![200, 201]);
+        assert_eq!(scratch.acquire(0x100_20ac), Some((201, true)));
+        assert_eq!(scratch.acquire(0x100_20ac), Some((201, false)));
+        assert_eq!(scratch.bound().collect::<Vec<_>>(), [201]);
+    }
+
+    #[test]
+    fn pinned_scratch_keys_are_not_evicted() {
+        let mut scratch = ScratchKeys::new(vec![200, 201]);
+        assert_eq!(scratch.acquire(1), Some((201, true)));
+        assert_eq!(scratch.acquire(2), Some((200, true)));
+        assert_eq!(scratch.acquire(3), None);
+
+        // Keys bound to the needed keysym remain usable.
+        assert_eq!(scratch.acquire(1), Some((201, false)));
+    }
+
+    #[test]
+    fn oldest_scratch_key_is_rebound_once_all_are_unpinned() {
+        let mut scratch = ScratchKeys::new(vec![200, 201, 202]);
+        assert_eq!(scratch.acquire(1), Some((202, true)));
+        assert_eq!(scratch.acquire(2), Some((201, true)));
+        assert_eq!(scratch.acquire(3), Some((200, true)));
+        assert_eq!(scratch.acquire(2), Some((201, false)));
+        assert_eq!(scratch.acquire(4), None);
+
+        scratch.unpin();
+        assert_eq!(scratch.acquire(4), Some((202, true)));
+        assert_eq!(scratch.acquire(5), Some((200, true)));
+        assert_eq!(scratch.acquire(2), Some((201, false)));
+    }
+
+    #[test]
+    fn unpinned_scratch_keys_are_evicted_in_lru_order() {
+        let mut scratch = ScratchKeys::new(vec![200, 201]);
+        scratch.acquire(1);
+        scratch.acquire(2);
+        scratch.acquire(1);
+        scratch.unpin();
+
+        assert_eq!(scratch.acquire(3), Some((200, true)));
+        assert_eq!(scratch.acquire(4), Some((201, true)));
+        assert_eq!(scratch.acquire(5), None);
+    }
+
+    #[test]
+    fn scratch_keys_are_limited() {
+        let mut scratch = ScratchKeys::new((100..200).collect());
+        for keysym in 1..=MAX_SCRATCH_KEYS as xlib::KeySym {
+            assert!(scratch.acquire(keysym).is_some());
+        }
+        assert_eq!(scratch.acquire(0xffff), None);
+        assert!(scratch
+            .bound()
+            .all(|keycode| keycode >= 200 - MAX_SCRATCH_KEYS as u8));
+    }
+
+    #[test]
+    fn unbound_modifiers_are_reported() {
+        let mut modifiers = [None; 8];
+        modifiers[0] = Some(50);
+        let keymap = Keymap::new([], modifiers);
+
+        assert_eq!(keymap.modifier_keys(0), Some(vec![]));
+        assert_eq!(keymap.modifier_keys(xlib::ShiftMask), Some(vec![50]));
+        assert_eq!(keymap.modifier_keys(xlib::ShiftMask | xlib::Mod5Mask), None);
+    }
+}
@@ -6,5 +6,6 @@ pub use self::simulator::*;
 mod error;
 pub use self::error::*;
 
+mod keymap;
 mod sys;
 mod utils;
@@ -1,8 +1,15 @@
-use x11::{keysym, xlib};
+use std::os::raw::c_uint;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
 
-use super::sys::OpenDisplay;
+use x11::xlib;
+
+use super::keymap::{Group, KeyLevel, Keymap, ScratchKeys};
+use super::sys::{OpenDisplay, ScrollValuator, ScrollValuators};
 use super::utils;
-use crate::{Button, Key};
+use crate::motion::SubpixelAccumulator;
+use crate::platform::{xauth, xsettings};
+use crate::{Button, Capabilities, Fidelity, Key, Screen, UnicodeSupport, X11Authority};
 
 /// The simulator used when using the X11 window manager.
 pub struct Simulator {
@@ -14,26 +21,141 @@ pub struct Simulator {
     /// The XTEST extension is prefered because events that are sent through it cannot be
     /// distinguished from regular user events.
     supports_xtest: bool,
+
+    /// The fractional part of the relative motions that have not been sent yet.
+    motion: SubpixelAccumulator,
+
+    /// The scroll valuators of the pointer device driven by the "XTEST" extension.
+    ///
+    /// When this is `None`, smooth scrolling is emulated with wheel clicks.
+    scroll_valuators: Option<ScrollValuators>,
+    /// The fractional part of the smooth scrolls that have not been sent yet.
+    scroll: SubpixelAccumulator,
+
+    /// The keyboard mapping of the X server, used to type characters.
+    ///
+    /// This is `None` until the first character is typed, and after the mapping changes.
+    keymap: Mutex<Option<Keymap>>,
+    /// The pointer mapping of the X server, used to send extra buttons.
+    ///
+    /// This is `None` until the first button is sent, and after the mapping changes.
+    pointer_mapping: Mutex<Option<Vec<u8>>>,
 }
 
 impl Simulator {
     /// Creates a new [`Simulator`] instance.
-    pub fn new() -> Result<Self, super::Error> {
-        let display = OpenDisplay::open()?;
+    ///
+    /// When `display` or `authority` is `None`, the `DISPLAY` and `XAUTHORITY` environment
+    /// variables are used instead.
+    pub fn new(
+        display: Option<&str>,
+        authority: Option<&X11Authority>,
+    ) -> Result<Self, super::Error> {
+        let auth = authority
+            .map(|authority| xauth::resolve(authority, display))
+            .transpose()
+            .map_err(super::Error::Authority)?;
+        let display = OpenDisplay::open(display, auth)?;
         let supports_xtest = display.xtest_query_extension();
+        let scroll_valuators = supports_xtest
+            .then(|| display.xtest_scroll_valuators())
+            .flatten();
 
         Ok(Self {
             display,
             supports_xtest,
+            motion: SubpixelAccumulator::default(),
+            scroll_valuators,
+            scroll: SubpixelAccumulator::default(),
+            keymap: Mutex::default(),
+            pointer_mapping: Mutex::default(),
         })
     }
 
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        let fidelity = match self.supports_xtest {
+            true => Fidelity::Native,
+            false => Fidelity::Synthetic,
+        };
+
+        // Characters missing from the keyboard mapping are typed by binding them to spare
+        // keycodes.
+        let unicode = match self.display.unused_keycodes().is_empty() {
+            true => UnicodeSupport::Layout,
+            false => UnicodeSupport::Full,
+        };
+
+        let max_extra_buttons = self.max_extra_buttons();
+        let mut ret = Capabilities::new("x11", fidelity, max_extra_buttons, unicode);
+        ret.inputs.smooth_scroll = self.scroll_valuators.is_some();
+        ret
+    }
+
+    /// Returns the number of extra buttons that can be sent, starting from
+    /// [`Button::Extra(0)`](Button::Extra).
+    fn max_extra_buttons(&self) -> u8 {
+        let max = (utils::MAX_BUTTON - utils::FIRST_EXTRA_BUTTON + 1) as u8;
+        if !self.supports_xtest {
+            return max;
+        }
+
+        let map = self.pointer_mapping();
+        if map.is_empty() {
+            return max;
+        }
+
+        (0..max)
+            .take_while(|&n| map.contains(&(utils::FIRST_EXTRA_BUTTON as u8 + n)))
+            .count() as u8
+    }
+
+    /// Returns the pointer mapping of the X server.
+    ///
+    /// The mapping is read from the X server the first time it is needed, and again whenever it
+    /// changes.
+    fn pointer_mapping(&self) -> Vec<u8> {
+        let mut map = self
+            .pointer_mapping
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if self.display.take_pointer_mapping_notify() {
+            *map = None;
+        }
+
+        map.get_or_insert_with(|| self.display.get_pointer_mapping())
+            .clone()
+    }
+
+    /// Returns the button number that must be sent to produce `button`.
+    ///
+    /// The "XTEST" extension simulates physical buttons, which the X server translates into
+    /// logical buttons according to its pointer mapping. That mapping is not applied to the
+    /// events sent with `XSendEvent`.
+    fn button_to_x11(&self, button: Button) -> Result<c_uint, super::Error> {
+        let logical =
+            utils::button_to_x11(button).ok_or(super::Error::UnsupportedButton(button))?;
+        if !self.supports_xtest {
+            return Ok(logical);
+        }
+
+        utils::physical_button(&self.pointer_mapping(), logical)
+            .ok_or(super::Error::UnsupportedButton(button))
+    }
+
+    /// Returns the keycode of `key`.
+    fn key_to_keycode(&self, key: Key) -> Result<xlib::KeyCode, super::Error> {
+        let keycode = match key {
+            Key::Scancode(code) => utils::scancode_to_x11(code),
+            _ => utils::key_to_x11(key).and_then(|keysym| self.display.keysym_to_keycode(keysym)),
+        };
+
+        keycode.ok_or(super::Error::UnsupportedKey(key))
+    }
+
     /// Sends a fake key press event to the top-level window.
     pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
-        let keycode = self
-            .display
-            .keysym_to_keycode(utils::key_to_x11(key))
-            .ok_or(super::Error::UnsupportedKey(key))?;
+        let keycode = self.key_to_keycode(key)?;
 
         if self.supports_xtest {
             self.display.xtest_fake_key_event(keycode as _, true, 0)?;
@@ -47,10 +169,7 @@ impl Simulator {
 
     /// Sends a fake key release event to the top-level window.
     pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
-        let keycode = self
-            .display
-            .keysym_to_keycode(utils::key_to_x11(key))
-            .ok_or(super::Error::UnsupportedKey(key))?;
+        let keycode = self.key_to_keycode(key)?;
 
         if self.supports_xtest {
             self.display.xtest_fake_key_event(keycode as _, false, 0)?;
@@ -65,10 +184,7 @@ impl Simulator {
 
     /// Sends a fake keystroke event to the top-level window.
     pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
-        let keycode = self
-            .display
-            .keysym_to_keycode(utils::key_to_x11(key))
-            .ok_or(super::Error::UnsupportedKey(key))?;
+        let keycode = self.key_to_keycode(key)?;
 
         if self.supports_xtest {
             self.display.xtest_fake_key_event(keycode as _, true, 0)?;
@@ -85,7 +201,7 @@ impl Simulator {
 
     /// Sends a fake button press to the top-level window.
     pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
-        let button = utils::button_to_x11(button);
+        let button = self.button_to_x11(button)?;
 
         if self.supports_xtest {
             self.display.xtest_fake_button_event(button, true, 0)?;
@@ -99,7 +215,7 @@ impl Simulator {
 
     /// Sends a fake button release to the top-level window.
     pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
-        let button = utils::button_to_x11(button);
+        let button = self.button_to_x11(button)?;
 
         if self.supports_xtest {
             self.display.xtest_fake_button_event(button, false, 0)?;
@@ -113,7 +229,7 @@ impl Simulator {
 
     /// Sends a fake button click to the top-level window.
     pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
-        let button = utils::button_to_x11(button);
+        let button = self.button_to_x11(button)?;
 
         if self.supports_xtest {
             self.display.xtest_fake_button_event(button, true, 0)?;
@@ -127,74 +243,349 @@ impl Simulator {
         Ok(())
     }
 
-    /// Sends a unicode code-point using the "XTEST" extension.
-    fn _send_char_xtest(&self, c: char) -> Result<(), super::Error> {
-        let (keysym, shift) = utils::char_to_x11(c).ok_or(super::Error::UnsupportedChar(c))?;
+    /// Scrolls by (`dx`, `dy`) wheel clicks by clicking the buttons `4` to `7`.
+    ///
+    /// Like other buttons, the wheel buttons go through the pointer mapping when they are sent
+    /// with "XTEST". The clicks of a wheel button that the mapping disables are dropped.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        let (window, map) = match self.supports_xtest {
+            true => (None, self.pointer_mapping()),
+            false => (Some(self.display.get_input_focus()?), Vec::new()),
+        };
 
-        let keycode = self
-            .display
-            .keysym_to_keycode(keysym)
-            .ok_or(super::Error::UnsupportedChar(c))?;
-        let mut shift_keycode = 0;
+        for (button, clicks) in utils::scroll_to_x11(dx, dy) {
+            let Some(button) = utils::physical_button(&map, button) else {
+                continue;
+            };
 
-        if shift {
-            shift_keycode = self
-                .display
-                .keysym_to_keycode(keysym::XK_Shift_L as _)
-                .ok_or(super::Error::UnsupportedChar(c))?;
-            self.display
-                .xtest_fake_key_event(shift_keycode as _, true, 0)?;
+            for _ in 0..clicks {
+                match window {
+                    None => {
+                        self.display.xtest_fake_button_event(button, true, 0)?;
+                        self.display.xtest_fake_button_event(button, false, 0)?;
+                    }
+                    Some(window) => {
+                        self.display.send_button_event(window, button, true)?;
+                        self.display.send_button_event(window, button, false)?;
+                    }
+                }
+            }
         }
+        self.display.flush()?;
+        Ok(())
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks, which may be fractional.
+    ///
+    /// The scroll valuators of the "XTEST" pointer device are used when it has any. Otherwise,
+    /// the scroll is rounded to whole wheel clicks.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let horizontal = self.scroll_valuators.and_then(|v| v.horizontal);
+        let vertical = self.scroll_valuators.and_then(|v| v.vertical);
 
-        self.display.xtest_fake_key_event(keycode as _, true, 0)?;
-        self.display.xtest_fake_key_event(keycode as _, false, 0)?;
+        // The deltas are accumulated in valuator units for the axes that have a valuator, and in
+        // wheel clicks for the others.
+        let scale = |valuator: Option<ScrollValuator>| valuator.map_or(1.0, |v| v.increment);
+        let (dx, dy) = self
+            .scroll
+            .take(dx * scale(horizontal), dy * scale(vertical));
 
-        if shift {
-            self.display
-                .xtest_fake_key_event(shift_keycode as _, false, 0)?;
+        let mut clicks = (dx, dy);
+        if let Some(valuators) = self.scroll_valuators {
+            for (delta, valuator, clicks) in [
+                (dx, valuators.horizontal, &mut clicks.0),
+                (dy, valuators.vertical, &mut clicks.1),
+            ] {
+                let Some(valuator) = valuator else {
+                    continue;
+                };
+
+                *clicks = 0;
+                if delta != 0 {
+                    self.display.xtest_fake_device_motion_event(
+                        valuators.device,
+                        valuator.number,
+                        delta,
+                        0,
+                    )?;
+                }
+            }
         }
 
+        self.scroll(clicks.0, clicks.1)
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`) of the default screen.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        let screen = self.display.default_screen();
+
+        if self.supports_xtest {
+            self.display.xtest_fake_motion_event(screen, x, y, 0)?;
+        } else {
+            let root = self.display.root_window(screen);
+            self.display.warp_pointer(root, x, y);
+        }
+        self.display.flush()?;
         Ok(())
     }
 
-    /// Sends a unicode code-point.
-    fn _send_char(&self, window: xlib::Window, c: char) -> Result<(), super::Error> {
-        let (keysym, shift) = utils::char_to_x11(c).ok_or(super::Error::UnsupportedChar(c))?;
+    /// Returns the position of the mouse pointer on the default screen.
+    ///
+    /// Returns `None` if the pointer is on another screen.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        let root = self.display.root_window(self.display.default_screen());
+        Ok(self.display.query_pointer(root))
+    }
+
+    /// Returns the monitors attached to the default screen.
+    ///
+    /// When the X server does not support version 1.5 of the "RANDR" extension, the whole
+    /// screen is returned as a single monitor.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        let screen = self.display.default_screen();
+        let root = self.display.root_window(screen);
+
+        let rects = self.display.xrandr_monitors(root).unwrap_or_else(|| {
+            let (width, height) = self.display.screen_size(screen);
+            vec![(0, 0, width, height)]
+        });
 
-        let keycode = self
+        Ok(rects
+            .into_iter()
+            .filter(|&(_, _, width, height)| width > 0 && height > 0)
+            .map(|(x, y, width, height)| Screen::new(x, y, width as u32, height as u32))
+            .collect())
+    }
+
+    /// Returns the double-click time advertised by the XSETTINGS manager of the default screen,
+    /// if any.
+    pub fn double_click_time(&self) -> Option<Duration> {
+        let screen = self.display.default_screen();
+        let selection = self
             .display
-            .keysym_to_keycode(keysym)
+            .intern_atom_if_exists(&xsettings::selection_name(screen))?;
+        let property = self
+            .display
+            .intern_atom_if_exists(xsettings::SETTINGS_PROPERTY)?;
+
+        let owner = self.display.get_selection_owner(selection)?;
+        let data = self
+            .display
+            .get_window_property(owner, property, property)?;
+        xsettings::double_click_time(&data)
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) relative to its current position.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let (dx, dy) = self.motion.take(dx, dy);
+        if dx == 0 && dy == 0 {
+            return Ok(());
+        }
+
+        if self.supports_xtest {
+            let screen = self.display.default_screen();
+            self.display
+                .xtest_fake_relative_motion_event(screen, dx, dy, 0)?;
+        } else {
+            self.display.warp_pointer_by(dx, dy);
+        }
+        self.display.flush()?;
+        Ok(())
+    }
+
+    /// Returns the level of a key that produces `c`, along with the modifier keys that must be
+    /// held to select it.
+    ///
+    /// The keyboard mapping is read from the X server the first time it is needed, and again
+    /// whenever it changes. Levels that can be reached without leaving `group` are preferred.
+    ///
+    /// When no key produces `c`, or when its modifiers are not bound to any key, a spare keycode
+    /// of `scratch` is bound to it instead.
+    fn char_to_key(
+        &self,
+        c: char,
+        group: u8,
+        scratch: &mut Option<ScratchKeys>,
+    ) -> Result<(KeyLevel, Vec<xlib::KeyCode>), super::Error> {
+        let mut keymap = self.keymap.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.display.take_mapping_notify() {
+            // The spare keycodes may not be spare anymore.
+            self.release_scratch_keys(scratch.take());
+            *keymap = None;
+        }
+
+        let keymap = keymap.get_or_insert_with(|| {
+            let levels = self
+                .display
+                .xkb_key_levels()
+                .unwrap_or_else(|| self.display.core_key_levels());
+            Keymap::new(levels, self.display.modifier_keys())
+        });
+
+        let keysyms = utils::char_to_keysyms(c);
+        let level = keysyms
+            .into_iter()
+            .flatten()
+            .find_map(|keysym| keymap.lookup(keysym, group));
+
+        if let Some(level) = level {
+            if let Some(modifiers) = keymap.modifier_keys(level.mods) {
+                return Ok((level, modifiers));
+            }
+        }
+
+        let keysym = keysyms
+            .into_iter()
+            .flatten()
+            .next()
             .ok_or(super::Error::UnsupportedChar(c))?;
-        let state = if shift { xlib::ShiftMask } else { 0 };
+        let scratch =
+            scratch.get_or_insert_with(|| ScratchKeys::new(self.display.unused_keycodes()));
+        let (keycode, rebind) = match scratch.acquire(keysym) {
+            Some(acquired) => acquired,
+            None => {
+                // All the spare keycodes have been used by the current characters. Once the X
+                // server has processed the events sent so far, the least recently used one can
+                // be bound again.
+                self.display.sync();
+                scratch.unpin();
+                scratch
+                    .acquire(keysym)
+                    .ok_or(super::Error::UnsupportedChar(c))?
+            }
+        };
+
+        if rebind {
+            self.display.change_keyboard_mapping(keycode, keysym);
+
+            // The mapping changes caused by the simulator do not invalidate the keymap, since
+            // the spare keycodes were not part of it.
+            self.display.sync();
+            self.display.take_mapping_notify();
+        }
+
+        // A key with a single group produces the same keysyms in all groups.
+        let level = KeyLevel {
+            keycode,
+            group: 0,
+            groups: 1,
+            mods: 0,
+        };
+        Ok((level, Vec::new()))
+    }
+
+    /// Unbinds the spare keycodes bound by [`char_to_key`](Self::char_to_key), restoring the
+    /// keyboard mapping.
+    ///
+    /// The X server is synchronized with first, so that the events sent with those keycodes are
+    /// processed before they are unbound.
+    fn release_scratch_keys(&self, scratch: Option<ScratchKeys>) {
+        let Some(scratch) = scratch else {
+            return;
+        };
+
+        self.display.sync();
+        for keycode in scratch.bound() {
+            self.display.change_keyboard_mapping(keycode, 0);
+        }
+
+        self.display.sync();
+        self.display.take_mapping_notify();
+    }
+
+    /// Sends a unicode code-point using the "XTEST" extension.
+    ///
+    /// When the character belongs to another group than the one in use, that group is locked
+    /// while the key is pressed.
+    fn _send_char_xtest(
+        &self,
+        c: char,
+        group: Group,
+        scratch: &mut Option<ScratchKeys>,
+    ) -> Result<(), super::Error> {
+        let (level, modifiers) = self.char_to_key(c, group.effective, scratch)?;
+        let switch_group = level.group != group.effective % level.groups.max(1);
+
+        if switch_group {
+            self.display.xkb_lock_group(level.group)?;
+        }
+
+        let mut pressed = 0;
+        let mut result = modifiers.iter().try_for_each(|&keycode| {
+            self.display.xtest_fake_key_event(keycode as _, true, 0)?;
+            pressed += 1;
+            Ok(())
+        });
+        if result.is_ok() {
+            result = self
+                .display
+                .xtest_fake_key_event(level.keycode as _, true, 0)
+                .and_then(|()| {
+                    self.display
+                        .xtest_fake_key_event(level.keycode as _, false, 0)
+                });
+        }
+
+        // Whatever happened, the modifiers and the group must not remain active for the rest of
+        // the session.
+        for &keycode in modifiers[..pressed].iter().rev() {
+            let released = self.display.xtest_fake_key_event(keycode as _, false, 0);
+            result = result.and(released);
+        }
+        if switch_group {
+            let restored = self.display.xkb_lock_group(group.locked);
+            result = result.and(restored);
+        }
+
+        result
+    }
+
+    /// Sends a unicode code-point.
+    ///
+    /// The group and the modifiers that select the level of the key are part of the state of the
+    /// events, and do not affect the rest of the system.
+    fn _send_char(
+        &self,
+        window: xlib::Window,
+        c: char,
+        group: Group,
+        scratch: &mut Option<ScratchKeys>,
+    ) -> Result<(), super::Error> {
+        let (level, _) = self.char_to_key(c, group.effective, scratch)?;
+
+        let state = level.mods | (level.group as c_uint) << 13;
         self.display
-            .send_key_event(window, keycode as _, state, true)?;
+            .send_key_event(window, level.keycode as _, state, true)?;
         self.display
-            .send_key_event(window, keycode as _, state, false)?;
+            .send_key_event(window, level.keycode as _, state, false)?;
 
         Ok(())
     }
 
     /// Sends a specific unicode code-point.
     pub fn send_char(&self, c: char) -> Result<(), super::Error> {
-        if self.supports_xtest {
-            self._send_char_xtest(c)?;
-        } else {
-            let window = self.display.get_input_focus()?;
-            self._send_char(window, c)?;
-        }
-
-        self.display.flush()?;
-        Ok(())
+        self.send_chars(std::iter::once(c))
     }
 
     /// Sends a collection of characters.
+    ///
+    /// The characters that no key produces are bound to spare keycodes, which are unbound once
+    /// all the characters have been sent. When more distinct missing characters are sent than there are
+    /// spare keycodes, the X server is synchronized with before the least recently used keycode
+    /// is bound again.
     pub fn send_chars(&self, mut it: impl Iterator<Item = char>) -> Result<(), super::Error> {
-        if self.supports_xtest {
-            it.try_for_each(|c| self._send_char_xtest(c))?;
+        let group = self.display.xkb_group().unwrap_or_default();
+        let mut scratch = None;
+
+        let result = if self.supports_xtest {
+            it.try_for_each(|c| self._send_char_xtest(c, group, &mut scratch))
         } else {
-            let window = self.display.get_input_focus()?;
-            it.try_for_each(move |c| self._send_char(window, c))?;
-        }
+            self.display.get_input_focus().and_then(|window| {
+                it.try_for_each(|c| self._send_char(window, c, group, &mut scratch))
+            })
+        };
+        self.release_scratch_keys(scratch);
+        result?;
 
         self.display.flush()?;
         Ok(())
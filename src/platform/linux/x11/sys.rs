@@ -1,12 +1,131 @@
 //! Wraps the calls to the X11 API into safe function calls associated to the [`OpenDisplay`] type.
 
-use std::os::raw::{c_uint, c_ulong};
+use std::cell::Cell;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_uchar, c_uint, c_ulong, c_ushort};
+use std::sync::Mutex;
 
-use x11::{xlib, xtest};
+use x11::{xinput, xinput2, xlib, xrandr, xtest};
+
+use super::keymap::{Group, KeyLevel};
+
+/// The name of the slave pointer device driven by the "XTEST" extension.
+const XTEST_POINTER_NAME: &CStr = c"Virtual core XTEST pointer";
+
+/// Designates the core keyboard in the requests of the "XKEYBOARD" extension.
+const XKB_USE_CORE_KBD: c_uint = 0x0100;
+/// Requests the key types of the client map with `XkbGetMap`.
+const XKB_KEY_TYPES_MASK: c_uint = 1 << 0;
+/// Requests the keysyms of the client map with `XkbGetMap`.
+const XKB_KEY_SYMS_MASK: c_uint = 1 << 1;
+
+/// The `XkbClientMapRec` structure of `X11/extensions/XKBstr.h`.
+///
+/// The `x11` crate declares this structure without its fields.
+#[repr(C)]
+struct XkbClientMapRec {
+    size_types: c_uchar,
+    num_types: c_uchar,
+    types: *mut XkbKeyTypeRec,
+    size_syms: c_ushort,
+    num_syms: c_ushort,
+    syms: *mut xlib::KeySym,
+    key_sym_map: *mut XkbSymMapRec,
+    modmap: *mut c_uchar,
+}
+
+/// The `XkbKeyTypeRec` structure of `X11/extensions/XKBstr.h`.
+#[repr(C)]
+struct XkbKeyTypeRec {
+    mods: xlib::XkbModsRec,
+    num_levels: c_uchar,
+    map_count: c_uchar,
+    map: *mut XkbKTMapEntryRec,
+    preserve: *mut xlib::XkbModsRec,
+    name: xlib::Atom,
+    level_names: *mut xlib::Atom,
+}
+
+/// The `XkbKTMapEntryRec` structure of `X11/extensions/XKBstr.h`.
+#[repr(C)]
+struct XkbKTMapEntryRec {
+    active: xlib::Bool,
+    level: c_uchar,
+    mods: xlib::XkbModsRec,
+}
+
+/// The `XkbSymMapRec` structure of `X11/extensions/XKBstr.h`.
+#[repr(C)]
+struct XkbSymMapRec {
+    kt_index: [c_uchar; 4],
+    group_info: c_uchar,
+    width: c_uchar,
+    offset: c_ushort,
+}
+
+/// A valuator of an input device that reports scrolling.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollValuator {
+    /// The index of the valuator within the device.
+    pub number: c_int,
+    /// The change of the valuator that is equivalent to one wheel click down or to the right.
+    pub increment: f64,
+}
+
+/// The scroll valuators of an input device.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollValuators {
+    /// The XInput identifier of the device.
+    pub device: c_int,
+    /// The valuator that reports horizontal scrolling, if any.
+    pub horizontal: Option<ScrollValuator>,
+    /// The valuator that reports vertical scrolling, if any.
+    pub vertical: Option<ScrollValuator>,
+}
+
+/// Creates a slice from a pointer and a length returned by Xlib.
+///
+/// Xlib leaves empty arrays as null pointers, which [`std::slice::from_raw_parts`] does not
+/// accept.
+///
+/// # Safety
+///
+/// Unless `ptr` is null or `len` is zero, `ptr` must point to `len` initialized values that
+/// remain valid for `'a`.
+unsafe fn slice_or_empty<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Returns whether `event` is a `MappingNotify` event about the pointer mapping if `*arg` is
+/// true, or about the keyboard mapping otherwise.
+///
+/// This is the predicate used with [`xlib::XCheckIfEvent`].
+unsafe extern "C" fn is_mapping_notify(
+    _: *mut xlib::Display,
+    event: *mut xlib::XEvent,
+    arg: *mut c_char,
+) -> xlib::Bool {
+    // Safety:
+    //  `event` is a valid event, whose `mapping` field is valid for `MappingNotify` events, and
+    //  `arg` points to a `bool`.
+    let event = &*event;
+    let pointer = *(arg as *const bool);
+    let matches = event.get_type() == xlib::MappingNotify
+        && (event.mapping.request == xlib::MappingPointer) == pointer;
+    matches as xlib::Bool
+}
 
 /// An open connection with the X server.
 pub struct OpenDisplay {
     raw: *mut x11::xlib::Display,
+    /// The input device opened by [`xtest_fake_device_motion_event`], along with its ID.
+    ///
+    /// [`xtest_fake_device_motion_event`]: Self::xtest_fake_device_motion_event
+    device: Cell<Option<(c_int, *mut xinput::XDevice)>>,
 }
 
 // TODO:
@@ -14,15 +133,56 @@ pub struct OpenDisplay {
 
 impl OpenDisplay {
     /// Opens a connection with the X11 server.
-    pub fn open() -> Result<Self, super::Error> {
-        // Calling `XOpenDisplay` with a null pointer is always safe.
-        let raw = unsafe { xlib::XOpenDisplay(std::ptr::null()) };
+    ///
+    /// `display` is the name of the display to connect to, and `auth` the authorization protocol
+    /// name and data to use. When they are `None`, `$DISPLAY` and `$XAUTHORITY` are used instead.
+    pub fn open(
+        display: Option<&str>,
+        auth: Option<(Vec<u8>, Vec<u8>)>,
+    ) -> Result<Self, super::Error> {
+        /// `XSetAuthorization` modifies a global state, which must not be touched by other
+        /// threads before the display is opened.
+        static AUTHORIZATION: Mutex<()> = Mutex::new(());
+
+        let display = display
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| super::Error::OpenDisplay)?;
+        let display_ptr = display.as_ref().map_or(std::ptr::null(), |d| d.as_ptr());
+
+        let raw = match auth {
+            Some((mut name, mut data)) => {
+                let _guard = AUTHORIZATION.lock().unwrap_or_else(|e| e.into_inner());
+
+                // Safety:
+                //  `XSetAuthorization` copies the provided buffers, which are valid for the
+                //  duration of the call. `display_ptr` is either null or a valid C string.
+                unsafe {
+                    xlib::XSetAuthorization(
+                        name.as_mut_ptr() as _,
+                        name.len() as c_int,
+                        data.as_mut_ptr() as _,
+                        data.len() as c_int,
+                    );
+                    let raw = xlib::XOpenDisplay(display_ptr);
+                    // Restore the default behavior of reading `$XAUTHORITY`.
+                    xlib::XSetAuthorization(std::ptr::null_mut(), 0, std::ptr::null_mut(), 0);
+                    raw
+                }
+            }
+            // Safety:
+            //  `display_ptr` is either null or a valid C string.
+            None => unsafe { xlib::XOpenDisplay(display_ptr) },
+        };
 
         if raw.is_null() {
             return Err(super::Error::OpenDisplay);
         }
 
-        Ok(Self { raw })
+        Ok(Self {
+            raw,
+            device: Cell::new(None),
+        })
     }
 
     /// Returns the raw pointer owned by this instance of `Display`.
@@ -55,6 +215,208 @@ impl OpenDisplay {
         }
     }
 
+    /// Returns the area covered by each monitor attached to the screen of `window`, relative to
+    /// its root window.
+    ///
+    /// Returns `None` if the X server does not support version 1.5 of the "RANDR" extension.
+    ///
+    /// This function wraps the [`xrandr::XRRGetMonitors`] function.
+    pub fn xrandr_monitors(
+        &self,
+        window: xlib::Window,
+    ) -> Option<Vec<(c_int, c_int, c_int, c_int)>> {
+        let mut event_base = 0;
+        let mut error_base = 0;
+        let mut major_version = 0;
+        let mut minor_version = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe {
+            if xrandr::XRRQueryExtension(self.raw, &mut event_base, &mut error_base) == xlib::False
+                || xrandr::XRRQueryVersion(self.raw, &mut major_version, &mut minor_version) == 0
+                || (major_version, minor_version) < (1, 5)
+            {
+                return None;
+            }
+        }
+
+        let mut count = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let monitors = unsafe { xrandr::XRRGetMonitors(self.raw, window, xlib::True, &mut count) };
+        if monitors.is_null() {
+            return None;
+        }
+
+        // Safety:
+        //  `XRRGetMonitors` returned an array of `count` monitors.
+        let ret = unsafe { std::slice::from_raw_parts(monitors, count.max(0) as usize) }
+            .iter()
+            .map(|m| (m.x, m.y, m.width, m.height))
+            .collect();
+
+        // Safety:
+        //  The monitors were allocated by `XRRGetMonitors` and are not used anymore.
+        unsafe { xrandr::XRRFreeMonitors(monitors) };
+
+        Some(ret)
+    }
+
+    /// Returns the scroll valuators of the pointer device driven by the "XTEST" extension.
+    ///
+    /// Returns `None` if the X server does not support version 2.1 of the "XInputExtension"
+    /// extension, or if the device does not have any scroll valuator.
+    pub fn xtest_scroll_valuators(&self) -> Option<ScrollValuators> {
+        let mut opcode = 0;
+        let mut event_base = 0;
+        let mut error_base = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let present = unsafe {
+            xlib::XQueryExtension(
+                self.raw,
+                c"XInputExtension".as_ptr(),
+                &mut opcode,
+                &mut event_base,
+                &mut error_base,
+            )
+        };
+        if present == xlib::False {
+            return None;
+        }
+
+        // Scroll classes are only reported to the clients that announce version 2.1.
+        let mut major = 2;
+        let mut minor = 1;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let status = unsafe { xinput2::XIQueryVersion(self.raw, &mut major, &mut minor) };
+        if status != xlib::Success as c_int || (major, minor) < (2, 1) {
+            return None;
+        }
+
+        let mut count = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let devices =
+            unsafe { xinput2::XIQueryDevice(self.raw, xinput2::XIAllDevices, &mut count) };
+        if devices.is_null() {
+            return None;
+        }
+
+        // Safety:
+        //  `XIQueryDevice` returned an array of `count` devices, which remains valid until it
+        //  is freed below.
+        let slice = unsafe { std::slice::from_raw_parts(devices, count as usize) };
+        let ret = slice
+            .iter()
+            .filter(|info| info._use == xinput2::XISlavePointer)
+            // Safety:
+            //  The name of a device is a valid C string.
+            .find(|info| unsafe { CStr::from_ptr(info.name) } == XTEST_POINTER_NAME)
+            .map(|info| {
+                let mut ret = ScrollValuators {
+                    device: info.deviceid,
+                    horizontal: None,
+                    vertical: None,
+                };
+
+                for i in 0..info.num_classes as usize {
+                    // Safety:
+                    //  `classes` is an array of `num_classes` valid pointers, and the class is
+                    //  known to be a scroll class when its type is `XIScrollClass`.
+                    let class = unsafe { *info.classes.add(i) };
+                    if unsafe { (*class)._type } != xinput2::XIScrollClass {
+                        continue;
+                    }
+                    let class = unsafe { &*(class as *const xinput2::XIScrollClassInfo) };
+                    if class.increment == 0.0 || !class.increment.is_finite() {
+                        continue;
+                    }
+
+                    let valuator = ScrollValuator {
+                        number: class.number,
+                        increment: class.increment,
+                    };
+                    match class.scroll_type {
+                        xinput2::XIScrollTypeHorizontal => ret.horizontal = Some(valuator),
+                        xinput2::XIScrollTypeVertical => ret.vertical = Some(valuator),
+                        _ => (),
+                    }
+                }
+
+                ret
+            })
+            .filter(|ret| ret.horizontal.is_some() || ret.vertical.is_some());
+
+        // Safety:
+        //  `devices` has been allocated by `XIQueryDevice` and is not used past this point.
+        unsafe { xinput2::XIFreeDeviceInfo(devices) };
+
+        ret
+    }
+
+    /// Changes the valuator `valuator` of the input device `device` by `delta`.
+    ///
+    /// This function wraps the [`xtest::XTestFakeDeviceMotionEvent`] function.
+    pub fn xtest_fake_device_motion_event(
+        &self,
+        device: c_int,
+        valuator: c_int,
+        delta: c_int,
+        delay: c_ulong,
+    ) -> Result<(), super::Error> {
+        let handle = match self.device.get() {
+            Some((id, handle)) if id == device => handle,
+            opened => {
+                // Safety:
+                //  The `raw` field of `Display` is known to be valid, by invariant.
+                let handle = unsafe { xinput::XOpenDevice(self.raw, device as _) };
+                if handle.is_null() {
+                    return Err(super::Error::Unexpected);
+                }
+
+                // The device is kept open until the display is closed, or another device is
+                // needed.
+                if let Some((_, previous)) = opened {
+                    // Safety:
+                    //  `previous` has been opened by a previous call, and is not used past this
+                    //  point.
+                    unsafe { xinput::XCloseDevice(self.raw, previous) };
+                }
+                self.device.set(Some((device, handle)));
+                handle
+            }
+        };
+
+        let mut axes = [delta];
+
+        // Safety:
+        //  `handle` is open, and `axes` contains exactly one valuator.
+        let status = unsafe {
+            xtest::XTestFakeDeviceMotionEvent(
+                self.raw,
+                handle,
+                xlib::True,
+                valuator,
+                axes.as_mut_ptr(),
+                1,
+                delay,
+            )
+        };
+
+        if status == xlib::False {
+            Err(super::Error::Unexpected)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Wraps the [`xlib::XGetInputFocus`] function.
     #[inline]
     pub fn get_input_focus(&self) -> Result<xlib::Window, super::Error> {
@@ -86,6 +448,440 @@ impl OpenDisplay {
         }
     }
 
+    /// Returns the level of every key of the core keyboard, along with the keysym it produces,
+    /// according to the "XKEYBOARD" extension.
+    ///
+    /// Returns `None` if the X server does not support the extension.
+    ///
+    /// This function wraps the [`xlib::XkbGetMap`] function.
+    pub fn xkb_key_levels(&self) -> Option<Vec<(xlib::KeySym, KeyLevel)>> {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let desc = unsafe {
+            xlib::XkbGetMap(
+                self.raw,
+                XKB_KEY_TYPES_MASK | XKB_KEY_SYMS_MASK,
+                XKB_USE_CORE_KBD,
+            )
+        };
+        if desc.is_null() {
+            return None;
+        }
+
+        let mut ret = Vec::new();
+
+        // Safety:
+        //  `XkbGetMap` returned a valid keyboard description whose client map holds the key
+        //  types and keysyms of the keyboard. The indices are checked against the sizes of the
+        //  arrays they refer to.
+        unsafe {
+            let map = (*desc).map as *const XkbClientMapRec;
+            if let Some(map) = map.as_ref() {
+                let types = slice_or_empty(map.types, map.num_types as usize);
+                let syms = slice_or_empty(map.syms, map.num_syms as usize);
+                let key_sym_maps =
+                    slice_or_empty(map.key_sym_map, (*desc).max_key_code as usize + 1);
+
+                for keycode in (*desc).min_key_code..=(*desc).max_key_code {
+                    let Some(sym_map) = key_sym_maps.get(keycode as usize) else {
+                        continue;
+                    };
+                    let groups = (sym_map.group_info & 0x0f).min(4);
+
+                    for group in 0..groups {
+                        let Some(key_type) = types.get(sym_map.kt_index[group as usize] as usize)
+                        else {
+                            continue;
+                        };
+                        // Types with a single level, such as `ONE_LEVEL`, have no map.
+                        let entries = slice_or_empty(key_type.map, key_type.map_count as usize);
+
+                        for level in 0..key_type.num_levels.min(sym_map.width) {
+                            let index = sym_map.offset as usize
+                                + group as usize * sym_map.width as usize
+                                + level as usize;
+                            let Some(&keysym) = syms.get(index) else {
+                                continue;
+                            };
+
+                            // The first level is selected when no entry matches.
+                            let mods = match level {
+                                0 => Some(0),
+                                _ => entries
+                                    .iter()
+                                    .filter(|e| e.active != xlib::False && e.level == level)
+                                    .map(|e| e.mods.mask as c_uint)
+                                    .min_by_key(|mask| mask.count_ones()),
+                            };
+                            let Some(mods) = mods else {
+                                continue;
+                            };
+
+                            ret.push((
+                                keysym,
+                                KeyLevel {
+                                    keycode,
+                                    group,
+                                    groups,
+                                    mods,
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+
+            xlib::XkbFreeKeyboard(desc, 0, xlib::True);
+        }
+
+        Some(ret)
+    }
+
+    /// Returns the first two levels of every key, along with the keysym they produce, according
+    /// to the core keyboard mapping.
+    ///
+    /// This function wraps the [`xlib::XGetKeyboardMapping`] function.
+    pub fn core_key_levels(&self) -> Vec<(xlib::KeySym, KeyLevel)> {
+        let mut min_keycode = 0;
+        let mut max_keycode = 0;
+        let mut keysyms_per_keycode = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let keysyms = unsafe {
+            xlib::XDisplayKeycodes(self.raw, &mut min_keycode, &mut max_keycode);
+            xlib::XGetKeyboardMapping(
+                self.raw,
+                min_keycode as _,
+                max_keycode - min_keycode + 1,
+                &mut keysyms_per_keycode,
+            )
+        };
+        if keysyms.is_null() {
+            return Vec::new();
+        }
+
+        let per_keycode = keysyms_per_keycode.max(1) as usize;
+        let len = (max_keycode - min_keycode + 1).max(0) as usize * per_keycode;
+
+        // Safety:
+        //  `XGetKeyboardMapping` returned `keysyms_per_keycode` keysyms for each keycode.
+        let ret = unsafe { std::slice::from_raw_parts(keysyms, len) }
+            .chunks(per_keycode)
+            .zip(min_keycode as xlib::KeyCode..)
+            .flat_map(|(keysyms, keycode)| {
+                // Only the first two levels can be reached with the shift key alone.
+                keysyms
+                    .iter()
+                    .take(2)
+                    .zip([0, xlib::ShiftMask])
+                    .map(move |(&keysym, mods)| {
+                        let level = KeyLevel {
+                            keycode,
+                            group: 0,
+                            groups: 1,
+                            mods,
+                        };
+                        (keysym, level)
+                    })
+            })
+            .collect();
+
+        // Safety:
+        //  `keysyms` was allocated by Xlib and is not used anymore.
+        unsafe { xlib::XFree(keysyms as *mut _) };
+
+        ret
+    }
+
+    /// Returns a key bound to each of the eight modifiers, if any.
+    ///
+    /// This function wraps the [`xlib::XGetModifierMapping`] function.
+    pub fn modifier_keys(&self) -> [Option<xlib::KeyCode>; 8] {
+        let mut ret = [None; 8];
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let map = unsafe { xlib::XGetModifierMapping(self.raw) };
+        if map.is_null() {
+            return ret;
+        }
+
+        // Safety:
+        //  `XGetModifierMapping` returned a valid map, which holds `max_keypermod` keycodes
+        //  for each of the eight modifiers.
+        unsafe {
+            let per_modifier = (*map).max_keypermod.max(0) as usize;
+            let keycodes = std::slice::from_raw_parts((*map).modifiermap, 8 * per_modifier);
+            for (keycodes, ret) in keycodes.chunks(per_modifier.max(1)).zip(&mut ret) {
+                *ret = keycodes.iter().copied().find(|&keycode| keycode != 0);
+            }
+
+            xlib::XFreeModifiermap(map);
+        }
+
+        ret
+    }
+
+    /// Returns the keyboard group in use.
+    ///
+    /// Returns `None` if the X server does not support the "XKEYBOARD" extension.
+    ///
+    /// This function wraps the [`xlib::XkbGetState`] function.
+    pub fn xkb_group(&self) -> Option<Group> {
+        // Safety:
+        //  `XkbStateRec` is made only of primitive types.
+        let mut state: xlib::XkbStateRec = unsafe { std::mem::zeroed() };
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let status = unsafe { xlib::XkbGetState(self.raw, XKB_USE_CORE_KBD, &mut state) };
+
+        (status == xlib::Success as c_int).then_some(Group {
+            effective: state.group,
+            locked: state.locked_group,
+        })
+    }
+
+    /// Locks the keyboard group `group`.
+    ///
+    /// This function wraps the [`xlib::XkbLockGroup`] function.
+    pub fn xkb_lock_group(&self, group: u8) -> Result<(), super::Error> {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let status = unsafe { xlib::XkbLockGroup(self.raw, XKB_USE_CORE_KBD, group as c_uint) };
+
+        if status == xlib::False {
+            Err(super::Error::Unexpected)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Removes the `MappingNotify` events about the keyboard and modifier mappings from the
+    /// event queue, and refreshes the keyboard mapping cached by Xlib accordingly.
+    ///
+    /// Returns whether any such event was received.
+    ///
+    /// This function wraps the [`xlib::XCheckIfEvent`] and [`xlib::XRefreshKeyboardMapping`]
+    /// functions.
+    pub fn take_mapping_notify(&self) -> bool {
+        self.check_mapping_notify(false)
+    }
+
+    /// Removes the `MappingNotify` events about the pointer mapping from the event queue.
+    ///
+    /// Returns whether any such event was received.
+    ///
+    /// This function wraps the [`xlib::XCheckIfEvent`] function.
+    pub fn take_pointer_mapping_notify(&self) -> bool {
+        self.check_mapping_notify(true)
+    }
+
+    /// Removes the `MappingNotify` events about the pointer mapping if `pointer` is true, or
+    /// about the keyboard mapping otherwise, from the event queue.
+    fn check_mapping_notify(&self, mut pointer: bool) -> bool {
+        let mut ret = false;
+
+        // Safety:
+        //  `XEvent` is made only of primitive types.
+        let mut event: xlib::XEvent = unsafe { std::mem::zeroed() };
+        let arg = &mut pointer as *mut bool as *mut c_char;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant, `arg` points to the
+        //  `bool` expected by the predicate, and `event` is a `MappingNotify` event whenever
+        //  `XCheckIfEvent` returns true.
+        unsafe {
+            while xlib::XCheckIfEvent(self.raw, &mut event, Some(is_mapping_notify), arg)
+                != xlib::False
+            {
+                xlib::XRefreshKeyboardMapping(&mut event.mapping);
+                ret = true;
+            }
+        }
+
+        ret
+    }
+
+    /// Returns the keycodes that are not bound to any keysym.
+    ///
+    /// This function wraps the [`xlib::XGetKeyboardMapping`] function.
+    pub fn unused_keycodes(&self) -> Vec<xlib::KeyCode> {
+        let mut min_keycode = 0;
+        let mut max_keycode = 0;
+        let mut keysyms_per_keycode = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let keysyms = unsafe {
+            xlib::XDisplayKeycodes(self.raw, &mut min_keycode, &mut max_keycode);
+            xlib::XGetKeyboardMapping(
+                self.raw,
+                min_keycode as _,
+                max_keycode - min_keycode + 1,
+                &mut keysyms_per_keycode,
+            )
+        };
+        if keysyms.is_null() {
+            return Vec::new();
+        }
+
+        let per_keycode = keysyms_per_keycode.max(1) as usize;
+        let len = (max_keycode - min_keycode + 1).max(0) as usize * per_keycode;
+
+        // Safety:
+        //  `XGetKeyboardMapping` returned `keysyms_per_keycode` keysyms for each keycode.
+        let ret = unsafe { std::slice::from_raw_parts(keysyms, len) }
+            .chunks(per_keycode)
+            .zip(min_keycode as xlib::KeyCode..)
+            .filter(|(keysyms, _)| keysyms.iter().all(|&keysym| keysym == 0))
+            .map(|(_, keycode)| keycode)
+            .collect();
+
+        // Safety:
+        //  `keysyms` was allocated by Xlib and is not used anymore.
+        unsafe { xlib::XFree(keysyms as *mut _) };
+
+        ret
+    }
+
+    /// Binds `keycode` to `keysym`, regardless of the modifiers, or unbinds it when `keysym` is
+    /// `0`.
+    ///
+    /// This function wraps the [`xlib::XChangeKeyboardMapping`] function.
+    pub fn change_keyboard_mapping(&self, keycode: xlib::KeyCode, keysym: xlib::KeySym) {
+        // `XChangeKeyboardMapping` does not report errors synchronously.
+
+        let mut keysyms = [keysym; 2];
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant, and `keysyms` holds
+        //  two keysyms for a single keycode.
+        unsafe {
+            xlib::XChangeKeyboardMapping(
+                self.raw,
+                keycode as c_int,
+                keysyms.len() as c_int,
+                keysyms.as_mut_ptr(),
+                1,
+            )
+        };
+    }
+
+    /// Waits until the X server has processed all the requests sent so far.
+    ///
+    /// This function wraps the [`xlib::XSync`] function.
+    #[inline]
+    pub fn sync(&self) {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe { xlib::XSync(self.raw, xlib::False) };
+    }
+
+    /// Wraps the [`xlib::XInternAtom`] function.
+    ///
+    /// Returns `None` if no atom named `name` exists yet.
+    pub fn intern_atom_if_exists(&self, name: &str) -> Option<xlib::Atom> {
+        let name = CString::new(name).ok()?;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let atom = unsafe { xlib::XInternAtom(self.raw, name.as_ptr(), xlib::True) };
+
+        if atom == 0 {
+            None
+        } else {
+            Some(atom)
+        }
+    }
+
+    /// Wraps the [`xlib::XGetSelectionOwner`] function.
+    ///
+    /// Returns `None` if the selection has no owner.
+    #[inline]
+    pub fn get_selection_owner(&self, selection: xlib::Atom) -> Option<xlib::Window> {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let window = unsafe { xlib::XGetSelectionOwner(self.raw, selection) };
+
+        if window == 0 {
+            None
+        } else {
+            Some(window)
+        }
+    }
+
+    /// Returns the content of the `property` of `window`, provided that its type is `type_` and
+    /// that it is made of bytes.
+    ///
+    /// This function wraps the [`xlib::XGetWindowProperty`] function.
+    pub fn get_window_property(
+        &self,
+        window: xlib::Window,
+        property: xlib::Atom,
+        type_: xlib::Atom,
+    ) -> Option<Vec<u8>> {
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut len = 0;
+        let mut bytes_after = 0;
+        let mut data = std::ptr::null_mut();
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let status = unsafe {
+            xlib::XGetWindowProperty(
+                self.raw,
+                window,
+                property,
+                0,
+                c_int::MAX as _,
+                xlib::False,
+                type_,
+                &mut actual_type,
+                &mut actual_format,
+                &mut len,
+                &mut bytes_after,
+                &mut data,
+            )
+        };
+
+        if status != xlib::Success as c_int || data.is_null() {
+            return None;
+        }
+
+        // Safety:
+        //  When the property has the requested type, `data` holds `len` items of
+        //  `actual_format` bits.
+        let ret = (actual_type == type_ && actual_format == 8)
+            .then(|| unsafe { std::slice::from_raw_parts(data, len as usize).to_vec() });
+
+        // Safety:
+        //  `data` was allocated by Xlib and is not used anymore.
+        unsafe { xlib::XFree(data as *mut _) };
+
+        ret
+    }
+
+    /// Returns the pointer mapping of the X server.
+    ///
+    /// The `n`th element of the returned list is the logical button produced by the physical
+    /// button `n + 1`, or `0` if that button is disabled.
+    ///
+    /// This function wraps the [`xlib::XGetPointerMapping`] function.
+    pub fn get_pointer_mapping(&self) -> Vec<u8> {
+        let mut map = [0u8; 256];
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant, and `map` can hold
+        //  the maximum number of buttons.
+        let len =
+            unsafe { xlib::XGetPointerMapping(self.raw, map.as_mut_ptr(), map.len() as c_int) };
+
+        map[..(len.clamp(0, map.len() as c_int) as usize)].to_vec()
+    }
+
     /// Wraps the [`xlib::XFlush`] function.
     #[inline]
     pub fn flush(&self) -> Result<(), super::Error> {
@@ -236,11 +1032,150 @@ impl OpenDisplay {
             Ok(())
         }
     }
+
+    /// Wraps the [`xlib::XDefaultScreen`] function.
+    ///
+    /// The default screen is the one selected by the name of the display (`:0.1` refers to the
+    /// screen `1`).
+    #[inline]
+    pub fn default_screen(&self) -> c_int {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe { xlib::XDefaultScreen(self.raw) }
+    }
+
+    /// Wraps the [`xlib::XRootWindow`] function.
+    #[inline]
+    pub fn root_window(&self, screen: c_int) -> xlib::Window {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe { xlib::XRootWindow(self.raw, screen) }
+    }
+
+    /// Returns the size of `screen`, in pixels.
+    ///
+    /// This function wraps the [`xlib::XDisplayWidth`] and [`xlib::XDisplayHeight`] functions.
+    #[inline]
+    pub fn screen_size(&self, screen: c_int) -> (c_int, c_int) {
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe {
+            (
+                xlib::XDisplayWidth(self.raw, screen),
+                xlib::XDisplayHeight(self.raw, screen),
+            )
+        }
+    }
+
+    /// Wraps the [`xtest::XTestFakeMotionEvent`] function.
+    #[inline]
+    pub fn xtest_fake_motion_event(
+        &self,
+        screen: c_int,
+        x: c_int,
+        y: c_int,
+        delay: c_ulong,
+    ) -> Result<(), super::Error> {
+        let status = unsafe { xtest::XTestFakeMotionEvent(self.raw, screen, x, y, delay) };
+
+        if status == xlib::False {
+            Err(super::Error::Unexpected)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wraps the [`xtest::XTestFakeRelativeMotionEvent`] function.
+    #[inline]
+    pub fn xtest_fake_relative_motion_event(
+        &self,
+        screen: c_int,
+        dx: c_int,
+        dy: c_int,
+        delay: c_ulong,
+    ) -> Result<(), super::Error> {
+        let status =
+            unsafe { xtest::XTestFakeRelativeMotionEvent(self.raw, screen, dx, dy, delay) };
+
+        if status == xlib::False {
+            Err(super::Error::Unexpected)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the position of the pointer relative to `window`.
+    ///
+    /// Returns `None` if the pointer is not on the same screen as `window`.
+    ///
+    /// This function wraps the [`xlib::XQueryPointer`] function.
+    pub fn query_pointer(&self, window: xlib::Window) -> Option<(c_int, c_int)> {
+        let mut root = 0;
+        let mut child = 0;
+        let mut root_x = 0;
+        let mut root_y = 0;
+        let mut win_x = 0;
+        let mut win_y = 0;
+        let mut mask = 0;
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        let same_screen = unsafe {
+            xlib::XQueryPointer(
+                self.raw,
+                window,
+                &mut root,
+                &mut child,
+                &mut root_x,
+                &mut root_y,
+                &mut win_x,
+                &mut win_y,
+                &mut mask,
+            )
+        };
+
+        if same_screen == xlib::False {
+            None
+        } else {
+            Some((win_x, win_y))
+        }
+    }
+
+    /// Moves the pointer to the position (`x`, `y`) relative to `window`.
+    ///
+    /// This function wraps the [`xlib::XWarpPointer`] function.
+    #[inline]
+    pub fn warp_pointer(&self, window: xlib::Window, x: c_int, y: c_int) {
+        // `XWarpPointer` does not report errors synchronously.
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe { xlib::XWarpPointer(self.raw, 0, window, 0, 0, 0, 0, x, y) };
+    }
+
+    /// Moves the pointer by (`dx`, `dy`) relative to its current position.
+    ///
+    /// This function wraps the [`xlib::XWarpPointer`] function.
+    #[inline]
+    pub fn warp_pointer_by(&self, dx: c_int, dy: c_int) {
+        // `XWarpPointer` does not report errors synchronously.
+
+        // Safety:
+        //  The `raw` field of `Display` is known to be valid, by invariant.
+        unsafe { xlib::XWarpPointer(self.raw, 0, 0, 0, 0, 0, 0, dx, dy) };
+    }
 }
 
 impl Drop for OpenDisplay {
     #[inline]
     fn drop(&mut self) {
+        if let Some((_, device)) = self.device.get() {
+            // Safety:
+            //  The `raw` field of `Display` is known to be valid, by invariant, and the device
+            //  has been opened on it.
+            unsafe { xinput::XCloseDevice(self.raw, device) };
+        }
+
         // Safety:
         //  The `raw` field of `Display` is known to be valid, by invariant.
         unsafe { xlib::XCloseDisplay(self.raw) };
@@ -7,233 +7,266 @@ use x11::{keysym, xlib};
 use crate::{Button, Key};
 
 /// Converts a [`Key`] into a [`xlib::KeySym`].
-pub fn key_to_x11(key: Key) -> xlib::KeySym {
+pub fn key_to_x11(key: Key) -> Option<xlib::KeySym> {
     match key {
-        Key::A => keysym::XK_A as xlib::KeySym,
-        Key::B => keysym::XK_B as xlib::KeySym,
-        Key::C => keysym::XK_C as xlib::KeySym,
-        Key::D => keysym::XK_D as xlib::KeySym,
-        Key::E => keysym::XK_E as xlib::KeySym,
-        Key::F => keysym::XK_F as xlib::KeySym,
-        Key::G => keysym::XK_G as xlib::KeySym,
-        Key::H => keysym::XK_H as xlib::KeySym,
-        Key::I => keysym::XK_I as xlib::KeySym,
-        Key::J => keysym::XK_J as xlib::KeySym,
-        Key::K => keysym::XK_K as xlib::KeySym,
-        Key::L => keysym::XK_L as xlib::KeySym,
-        Key::M => keysym::XK_M as xlib::KeySym,
-        Key::N => keysym::XK_N as xlib::KeySym,
-        Key::O => keysym::XK_O as xlib::KeySym,
-        Key::P => keysym::XK_P as xlib::KeySym,
-        Key::Q => keysym::XK_Q as xlib::KeySym,
-        Key::R => keysym::XK_R as xlib::KeySym,
-        Key::S => keysym::XK_S as xlib::KeySym,
-        Key::T => keysym::XK_T as xlib::KeySym,
-        Key::U => keysym::XK_U as xlib::KeySym,
-        Key::V => keysym::XK_V as xlib::KeySym,
-        Key::W => keysym::XK_W as xlib::KeySym,
-        Key::X => keysym::XK_X as xlib::KeySym,
-        Key::Y => keysym::XK_Y as xlib::KeySym,
-        Key::Z => keysym::XK_Z as xlib::KeySym,
-        Key::F1 => keysym::XK_F1 as xlib::KeySym,
-        Key::F2 => keysym::XK_F2 as xlib::KeySym,
-        Key::F3 => keysym::XK_F3 as xlib::KeySym,
-        Key::F4 => keysym::XK_F4 as xlib::KeySym,
-        Key::F5 => keysym::XK_F5 as xlib::KeySym,
-        Key::F6 => keysym::XK_F6 as xlib::KeySym,
-        Key::F7 => keysym::XK_F7 as xlib::KeySym,
-        Key::F8 => keysym::XK_F8 as xlib::KeySym,
-        Key::F9 => keysym::XK_F9 as xlib::KeySym,
-        Key::F10 => keysym::XK_F10 as xlib::KeySym,
-        Key::F11 => keysym::XK_F11 as xlib::KeySym,
-        Key::F12 => keysym::XK_F12 as xlib::KeySym,
-        Key::F13 => keysym::XK_F13 as xlib::KeySym,
-        Key::F14 => keysym::XK_F14 as xlib::KeySym,
-        Key::F15 => keysym::XK_F15 as xlib::KeySym,
-        Key::F16 => keysym::XK_F16 as xlib::KeySym,
-        Key::F17 => keysym::XK_F17 as xlib::KeySym,
-        Key::F18 => keysym::XK_F18 as xlib::KeySym,
-        Key::F19 => keysym::XK_F19 as xlib::KeySym,
-        Key::F20 => keysym::XK_F20 as xlib::KeySym,
-        Key::F21 => keysym::XK_F21 as xlib::KeySym,
-        Key::F22 => keysym::XK_F22 as xlib::KeySym,
-        Key::F23 => keysym::XK_F23 as xlib::KeySym,
-        Key::F24 => keysym::XK_F24 as xlib::KeySym,
-        Key::Zero => keysym::XK_0 as xlib::KeySym,
-        Key::One => keysym::XK_1 as xlib::KeySym,
-        Key::Two => keysym::XK_2 as xlib::KeySym,
-        Key::Three => keysym::XK_3 as xlib::KeySym,
-        Key::Four => keysym::XK_4 as xlib::KeySym,
-        Key::Five => keysym::XK_5 as xlib::KeySym,
-        Key::Six => keysym::XK_6 as xlib::KeySym,
-        Key::Seven => keysym::XK_7 as xlib::KeySym,
-        Key::Eight => keysym::XK_8 as xlib::KeySym,
-        Key::Nine => keysym::XK_9 as xlib::KeySym,
-        Key::Escape => keysym::XK_Escape as xlib::KeySym,
-        Key::Tab => keysym::XK_Tab as xlib::KeySym,
-        Key::CapsLock => keysym::XK_Caps_Lock as xlib::KeySym,
-        Key::LeftShift => keysym::XK_Shift_L as xlib::KeySym,
-        Key::LeftControl => keysym::XK_Control_L as xlib::KeySym,
-        Key::LeftAlt => keysym::XK_Alt_L as xlib::KeySym,
-        Key::LeftMeta => keysym::XK_Meta_L as xlib::KeySym,
-        Key::Space => keysym::XK_space as xlib::KeySym,
-        Key::RightMeta => keysym::XK_Meta_R as xlib::KeySym,
-        Key::RightAlt => keysym::XK_Alt_R as xlib::KeySym,
-        Key::RightControl => keysym::XK_Control_R as xlib::KeySym,
-        Key::RightShift => keysym::XK_Shift_R as xlib::KeySym,
-        Key::Enter => keysym::XK_Return as xlib::KeySym,
-        Key::Backspace => keysym::XK_BackSpace as xlib::KeySym,
-        Key::Insert => keysym::XK_Insert as xlib::KeySym,
-        Key::Delete => keysym::XK_Delete as xlib::KeySym,
-        Key::Home => keysym::XK_Home as xlib::KeySym,
-        Key::End => keysym::XK_End as xlib::KeySym,
-        Key::PageUp => keysym::XK_Page_Up as xlib::KeySym,
-        Key::PageDown => keysym::XK_Page_Down as xlib::KeySym,
-        Key::ArrowUp => keysym::XK_Up as xlib::KeySym,
-        Key::ArrowDown => keysym::XK_Down as xlib::KeySym,
-        Key::ArrowLeft => keysym::XK_Left as xlib::KeySym,
-        Key::ArrowRight => keysym::XK_Right as xlib::KeySym,
-        Key::NumLock => keysym::XK_Num_Lock as xlib::KeySym,
-        Key::NumpadEqual => keysym::XK_KP_Equal as xlib::KeySym,
-        Key::NumpadDivide => keysym::XK_KP_Divide as xlib::KeySym,
-        Key::NumpadMultiply => keysym::XK_KP_Multiply as xlib::KeySym,
-        Key::NumpadAdd => keysym::XK_KP_Add as xlib::KeySym,
-        Key::NumpadEnter => keysym::XK_KP_Enter as xlib::KeySym,
-        Key::NumpadDecimal => keysym::XK_KP_Decimal as xlib::KeySym,
-        Key::Numpad0 => keysym::XK_KP_0 as xlib::KeySym,
-        Key::Numpad1 => keysym::XK_KP_1 as xlib::KeySym,
-        Key::Numpad2 => keysym::XK_KP_2 as xlib::KeySym,
-        Key::Numpad3 => keysym::XK_KP_3 as xlib::KeySym,
-        Key::Numpad4 => keysym::XK_KP_4 as xlib::KeySym,
-        Key::Numpad5 => keysym::XK_KP_5 as xlib::KeySym,
-        Key::Numpad6 => keysym::XK_KP_6 as xlib::KeySym,
-        Key::Numpad7 => keysym::XK_KP_7 as xlib::KeySym,
-        Key::Numpad8 => keysym::XK_KP_8 as xlib::KeySym,
-        Key::Numpad9 => keysym::XK_KP_9 as xlib::KeySym,
-        Key::VolumeUp => keysym::XF86XK_AudioRaiseVolume as xlib::KeySym,
-        Key::VolumeDown => keysym::XF86XK_AudioLowerVolume as xlib::KeySym,
-        Key::VolumeMute => keysym::XF86XK_AudioMute as xlib::KeySym,
-        Key::MediaNext => keysym::XF86XK_AudioNext as xlib::KeySym,
-        Key::MediaPrevious => keysym::XF86XK_AudioPrev as xlib::KeySym,
-        Key::MediaStop => keysym::XF86XK_AudioStop as xlib::KeySym,
-        Key::MediaPause => keysym::XF86XK_AudioPause as xlib::KeySym,
+        Key::A => Some(keysym::XK_A as xlib::KeySym),
+        Key::B => Some(keysym::XK_B as xlib::KeySym),
+        Key::C => Some(keysym::XK_C as xlib::KeySym),
+        Key::D => Some(keysym::XK_D as xlib::KeySym),
+        Key::E => Some(keysym::XK_E as xlib::KeySym),
+        Key::F => Some(keysym::XK_F as xlib::KeySym),
+        Key::G => Some(keysym::XK_G as xlib::KeySym),
+        Key::H => Some(keysym::XK_H as xlib::KeySym),
+        Key::I => Some(keysym::XK_I as xlib::KeySym),
+        Key::J => Some(keysym::XK_J as xlib::KeySym),
+        Key::K => Some(keysym::XK_K as xlib::KeySym),
+        Key::L => Some(keysym::XK_L as xlib::KeySym),
+        Key::M => Some(keysym::XK_M as xlib::KeySym),
+        Key::N => Some(keysym::XK_N as xlib::KeySym),
+        Key::O => Some(keysym::XK_O as xlib::KeySym),
+        Key::P => Some(keysym::XK_P as xlib::KeySym),
+        Key::Q => Some(keysym::XK_Q as xlib::KeySym),
+        Key::R => Some(keysym::XK_R as xlib::KeySym),
+        Key::S => Some(keysym::XK_S as xlib::KeySym),
+        Key::T => Some(keysym::XK_T as xlib::KeySym),
+        Key::U => Some(keysym::XK_U as xlib::KeySym),
+        Key::V => Some(keysym::XK_V as xlib::KeySym),
+        Key::W => Some(keysym::XK_W as xlib::KeySym),
+        Key::X => Some(keysym::XK_X as xlib::KeySym),
+        Key::Y => Some(keysym::XK_Y as xlib::KeySym),
+        Key::Z => Some(keysym::XK_Z as xlib::KeySym),
+        Key::F1 => Some(keysym::XK_F1 as xlib::KeySym),
+        Key::F2 => Some(keysym::XK_F2 as xlib::KeySym),
+        Key::F3 => Some(keysym::XK_F3 as xlib::KeySym),
+        Key::F4 => Some(keysym::XK_F4 as xlib::KeySym),
+        Key::F5 => Some(keysym::XK_F5 as xlib::KeySym),
+        Key::F6 => Some(keysym::XK_F6 as xlib::KeySym),
+        Key::F7 => Some(keysym::XK_F7 as xlib::KeySym),
+        Key::F8 => Some(keysym::XK_F8 as xlib::KeySym),
+        Key::F9 => Some(keysym::XK_F9 as xlib::KeySym),
+        Key::F10 => Some(keysym::XK_F10 as xlib::KeySym),
+        Key::F11 => Some(keysym::XK_F11 as xlib::KeySym),
+        Key::F12 => Some(keysym::XK_F12 as xlib::KeySym),
+        Key::F13 => Some(keysym::XK_F13 as xlib::KeySym),
+        Key::F14 => Some(keysym::XK_F14 as xlib::KeySym),
+        Key::F15 => Some(keysym::XK_F15 as xlib::KeySym),
+        Key::F16 => Some(keysym::XK_F16 as xlib::KeySym),
+        Key::F17 => Some(keysym::XK_F17 as xlib::KeySym),
+        Key::F18 => Some(keysym::XK_F18 as xlib::KeySym),
+        Key::F19 => Some(keysym::XK_F19 as xlib::KeySym),
+        Key::F20 => Some(keysym::XK_F20 as xlib::KeySym),
+        Key::F21 => Some(keysym::XK_F21 as xlib::KeySym),
+        Key::F22 => Some(keysym::XK_F22 as xlib::KeySym),
+        Key::F23 => Some(keysym::XK_F23 as xlib::KeySym),
+        Key::F24 => Some(keysym::XK_F24 as xlib::KeySym),
+        Key::Zero => Some(keysym::XK_0 as xlib::KeySym),
+        Key::One => Some(keysym::XK_1 as xlib::KeySym),
+        Key::Two => Some(keysym::XK_2 as xlib::KeySym),
+        Key::Three => Some(keysym::XK_3 as xlib::KeySym),
+        Key::Four => Some(keysym::XK_4 as xlib::KeySym),
+        Key::Five => Some(keysym::XK_5 as xlib::KeySym),
+        Key::Six => Some(keysym::XK_6 as xlib::KeySym),
+        Key::Seven => Some(keysym::XK_7 as xlib::KeySym),
+        Key::Eight => Some(keysym::XK_8 as xlib::KeySym),
+        Key::Nine => Some(keysym::XK_9 as xlib::KeySym),
+        Key::Minus => Some(keysym::XK_minus as xlib::KeySym),
+        Key::Equal => Some(keysym::XK_equal as xlib::KeySym),
+        Key::BracketLeft => Some(keysym::XK_bracketleft as xlib::KeySym),
+        Key::BracketRight => Some(keysym::XK_bracketright as xlib::KeySym),
+        Key::Semicolon => Some(keysym::XK_semicolon as xlib::KeySym),
+        Key::Quote => Some(keysym::XK_apostrophe as xlib::KeySym),
+        Key::Comma => Some(keysym::XK_comma as xlib::KeySym),
+        Key::Period => Some(keysym::XK_period as xlib::KeySym),
+        Key::Slash => Some(keysym::XK_slash as xlib::KeySym),
+        Key::Backslash => Some(keysym::XK_backslash as xlib::KeySym),
+        Key::Grave => Some(keysym::XK_grave as xlib::KeySym),
+        Key::Escape => Some(keysym::XK_Escape as xlib::KeySym),
+        Key::Tab => Some(keysym::XK_Tab as xlib::KeySym),
+        Key::CapsLock => Some(keysym::XK_Caps_Lock as xlib::KeySym),
+        Key::LeftShift => Some(keysym::XK_Shift_L as xlib::KeySym),
+        Key::LeftControl => Some(keysym::XK_Control_L as xlib::KeySym),
+        Key::LeftAlt => Some(keysym::XK_Alt_L as xlib::KeySym),
+        Key::LeftMeta => Some(keysym::XK_Meta_L as xlib::KeySym),
+        Key::Space => Some(keysym::XK_space as xlib::KeySym),
+        Key::RightMeta => Some(keysym::XK_Meta_R as xlib::KeySym),
+        Key::RightAlt => Some(keysym::XK_Alt_R as xlib::KeySym),
+        Key::RightControl => Some(keysym::XK_Control_R as xlib::KeySym),
+        Key::RightShift => Some(keysym::XK_Shift_R as xlib::KeySym),
+        Key::Enter => Some(keysym::XK_Return as xlib::KeySym),
+        Key::Backspace => Some(keysym::XK_BackSpace as xlib::KeySym),
+        Key::PrintScreen => Some(keysym::XK_Print as xlib::KeySym),
+        Key::ScrollLock => Some(keysym::XK_Scroll_Lock as xlib::KeySym),
+        Key::Pause => Some(keysym::XK_Pause as xlib::KeySym),
+        Key::ContextMenu => Some(keysym::XK_Menu as xlib::KeySym),
+        Key::Insert => Some(keysym::XK_Insert as xlib::KeySym),
+        Key::Delete => Some(keysym::XK_Delete as xlib::KeySym),
+        Key::Home => Some(keysym::XK_Home as xlib::KeySym),
+        Key::End => Some(keysym::XK_End as xlib::KeySym),
+        Key::PageUp => Some(keysym::XK_Page_Up as xlib::KeySym),
+        Key::PageDown => Some(keysym::XK_Page_Down as xlib::KeySym),
+        Key::ArrowUp => Some(keysym::XK_Up as xlib::KeySym),
+        Key::ArrowDown => Some(keysym::XK_Down as xlib::KeySym),
+        Key::ArrowLeft => Some(keysym::XK_Left as xlib::KeySym),
+        Key::ArrowRight => Some(keysym::XK_Right as xlib::KeySym),
+        Key::NumLock => Some(keysym::XK_Num_Lock as xlib::KeySym),
+        Key::NumpadEqual => Some(keysym::XK_KP_Equal as xlib::KeySym),
+        Key::NumpadDivide => Some(keysym::XK_KP_Divide as xlib::KeySym),
+        Key::NumpadMultiply => Some(keysym::XK_KP_Multiply as xlib::KeySym),
+        Key::NumpadSubtract => Some(keysym::XK_KP_Subtract as xlib::KeySym),
+        Key::NumpadAdd => Some(keysym::XK_KP_Add as xlib::KeySym),
+        Key::NumpadEnter => Some(keysym::XK_KP_Enter as xlib::KeySym),
+        Key::NumpadDecimal => Some(keysym::XK_KP_Decimal as xlib::KeySym),
+        Key::Numpad0 => Some(keysym::XK_KP_0 as xlib::KeySym),
+        Key::Numpad1 => Some(keysym::XK_KP_1 as xlib::KeySym),
+        Key::Numpad2 => Some(keysym::XK_KP_2 as xlib::KeySym),
+        Key::Numpad3 => Some(keysym::XK_KP_3 as xlib::KeySym),
+        Key::Numpad4 => Some(keysym::XK_KP_4 as xlib::KeySym),
+        Key::Numpad5 => Some(keysym::XK_KP_5 as xlib::KeySym),
+        Key::Numpad6 => Some(keysym::XK_KP_6 as xlib::KeySym),
+        Key::Numpad7 => Some(keysym::XK_KP_7 as xlib::KeySym),
+        Key::Numpad8 => Some(keysym::XK_KP_8 as xlib::KeySym),
+        Key::Numpad9 => Some(keysym::XK_KP_9 as xlib::KeySym),
+        Key::VolumeUp => Some(keysym::XF86XK_AudioRaiseVolume as xlib::KeySym),
+        Key::VolumeDown => Some(keysym::XF86XK_AudioLowerVolume as xlib::KeySym),
+        Key::VolumeMute => Some(keysym::XF86XK_AudioMute as xlib::KeySym),
+        Key::MediaNext => Some(keysym::XF86XK_AudioNext as xlib::KeySym),
+        Key::MediaPrevious => Some(keysym::XF86XK_AudioPrev as xlib::KeySym),
+        Key::MediaStop => Some(keysym::XF86XK_AudioStop as xlib::KeySym),
+        Key::MediaPause => Some(keysym::XF86XK_AudioPause as xlib::KeySym),
+        Key::MediaPlay => Some(keysym::XF86XK_AudioPlay as xlib::KeySym),
+        Key::BrowserBack => Some(keysym::XF86XK_Back as xlib::KeySym),
+        Key::BrowserForward => Some(keysym::XF86XK_Forward as xlib::KeySym),
+        Key::BrowserRefresh => Some(keysym::XF86XK_Reload as xlib::KeySym),
+        Key::BrightnessUp => Some(keysym::XF86XK_MonBrightnessUp as xlib::KeySym),
+        Key::BrightnessDown => Some(keysym::XF86XK_MonBrightnessDown as xlib::KeySym),
+        Key::LaunchMail => Some(keysym::XF86XK_Mail as xlib::KeySym),
+        Key::LaunchCalculator => Some(keysym::XF86XK_Calculator as xlib::KeySym),
+        Key::Keysym(keysym) => Some(keysym as xlib::KeySym),
+        Key::Scancode(_) => None,
     }
 }
 
-/// Converts a [`Button`] instance into a [`c_uint`] understood by X11.
-pub fn button_to_x11(button: Button) -> c_uint {
+/// Converts an evdev key code into an X11 keycode.
+///
+/// The X servers that use the `evdev` or `libinput` drivers offset the evdev key codes by `8`.
+pub fn scancode_to_x11(code: u32) -> Option<xlib::KeyCode> {
+    code.checked_add(8)?.try_into().ok()
+}
+
+/// The highest button number that can be described by the X11 protocol.
+pub const MAX_BUTTON: c_uint = 255;
+
+/// The button number of [`Button::Extra(0)`](Button::Extra). The buttons `4` to `7` are used
+/// by the mouse wheel.
+pub const FIRST_EXTRA_BUTTON: c_uint = 8;
+
+/// Converts a [`Button`] instance into a logical button number understood by X11.
+///
+/// Extra buttons are mapped onto the buttons `8` and above. Returns `None` if the button number
+/// exceeds [`MAX_BUTTON`].
+pub fn button_to_x11(button: Button) -> Option<c_uint> {
     match button {
-        Button::Left => 1,
-        Button::Middle => 2,
-        Button::Right => 3,
-        Button::Extra(0) => 8,
-        Button::Extra(1) => 9,
-        Button::Extra(_) => 0,
+        Button::Left => Some(1),
+        Button::Middle => Some(2),
+        Button::Right => Some(3),
+        Button::Extra(n) => Some(FIRST_EXTRA_BUTTON + n as c_uint).filter(|&b| b <= MAX_BUTTON),
+    }
+}
+
+/// Returns the X11 buttons that scroll by (`dx`, `dy`) wheel clicks, along with the number of
+/// times they must be clicked.
+///
+/// Buttons `4` and `5` scroll up and down, buttons `6` and `7` scroll left and right.
+pub fn scroll_to_x11(dx: i32, dy: i32) -> [(c_uint, u32); 2] {
+    let vertical = if dy < 0 { 4 } else { 5 };
+    let horizontal = if dx < 0 { 6 } else { 7 };
+    [
+        (vertical, dy.unsigned_abs()),
+        (horizontal, dx.unsigned_abs()),
+    ]
+}
+
+/// Returns the physical button that the pointer mapping `map` translates into the `logical`
+/// button.
+///
+/// An empty mapping is treated as the identity. Returns `None` if no physical button produces
+/// `logical`.
+pub fn physical_button(map: &[u8], logical: c_uint) -> Option<c_uint> {
+    if map.is_empty() {
+        return Some(logical);
     }
+
+    map.iter()
+        .position(|&b| b as c_uint == logical)
+        .map(|index| index as c_uint + 1)
 }
 
-/// Converts the given character into a [`xlib::KeySym`] instance, if possible.
+/// Returns the keysyms that may produce the character `c`, in order of preference.
 ///
-/// The `bool` indicates whether the shift key should be pressed.
-pub fn char_to_x11(c: char) -> Option<(xlib::KeySym, bool)> {
-    match c {
-        'a' => Some((keysym::XK_a as xlib::KeySym, false)),
-        'b' => Some((keysym::XK_b as xlib::KeySym, false)),
-        'c' => Some((keysym::XK_c as xlib::KeySym, false)),
-        'd' => Some((keysym::XK_d as xlib::KeySym, false)),
-        'e' => Some((keysym::XK_e as xlib::KeySym, false)),
-        'f' => Some((keysym::XK_f as xlib::KeySym, false)),
-        'g' => Some((keysym::XK_g as xlib::KeySym, false)),
-        'h' => Some((keysym::XK_h as xlib::KeySym, false)),
-        'i' => Some((keysym::XK_i as xlib::KeySym, false)),
-        'j' => Some((keysym::XK_j as xlib::KeySym, false)),
-        'k' => Some((keysym::XK_k as xlib::KeySym, false)),
-        'l' => Some((keysym::XK_l as xlib::KeySym, false)),
-        'm' => Some((keysym::XK_m as xlib::KeySym, false)),
-        'n' => Some((keysym::XK_n as xlib::KeySym, false)),
-        'o' => Some((keysym::XK_o as xlib::KeySym, false)),
-        'p' => Some((keysym::XK_p as xlib::KeySym, false)),
-        'q' => Some((keysym::XK_q as xlib::KeySym, false)),
-        'r' => Some((keysym::XK_r as xlib::KeySym, false)),
-        's' => Some((keysym::XK_s as xlib::KeySym, false)),
-        't' => Some((keysym::XK_t as xlib::KeySym, false)),
-        'u' => Some((keysym::XK_u as xlib::KeySym, false)),
-        'v' => Some((keysym::XK_v as xlib::KeySym, false)),
-        'w' => Some((keysym::XK_w as xlib::KeySym, false)),
-        'x' => Some((keysym::XK_x as xlib::KeySym, false)),
-        'y' => Some((keysym::XK_y as xlib::KeySym, false)),
-        'z' => Some((keysym::XK_z as xlib::KeySym, false)),
-        'A' => Some((keysym::XK_A as xlib::KeySym, true)),
-        'B' => Some((keysym::XK_B as xlib::KeySym, true)),
-        'C' => Some((keysym::XK_C as xlib::KeySym, true)),
-        'D' => Some((keysym::XK_D as xlib::KeySym, true)),
-        'E' => Some((keysym::XK_E as xlib::KeySym, true)),
-        'F' => Some((keysym::XK_F as xlib::KeySym, true)),
-        'G' => Some((keysym::XK_G as xlib::KeySym, true)),
-        'H' => Some((keysym::XK_H as xlib::KeySym, true)),
-        'I' => Some((keysym::XK_I as xlib::KeySym, true)),
-        'J' => Some((keysym::XK_J as xlib::KeySym, true)),
-        'K' => Some((keysym::XK_K as xlib::KeySym, true)),
-        'L' => Some((keysym::XK_L as xlib::KeySym, true)),
-        'M' => Some((keysym::XK_M as xlib::KeySym, true)),
-        'N' => Some((keysym::XK_N as xlib::KeySym, true)),
-        'O' => Some((keysym::XK_O as xlib::KeySym, true)),
-        'P' => Some((keysym::XK_P as xlib::KeySym, true)),
-        'Q' => Some((keysym::XK_Q as xlib::KeySym, true)),
-        'R' => Some((keysym::XK_R as xlib::KeySym, true)),
-        'S' => Some((keysym::XK_S as xlib::KeySym, true)),
-        'T' => Some((keysym::XK_T as xlib::KeySym, true)),
-        'U' => Some((keysym::XK_U as xlib::KeySym, true)),
-        'V' => Some((keysym::XK_V as xlib::KeySym, true)),
-        'W' => Some((keysym::XK_W as xlib::KeySym, true)),
-        'X' => Some((keysym::XK_X as xlib::KeySym, true)),
-        'Y' => Some((keysym::XK_Y as xlib::KeySym, true)),
-        'Z' => Some((keysym::XK_Z as xlib::KeySym, true)),
-        '0' => Some((keysym::XK_0 as xlib::KeySym, false)),
-        '1' => Some((keysym::XK_1 as xlib::KeySym, false)),
-        '2' => Some((keysym::XK_2 as xlib::KeySym, false)),
-        '3' => Some((keysym::XK_3 as xlib::KeySym, false)),
-        '4' => Some((keysym::XK_4 as xlib::KeySym, false)),
-        '5' => Some((keysym::XK_5 as xlib::KeySym, false)),
-        '6' => Some((keysym::XK_6 as xlib::KeySym, false)),
-        '7' => Some((keysym::XK_7 as xlib::KeySym, false)),
-        '8' => Some((keysym::XK_8 as xlib::KeySym, false)),
-        '9' => Some((keysym::XK_9 as xlib::KeySym, false)),
-        ' ' => Some((keysym::XK_space as xlib::KeySym, false)),
-        '!' => Some((keysym::XK_exclam as xlib::KeySym, true)),
-        '"' => Some((keysym::XK_quotedbl as xlib::KeySym, true)),
-        '#' => Some((keysym::XK_numbersign as xlib::KeySym, true)),
-        '$' => Some((keysym::XK_dollar as xlib::KeySym, true)),
-        '%' => Some((keysym::XK_percent as xlib::KeySym, true)),
-        '&' => Some((keysym::XK_ampersand as xlib::KeySym, true)),
-        '(' => Some((keysym::XK_parenleft as xlib::KeySym, true)),
-        ')' => Some((keysym::XK_parenright as xlib::KeySym, true)),
-        '*' => Some((keysym::XK_asterisk as xlib::KeySym, true)),
-        '+' => Some((keysym::XK_plus as xlib::KeySym, true)),
-        ',' => Some((keysym::XK_comma as xlib::KeySym, false)),
-        '-' => Some((keysym::XK_minus as xlib::KeySym, false)),
-        '.' => Some((keysym::XK_period as xlib::KeySym, false)),
-        '/' => Some((keysym::XK_slash as xlib::KeySym, false)),
-        ':' => Some((keysym::XK_colon as xlib::KeySym, true)),
-        ';' => Some((keysym::XK_semicolon as xlib::KeySym, false)),
-        '<' => Some((keysym::XK_less as xlib::KeySym, true)),
-        '=' => Some((keysym::XK_equal as xlib::KeySym, false)),
-        '>' => Some((keysym::XK_greater as xlib::KeySym, true)),
-        '?' => Some((keysym::XK_question as xlib::KeySym, true)),
-        '@' => Some((keysym::XK_at as xlib::KeySym, true)),
-        '[' => Some((keysym::XK_bracketleft as xlib::KeySym, false)),
-        '\'' => Some((keysym::XK_hyphen as xlib::KeySym, false)),
-        '\\' => Some((keysym::XK_backslash as xlib::KeySym, false)),
-        '\n' => Some((keysym::XK_Return as xlib::KeySym, false)),
-        '\t' => Some((keysym::XK_Tab as xlib::KeySym, false)),
-        ']' => Some((keysym::XK_bracketright as xlib::KeySym, false)),
-        '^' => Some((keysym::XK_asciicircum as xlib::KeySym, true)),
-        '_' => Some((keysym::XK_underscore as xlib::KeySym, true)),
-        '`' => Some((keysym::XK_grave as xlib::KeySym, false)),
-        '{' => Some((keysym::XK_braceleft as xlib::KeySym, true)),
-        '|' => Some((keysym::XK_bar as xlib::KeySym, true)),
-        '}' => Some((keysym::XK_braceright as xlib::KeySym, true)),
-        '~' => Some((keysym::XK_asciitilde as xlib::KeySym, true)),
-        _ => None,
+/// Characters that have a legacy keysym, such as `é` or `€`, may also be bound to their Unicode
+/// keysym (the code point with the `0x01000000` bit set) by some layouts.
+pub fn char_to_keysyms(c: char) -> [Option<xlib::KeySym>; 2] {
+    // The line feed character has its own keysym, which most applications ignore.
+    let keysym = match c {
+        '\n' => keysym::XK_Return,
+        _ => xkeysym::Keysym::from_char(c).raw(),
+    };
+
+    // Unicode keysyms only exist for the code points beyond Latin-1.
+    let unicode = 0x0100_0000 | c as u32;
+    let unicode = (c as u32 >= 0x100 && unicode != keysym).then_some(unicode);
+
+    [
+        (keysym != xkeysym::key::NoSymbol).then_some(keysym as xlib::KeySym),
+        unicode.map(|keysym| keysym as xlib::KeySym),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::NAMED_KEYS;
+
+    #[test]
+    fn every_named_key_has_a_distinct_keysym() {
+        let mut keysyms = Vec::new();
+        for &key in NAMED_KEYS {
+            let keysym = key_to_x11(key).unwrap_or_else(|| panic!("{key:?} has no keysym"));
+            assert_ne!(keysym, 0, "{key:?} has no keysym");
+            assert!(!keysyms.contains(&keysym), "{key:?} shares its keysym");
+            keysyms.push(keysym);
+        }
+    }
+
+    #[test]
+    fn keysyms_are_passed_through() {
+        let search = keysym::XF86XK_Search;
+        assert_eq!(
+            key_to_x11(Key::Keysym(search)),
+            Some(search as xlib::KeySym)
+        );
+    }
+
+    #[test]
+    fn scancodes_are_offset_evdev_codes() {
+        assert_eq!(key_to_x11(Key::Scancode(30)), None);
+        assert_eq!(scancode_to_x11(30), Some(38));
+        assert_eq!(scancode_to_x11(247), Some(255));
+        assert_eq!(scancode_to_x11(248), None);
+        assert_eq!(scancode_to_x11(u32::MAX), None);
+    }
+
+    #[test]
+    fn wheel_buttons_go_through_the_pointer_mapping() {
+        // Natural scrolling swaps the wheel directions.
+        let map = [1, 2, 3, 5, 4, 7, 6, 8, 9];
+        let [(up, _), (left, _)] = scroll_to_x11(-1, -1);
+        assert_eq!(physical_button(&map, up), Some(5));
+        assert_eq!(physical_button(&map, left), Some(7));
+        assert_eq!(physical_button(&map, 1), Some(1));
+
+        // A disabled button cannot be produced, and an empty mapping changes nothing.
+        assert_eq!(physical_button(&[1, 2, 3, 0, 5], 4), None);
+        assert_eq!(physical_button(&[], 4), Some(4));
     }
 }
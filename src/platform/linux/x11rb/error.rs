@@ -0,0 +1,54 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+
+use x11rb::errors::{ConnectError, ConnectionError, ReplyError};
+
+use crate::{Button, Key};
+
+/// An error that may occur when interacting with the X11 window manager.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occured when opening a connection with the X server.
+    OpenDisplay(ConnectError),
+    /// The credentials used to connect to the X server could not be read.
+    Authority(io::Error),
+    /// The connection with the X server failed.
+    Connection(ConnectionError),
+    /// The X server replied to a request with an error.
+    Reply(ReplyError),
+    /// The key is not supported.
+    UnsupportedKey(Key),
+    /// The button is not supported.
+    UnsupportedButton(Button),
+    /// The character is not supported.
+    UnsupportedChar(char),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OpenDisplay(e) => write!(f, "failed to open a connection with the X server: {e}"),
+            Self::Authority(e) => write!(f, "failed to read the X authority: {e}"),
+            Self::Connection(e) => write!(f, "the connection with the X server failed: {e}"),
+            Self::Reply(e) => write!(f, "the X server behaved in an unexpected way: {e}"),
+            Self::UnsupportedKey(k) => write!(f, "the X server does not support the '{k:?}' key"),
+            Self::UnsupportedButton(b) => {
+                write!(f, "the X server does not support the '{b:?}' button")
+            }
+            Self::UnsupportedChar(c) => write!(f, "the X server does not support the {c:?} char"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::OpenDisplay(e) => Some(e),
+            Self::Authority(e) => Some(e),
+            Self::Connection(e) => Some(e),
+            Self::Reply(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! This module provides the implementation of `keysim` when using the X11 window manager, through
+//! the pure-Rust `x11rb` crate.
+//!
+//! Unlike the [`x11`](super::x11) backend, this one does not need to link against `libX11` and
+//! `libXtst`.
+
+mod simulator;
+pub use self::simulator::*;
+
+mod error;
+pub use self::error::*;
+
+mod sys;
+mod utils;
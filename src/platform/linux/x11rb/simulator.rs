@@ -0,0 +1,406 @@
+use std::time::Duration;
+
+use x11rb::protocol::xproto::{KeyButMask, Window};
+use xkeysym::key;
+
+use super::sys::OpenDisplay;
+use super::utils;
+use crate::motion::SubpixelAccumulator;
+use crate::platform::utils::{char_to_keysym, key_to_keysym};
+use crate::platform::{xauth, xsettings};
+use crate::{Button, Capabilities, Fidelity, Key, Screen, UnicodeSupport, X11Authority};
+
+/// The simulator used when using the X11 window manager through `x11rb`.
+pub struct Simulator {
+    /// The connection that's being used to simulate keystrokes.
+    display: OpenDisplay,
+
+    /// Whether the current X11 server supports the "XTEST" extension.
+    ///
+    /// The XTEST extension is prefered because events that are sent through it cannot be
+    /// distinguished from regular user events.
+    supports_xtest: bool,
+
+    /// The fractional part of the relative motions that have not been sent yet.
+    motion: SubpixelAccumulator,
+    /// The fractional part of the smooth scrolls that have not been sent yet.
+    scroll: SubpixelAccumulator,
+}
+
+impl Simulator {
+    /// Creates a new [`Simulator`] instance.
+    ///
+    /// When `display` or `authority` is `None`, the `DISPLAY` and `XAUTHORITY` environment
+    /// variables are used instead.
+    pub fn new(
+        display: Option<&str>,
+        authority: Option<&X11Authority>,
+    ) -> Result<Self, super::Error> {
+        let auth = authority
+            .map(|authority| xauth::resolve(authority, display))
+            .transpose()
+            .map_err(super::Error::Authority)?;
+        let display = OpenDisplay::open(display, auth)?;
+        let supports_xtest = display.xtest_query_extension();
+
+        Ok(Self {
+            display,
+            supports_xtest,
+            motion: SubpixelAccumulator::default(),
+            scroll: SubpixelAccumulator::default(),
+        })
+    }
+
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        let fidelity = match self.supports_xtest {
+            true => Fidelity::Native,
+            false => Fidelity::Synthetic,
+        };
+
+        // Valuators cannot be attached to the `FakeInput` requests sent by `x11rb`.
+        let max_extra_buttons = self.max_extra_buttons();
+        let mut ret =
+            Capabilities::new("x11rb", fidelity, max_extra_buttons, UnicodeSupport::Layout);
+        ret.inputs.smooth_scroll = false;
+        ret
+    }
+
+    /// Returns the number of extra buttons that can be sent, starting from
+    /// [`Button::Extra(0)`](Button::Extra).
+    fn max_extra_buttons(&self) -> u8 {
+        let max = u8::MAX - utils::FIRST_EXTRA_BUTTON + 1;
+        if !self.supports_xtest {
+            return max;
+        }
+
+        let map = self.display.pointer_mapping().unwrap_or_default();
+        if map.is_empty() {
+            return max;
+        }
+
+        (0..max)
+            .take_while(|&n| map.contains(&(utils::FIRST_EXTRA_BUTTON + n)))
+            .count() as u8
+    }
+
+    /// Returns the button number that must be sent to produce `button`.
+    ///
+    /// The "XTEST" extension simulates physical buttons, which the X server translates into
+    /// logical buttons according to its pointer mapping. That mapping is not applied to the
+    /// events sent with `SendEvent`.
+    fn button_to_x11(&self, button: Button) -> Result<u8, super::Error> {
+        let logical =
+            utils::button_to_x11(button).ok_or(super::Error::UnsupportedButton(button))?;
+        if !self.supports_xtest {
+            return Ok(logical);
+        }
+
+        utils::physical_button(&self.display.pointer_mapping()?, logical)
+            .ok_or(super::Error::UnsupportedButton(button))
+    }
+
+    /// Returns the keycode of `key`.
+    fn key_to_keycode(&self, key: Key) -> Result<u8, super::Error> {
+        let keycode = match key {
+            Key::Scancode(code) => utils::scancode_to_x11(code),
+            _ => match key_to_keysym(key) {
+                Some(keysym) => self.display.keysym_to_keycode(keysym)?,
+                None => None,
+            }
+            .map(|(keycode, _)| keycode),
+        };
+
+        keycode.ok_or(super::Error::UnsupportedKey(key))
+    }
+
+    /// Sends a fake key press event to the top-level window.
+    pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
+        let keycode = self.key_to_keycode(key)?;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_key_event(keycode, true, 0)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self.display
+                .send_key_event(window, keycode, KeyButMask::default(), true)?;
+        }
+        self.display.flush()
+    }
+
+    /// Sends a fake key release event to the top-level window.
+    pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
+        let keycode = self.key_to_keycode(key)?;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_key_event(keycode, false, 0)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self.display
+                .send_key_event(window, keycode, KeyButMask::default(), false)?;
+        }
+        self.display.flush()
+    }
+
+    /// Sends a fake keystroke event to the top-level window.
+    pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
+        let keycode = self.key_to_keycode(key)?;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_key_event(keycode, true, 0)?;
+            self.display.xtest_fake_key_event(keycode, false, 0)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self.display
+                .send_key_event(window, keycode, KeyButMask::default(), true)?;
+            self.display
+                .send_key_event(window, keycode, KeyButMask::default(), false)?;
+        }
+        self.display.flush()
+    }
+
+    /// Sends a fake button press to the top-level window.
+    pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
+        let button = self.button_to_x11(button)?;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_button_event(button, true, 0)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self.display.send_button_event(window, button, true)?;
+        }
+        self.display.flush()
+    }
+
+    /// Sends a fake button release to the top-level window.
+    pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
+        let button = self.button_to_x11(button)?;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_button_event(button, false, 0)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self.display.send_button_event(window, button, false)?;
+        }
+        self.display.flush()
+    }
+
+    /// Sends a fake button click to the top-level window.
+    pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
+        let button = self.button_to_x11(button)?;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_button_event(button, true, 0)?;
+            self.display.xtest_fake_button_event(button, false, 0)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self.display.send_button_event(window, button, true)?;
+            self.display.send_button_event(window, button, false)?;
+        }
+        self.display.flush()
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks by clicking the buttons `4` to `7`.
+    ///
+    /// Like other buttons, the wheel buttons go through the pointer mapping when they are sent
+    /// with "XTEST". The clicks of a wheel button that the mapping disables are dropped.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        let (window, map) = match self.supports_xtest {
+            true => (None, self.display.pointer_mapping()?),
+            false => (Some(self.display.get_input_focus()?), Vec::new()),
+        };
+
+        for (button, clicks) in utils::scroll_to_x11(dx, dy) {
+            let Some(button) = utils::physical_button(&map, button) else {
+                continue;
+            };
+
+            for _ in 0..clicks {
+                match window {
+                    None => {
+                        self.display.xtest_fake_button_event(button, true, 0)?;
+                        self.display.xtest_fake_button_event(button, false, 0)?;
+                    }
+                    Some(window) => {
+                        self.display.send_button_event(window, button, true)?;
+                        self.display.send_button_event(window, button, false)?;
+                    }
+                }
+            }
+        }
+        self.display.flush()
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks, rounded to whole wheel clicks.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let (dx, dy) = self.scroll.take(dx, dy);
+        self.scroll(dx, dy)
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`) of the default screen.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        // The X11 protocol uses 16-bit coordinates.
+        let x = x.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        let y = y.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_motion_event(x, y, 0)?;
+        } else {
+            self.display.warp_pointer(x, y)?;
+        }
+        self.display.flush()
+    }
+
+    /// Returns the position of the mouse pointer on the default screen.
+    ///
+    /// Returns `None` if the pointer is on another screen.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        let position = self.display.query_pointer()?;
+        Ok(position.map(|(x, y)| (x as i32, y as i32)))
+    }
+
+    /// Returns the monitors attached to the screen the connection was opened on.
+    ///
+    /// When the X server does not support version 1.5 of the "RANDR" extension, the whole
+    /// screen is returned as a single monitor.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        let rects = match self.display.randr_get_monitors()? {
+            Some(monitors) => monitors
+                .iter()
+                .map(|m| (m.x, m.y, m.width, m.height))
+                .collect(),
+            None => {
+                let (width, height) = self.display.screen_size();
+                vec![(0, 0, width, height)]
+            }
+        };
+
+        Ok(rects
+            .into_iter()
+            .filter(|&(_, _, width, height)| width > 0 && height > 0)
+            .map(|(x, y, width, height)| {
+                Screen::new(x.into(), y.into(), width.into(), height.into())
+            })
+            .collect())
+    }
+
+    /// Returns the double-click time advertised by the XSETTINGS manager of the screen the
+    /// connection was opened on, if any.
+    pub fn double_click_time(&self) -> Option<Duration> {
+        let screen = self.display.screen() as i32;
+        let selection = self
+            .display
+            .intern_atom_if_exists(&xsettings::selection_name(screen))
+            .ok()??;
+        let property = self
+            .display
+            .intern_atom_if_exists(xsettings::SETTINGS_PROPERTY)
+            .ok()??;
+
+        let owner = self.display.get_selection_owner(selection).ok()??;
+        let data = self
+            .display
+            .get_property(owner, property, property)
+            .ok()??;
+        xsettings::double_click_time(&data)
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) relative to its current position.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let (dx, dy) = self.motion.take(dx, dy);
+        if dx == 0 && dy == 0 {
+            return Ok(());
+        }
+
+        // The X11 protocol uses 16-bit coordinates.
+        let dx = dx.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        let dy = dy.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+
+        if self.supports_xtest {
+            self.display.xtest_fake_relative_motion_event(dx, dy, 0)?;
+        } else {
+            self.display.warp_pointer_by(dx, dy)?;
+        }
+        self.display.flush()
+    }
+
+    /// Sends a unicode code-point using the "XTEST" extension.
+    fn _send_char_xtest(&self, c: char) -> Result<(), super::Error> {
+        let keysym = char_to_keysym(c).ok_or(super::Error::UnsupportedChar(c))?;
+
+        let (keycode, shift) = self
+            .display
+            .keysym_to_keycode(keysym)?
+            .ok_or(super::Error::UnsupportedChar(c))?;
+        let mut shift_keycode = 0;
+
+        if shift {
+            (shift_keycode, _) = self
+                .display
+                .keysym_to_keycode(key::Shift_L)?
+                .ok_or(super::Error::UnsupportedChar(c))?;
+            self.display.xtest_fake_key_event(shift_keycode, true, 0)?;
+        }
+
+        let mut result = self
+            .display
+            .xtest_fake_key_event(keycode, true, 0)
+            .and_then(|()| self.display.xtest_fake_key_event(keycode, false, 0));
+
+        if shift {
+            // Shift must not remain held, even when the key could not be sent.
+            let released = self.display.xtest_fake_key_event(shift_keycode, false, 0);
+            result = result.and(released);
+        }
+
+        result
+    }
+
+    /// Sends a unicode code-point.
+    fn _send_char(&self, window: Window, c: char) -> Result<(), super::Error> {
+        let keysym = char_to_keysym(c).ok_or(super::Error::UnsupportedChar(c))?;
+
+        let (keycode, shift) = self
+            .display
+            .keysym_to_keycode(keysym)?
+            .ok_or(super::Error::UnsupportedChar(c))?;
+        let state = if shift {
+            KeyButMask::SHIFT
+        } else {
+            KeyButMask::default()
+        };
+        self.display.send_key_event(window, keycode, state, true)?;
+        self.display.send_key_event(window, keycode, state, false)?;
+
+        Ok(())
+    }
+
+    /// Sends a specific unicode code-point.
+    pub fn send_char(&self, c: char) -> Result<(), super::Error> {
+        if self.supports_xtest {
+            self._send_char_xtest(c)?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            self._send_char(window, c)?;
+        }
+
+        self.display.flush()
+    }
+
+    /// Sends a collection of characters.
+    pub fn send_chars(&self, mut it: impl Iterator<Item = char>) -> Result<(), super::Error> {
+        if self.supports_xtest {
+            it.try_for_each(|c| self._send_char_xtest(c))?;
+        } else {
+            let window = self.display.get_input_focus()?;
+            it.try_for_each(move |c| self._send_char(window, c))?;
+        }
+
+        self.display.flush()
+    }
+
+    /// Sends a string.
+    pub fn send_str(&self, s: &str) -> Result<(), super::Error> {
+        self.send_chars(s.chars())
+    }
+}
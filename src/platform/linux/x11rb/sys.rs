@@ -0,0 +1,506 @@
+//! Wraps the requests made to the X server into function calls associated to the [`OpenDisplay`]
+//! type.
+
+use std::sync::{Mutex, PoisonError};
+
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::errors::{ConnectError, DisplayParsingError};
+use x11rb::protocol::randr::{self, ConnectionExt as _, MonitorInfo};
+use x11rb::protocol::xproto::{
+    self, Atom, ButtonPressEvent, ConnectionExt as _, EventMask, KeyButMask, KeyPressEvent,
+    Keycode, Keysym, Mapping, Window,
+};
+use x11rb::protocol::xtest::{self, ConnectionExt as _};
+use x11rb::protocol::Event;
+use x11rb::reexports::x11rb_protocol::parse_display::parse_display;
+use x11rb::rust_connection::{DefaultStream, RustConnection};
+
+/// The keyboard mapping of the X server, as returned by `GetKeyboardMapping`.
+struct KeyboardMapping {
+    /// The first keycode described in `keysyms`.
+    min_keycode: Keycode,
+    /// The number of keysyms associated with each keycode in `keysyms`.
+    keysyms_per_keycode: usize,
+    /// The keysyms associated with each keycode.
+    keysyms: Vec<Keysym>,
+}
+
+/// An open connection with the X server.
+pub struct OpenDisplay {
+    /// The connection is boxed because it is quite large, and would otherwise bloat the
+    /// platform simulator.
+    connection: Box<RustConnection>,
+
+    /// The screen the connection was opened on.
+    screen: usize,
+    /// The root window of the screen the connection was opened on.
+    root: Window,
+
+    /// The keyboard mapping of the X server.
+    ///
+    /// This is `None` until it is needed, and after the mapping changes.
+    keyboard_mapping: Mutex<Option<KeyboardMapping>>,
+
+    /// The pointer mapping of the X server, as returned by `GetPointerMapping`.
+    ///
+    /// This is `None` until it is needed, and after the mapping changes.
+    pointer_mapping: Mutex<Option<Vec<u8>>>,
+}
+
+/// Connects to the X display named `display` with the provided authorization protocol name and
+/// data, rather than the ones found in `$XAUTHORITY`.
+fn connect_with_auth(
+    display: Option<&str>,
+    name: Vec<u8>,
+    data: Vec<u8>,
+) -> Result<(RustConnection, usize), ConnectError> {
+    let parsed = parse_display(display)?;
+    let screen = parsed.screen as usize;
+
+    let mut error = None;
+    for address in parsed.connect_instruction() {
+        match DefaultStream::connect(&address) {
+            Ok((stream, _)) => {
+                let connection =
+                    RustConnection::connect_to_stream_with_auth_info(stream, screen, name, data)?;
+                return Ok((connection, screen));
+            }
+            Err(e) => error = Some(e),
+        }
+    }
+
+    Err(match error {
+        Some(e) => ConnectError::IoError(e),
+        None => DisplayParsingError::Unknown.into(),
+    })
+}
+
+impl OpenDisplay {
+    /// Opens a connection with the X11 server.
+    ///
+    /// `display` is the name of the display to connect to, and `auth` the authorization protocol
+    /// name and data to use. When they are `None`, `$DISPLAY` and `$XAUTHORITY` are used instead.
+    pub fn open(
+        display: Option<&str>,
+        auth: Option<(Vec<u8>, Vec<u8>)>,
+    ) -> Result<Self, super::Error> {
+        let (connection, screen) = match auth {
+            Some((name, data)) => connect_with_auth(display, name, data),
+            None => x11rb::connect(display),
+        }
+        .map_err(super::Error::OpenDisplay)?;
+
+        let root = connection.setup().roots[screen].root;
+
+        Ok(Self {
+            connection: Box::new(connection),
+            screen,
+            root,
+            keyboard_mapping: Mutex::default(),
+            pointer_mapping: Mutex::default(),
+        })
+    }
+
+    /// Determines whether the X server supports the "XTEST" extension.
+    pub fn xtest_query_extension(&self) -> bool {
+        matches!(
+            self.connection
+                .extension_information(xtest::X11_EXTENSION_NAME),
+            Ok(Some(_))
+        )
+    }
+
+    /// Returns the area covered by each monitor attached to the screen the connection was opened
+    /// on, with the `RRGetMonitors` request.
+    ///
+    /// Returns `None` if the X server does not support version 1.5 of the "RANDR" extension.
+    pub fn randr_get_monitors(&self) -> Result<Option<Vec<MonitorInfo>>, super::Error> {
+        if !matches!(
+            self.connection
+                .extension_information(randr::X11_EXTENSION_NAME),
+            Ok(Some(_))
+        ) {
+            return Ok(None);
+        }
+
+        let version = self
+            .connection
+            .randr_query_version(1, 5)
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+        if (version.major_version, version.minor_version) < (1, 5) {
+            return Ok(None);
+        }
+
+        let reply = self
+            .connection
+            .randr_get_monitors(self.root, true)
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok(Some(reply.monitors))
+    }
+
+    /// Returns the size of the screen the connection was opened on, in pixels.
+    pub fn screen_size(&self) -> (u16, u16) {
+        let screen = &self.connection.setup().roots[self.screen];
+        (screen.width_in_pixels, screen.height_in_pixels)
+    }
+
+    /// Wraps the `GetInputFocus` request.
+    pub fn get_input_focus(&self) -> Result<Window, super::Error> {
+        let reply = self
+            .connection
+            .get_input_focus()
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok(reply.focus)
+    }
+
+    /// Returns the screen the connection was opened on.
+    #[inline]
+    pub fn screen(&self) -> usize {
+        self.screen
+    }
+
+    /// Returns the atom named `name`, with the `InternAtom` request.
+    ///
+    /// Returns `None` if no atom with that name exists yet.
+    pub fn intern_atom_if_exists(&self, name: &str) -> Result<Option<Atom>, super::Error> {
+        let reply = self
+            .connection
+            .intern_atom(true, name.as_bytes())
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok((reply.atom != x11rb::NONE).then_some(reply.atom))
+    }
+
+    /// Returns the owner of `selection`, with the `GetSelectionOwner` request.
+    ///
+    /// Returns `None` if the selection has no owner.
+    pub fn get_selection_owner(&self, selection: Atom) -> Result<Option<Window>, super::Error> {
+        let reply = self
+            .connection
+            .get_selection_owner(selection)
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok((reply.owner != x11rb::NONE).then_some(reply.owner))
+    }
+
+    /// Returns the content of the `property` of `window`, provided that its type is `type_` and
+    /// that it is made of bytes, with the `GetProperty` request.
+    pub fn get_property(
+        &self,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+    ) -> Result<Option<Vec<u8>>, super::Error> {
+        let reply = self
+            .connection
+            .get_property(false, window, property, type_, 0, u32::MAX)
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok((reply.type_ == type_ && reply.format == 8).then_some(reply.value))
+    }
+
+    /// Processes the events sent by the X server, dropping the mappings that `MappingNotify`
+    /// events invalidate.
+    fn take_mapping_notify(&self) -> Result<(), super::Error> {
+        while let Some(event) = self
+            .connection
+            .poll_for_event()
+            .map_err(super::Error::Connection)?
+        {
+            let Event::MappingNotify(event) = event else {
+                continue;
+            };
+
+            if event.request == Mapping::POINTER {
+                *self
+                    .pointer_mapping
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner) = None;
+            } else {
+                *self
+                    .keyboard_mapping
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner) = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the pointer mapping of the X server.
+    ///
+    /// The `n`th element of the returned list is the logical button produced by the physical
+    /// button `n + 1`, or `0` if that button is disabled.
+    ///
+    /// The mapping is read with the `GetPointerMapping` request the first time it is needed, and
+    /// again whenever it changes.
+    pub fn pointer_mapping(&self) -> Result<Vec<u8>, super::Error> {
+        self.take_mapping_notify()?;
+
+        let mut map = self
+            .pointer_mapping
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(map) = &*map {
+            return Ok(map.clone());
+        }
+
+        let reply = self
+            .connection
+            .get_pointer_mapping()
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok(map.insert(reply.map).clone())
+    }
+
+    /// Looks up the keyboard mapping of the X server for a keycode that produces `keysym`.
+    ///
+    /// The returned `bool` indicates whether the shift key must be pressed in order to produce
+    /// the keysym.
+    ///
+    /// The mapping is read with the `GetKeyboardMapping` request the first time it is needed,
+    /// and again whenever it changes.
+    pub fn keysym_to_keycode(
+        &self,
+        keysym: Keysym,
+    ) -> Result<Option<(Keycode, bool)>, super::Error> {
+        self.take_mapping_notify()?;
+
+        let mut mapping = self
+            .keyboard_mapping
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mapping = match &mut *mapping {
+            Some(mapping) => mapping,
+            None => mapping.insert(self.get_keyboard_mapping()?),
+        };
+
+        if mapping.keysyms_per_keycode == 0 || keysym == 0 {
+            return Ok(None);
+        }
+
+        Ok(mapping
+            .keysyms
+            .chunks(mapping.keysyms_per_keycode)
+            .enumerate()
+            .find_map(|(index, keysyms)| {
+                // Only the first two levels can be reached with the shift key alone.
+                let level = keysyms.iter().take(2).position(|&k| k == keysym)?;
+                Some((mapping.min_keycode + index as u8, level == 1))
+            }))
+    }
+
+    /// Reads the keyboard mapping of the X server, with the `GetKeyboardMapping` request.
+    fn get_keyboard_mapping(&self) -> Result<KeyboardMapping, super::Error> {
+        let setup = self.connection.setup();
+        let min_keycode = setup.min_keycode;
+        let max_keycode = setup.max_keycode;
+
+        let reply = self
+            .connection
+            .get_keyboard_mapping(min_keycode, max_keycode - min_keycode + 1)
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok(KeyboardMapping {
+            min_keycode,
+            keysyms_per_keycode: reply.keysyms_per_keycode as usize,
+            keysyms: reply.keysyms,
+        })
+    }
+
+    /// Flushes the requests that have not been sent to the X server yet.
+    #[inline]
+    pub fn flush(&self) -> Result<(), super::Error> {
+        self.connection.flush().map_err(super::Error::Connection)
+    }
+
+    /// Sends a key event to `window` with the `SendEvent` request.
+    pub fn send_key_event(
+        &self,
+        window: Window,
+        keycode: Keycode,
+        state: KeyButMask,
+        press: bool,
+    ) -> Result<(), super::Error> {
+        let (response_type, mask) = match press {
+            true => (xproto::KEY_PRESS_EVENT, EventMask::KEY_PRESS),
+            false => (xproto::KEY_RELEASE_EVENT, EventMask::KEY_RELEASE),
+        };
+
+        let event = KeyPressEvent {
+            response_type,
+            detail: keycode,
+            sequence: 0,
+            time: x11rb::CURRENT_TIME,
+            root: self.root,
+            event: window,
+            child: x11rb::NONE,
+            root_x: 0,
+            root_y: 0,
+            event_x: 0,
+            event_y: 0,
+            state,
+            same_screen: true,
+        };
+
+        self.connection
+            .send_event(true, window, mask, event)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Sends a button event to `window` with the `SendEvent` request.
+    pub fn send_button_event(
+        &self,
+        window: Window,
+        button: u8,
+        press: bool,
+    ) -> Result<(), super::Error> {
+        let (response_type, mask) = match press {
+            true => (xproto::BUTTON_PRESS_EVENT, EventMask::BUTTON_PRESS),
+            false => (xproto::BUTTON_RELEASE_EVENT, EventMask::BUTTON_RELEASE),
+        };
+
+        let event = ButtonPressEvent {
+            response_type,
+            detail: button,
+            sequence: 0,
+            time: x11rb::CURRENT_TIME,
+            root: self.root,
+            event: window,
+            child: x11rb::NONE,
+            root_x: 0,
+            root_y: 0,
+            event_x: 0,
+            event_y: 0,
+            state: KeyButMask::default(),
+            same_screen: true,
+        };
+
+        self.connection
+            .send_event(true, window, mask, event)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Wraps the `FakeInput` request of the "XTEST" extension for motion events.
+    ///
+    /// The pointer is moved to the position (`x`, `y`) of the screen the connection was opened
+    /// on.
+    pub fn xtest_fake_motion_event(&self, x: i16, y: i16, delay: u32) -> Result<(), super::Error> {
+        // A `detail` of zero means that the coordinates are absolute.
+        self.connection
+            .xtest_fake_input(xproto::MOTION_NOTIFY_EVENT, 0, delay, self.root, x, y, 0)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Wraps the `FakeInput` request of the "XTEST" extension for relative motion events.
+    pub fn xtest_fake_relative_motion_event(
+        &self,
+        dx: i16,
+        dy: i16,
+        delay: u32,
+    ) -> Result<(), super::Error> {
+        // A `detail` of one means that the coordinates are relative to the current position.
+        self.connection
+            .xtest_fake_input(
+                xproto::MOTION_NOTIFY_EVENT,
+                1,
+                delay,
+                x11rb::NONE,
+                dx,
+                dy,
+                0,
+            )
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Returns the position of the pointer on the screen the connection was opened on, with the
+    /// `QueryPointer` request.
+    ///
+    /// Returns `None` if the pointer is on another screen.
+    pub fn query_pointer(&self) -> Result<Option<(i16, i16)>, super::Error> {
+        let reply = self
+            .connection
+            .query_pointer(self.root)
+            .map_err(super::Error::Connection)?
+            .reply()
+            .map_err(super::Error::Reply)?;
+
+        Ok(reply.same_screen.then_some((reply.root_x, reply.root_y)))
+    }
+
+    /// Moves the pointer to the position (`x`, `y`) of the screen the connection was opened on,
+    /// with the `WarpPointer` request.
+    pub fn warp_pointer(&self, x: i16, y: i16) -> Result<(), super::Error> {
+        self.connection
+            .warp_pointer(x11rb::NONE, self.root, 0, 0, 0, 0, x, y)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Moves the pointer by (`dx`, `dy`) relative to its current position, with the
+    /// `WarpPointer` request.
+    pub fn warp_pointer_by(&self, dx: i16, dy: i16) -> Result<(), super::Error> {
+        self.connection
+            .warp_pointer(x11rb::NONE, x11rb::NONE, 0, 0, 0, 0, dx, dy)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Wraps the `FakeInput` request of the "XTEST" extension for key events.
+    pub fn xtest_fake_key_event(
+        &self,
+        keycode: Keycode,
+        press: bool,
+        delay: u32,
+    ) -> Result<(), super::Error> {
+        let type_ = match press {
+            true => xproto::KEY_PRESS_EVENT,
+            false => xproto::KEY_RELEASE_EVENT,
+        };
+
+        self.connection
+            .xtest_fake_input(type_, keycode, delay, x11rb::NONE, 0, 0, 0)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+
+    /// Wraps the `FakeInput` request of the "XTEST" extension for button events.
+    pub fn xtest_fake_button_event(
+        &self,
+        button: u8,
+        press: bool,
+        delay: u32,
+    ) -> Result<(), super::Error> {
+        let type_ = match press {
+            true => xproto::BUTTON_PRESS_EVENT,
+            false => xproto::BUTTON_RELEASE_EVENT,
+        };
+
+        self.connection
+            .xtest_fake_input(type_, button, delay, x11rb::NONE, 0, 0, 0)
+            .map_err(super::Error::Connection)?;
+        Ok(())
+    }
+}
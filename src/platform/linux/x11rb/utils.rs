@@ -0,0 +1,82 @@
+//! This module provides utility functions to work with the X11 protocol.
+
+use crate::Button;
+
+/// The button number of [`Button::Extra(0)`](Button::Extra). The buttons `4` to `7` are used
+/// by the mouse wheel.
+pub const FIRST_EXTRA_BUTTON: u8 = 8;
+
+/// Converts an evdev key code into an X11 keycode.
+///
+/// The X servers that use the `evdev` or `libinput` drivers offset the evdev key codes by `8`.
+pub fn scancode_to_x11(code: u32) -> Option<u8> {
+    code.checked_add(8)?.try_into().ok()
+}
+
+/// Converts a [`Button`] instance into a logical button number understood by X11.
+///
+/// Extra buttons are mapped onto the buttons `8` and above. Returns `None` if the button number
+/// does not fit in a byte.
+pub fn button_to_x11(button: Button) -> Option<u8> {
+    match button {
+        Button::Left => Some(1),
+        Button::Middle => Some(2),
+        Button::Right => Some(3),
+        Button::Extra(n) => FIRST_EXTRA_BUTTON.checked_add(n),
+    }
+}
+
+/// Returns the X11 buttons that scroll by (`dx`, `dy`) wheel clicks, along with the number of
+/// times they must be clicked.
+///
+/// Buttons `4` and `5` scroll up and down, buttons `6` and `7` scroll left and right.
+pub fn scroll_to_x11(dx: i32, dy: i32) -> [(u8, u32); 2] {
+    let vertical = if dy < 0 { 4 } else { 5 };
+    let horizontal = if dx < 0 { 6 } else { 7 };
+    [
+        (vertical, dy.unsigned_abs()),
+        (horizontal, dx.unsigned_abs()),
+    ]
+}
+
+/// Returns the physical button that the pointer mapping `map` translates into the `logical`
+/// button.
+///
+/// An empty mapping is treated as the identity. Returns `None` if no physical button produces
+/// `logical`.
+pub fn physical_button(map: &[u8], logical: u8) -> Option<u8> {
+    if map.is_empty() {
+        return Some(logical);
+    }
+
+    map.iter()
+        .position(|&b| b == logical)
+        .map(|index| index as u8 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scancodes_are_offset_evdev_codes() {
+        assert_eq!(scancode_to_x11(30), Some(38));
+        assert_eq!(scancode_to_x11(247), Some(255));
+        assert_eq!(scancode_to_x11(248), None);
+        assert_eq!(scancode_to_x11(u32::MAX), None);
+    }
+
+    #[test]
+    fn wheel_buttons_go_through_the_pointer_mapping() {
+        // Natural scrolling swaps the wheel directions.
+        let map = [1, 2, 3, 5, 4, 7, 6, 8, 9];
+        let [(up, _), (left, _)] = scroll_to_x11(-1, -1);
+        assert_eq!(physical_button(&map, up), Some(5));
+        assert_eq!(physical_button(&map, left), Some(7));
+        assert_eq!(physical_button(&map, 1), Some(1));
+
+        // A disabled button cannot be produced, and an empty mapping changes nothing.
+        assert_eq!(physical_button(&[1, 2, 3, 0, 5], 4), None);
+        assert_eq!(physical_button(&[], 4), Some(4));
+    }
+}
@@ -0,0 +1,188 @@
+//! Reads the credentials used to connect to an X server from an `Xauthority` file.
+
+use std::io;
+use std::path::Path;
+
+use crate::X11Authority;
+
+/// The address family of the entries that apply to any host.
+const FAMILY_WILD: u16 = 0xffff;
+/// The address family of the entries that apply to local connections.
+const FAMILY_LOCAL: u16 = 0x0100;
+
+/// Returns the display number of the X display named `display`.
+///
+/// When `display` is `None`, the `DISPLAY` environment variable is used instead. For example,
+/// `"localhost:3.0"` becomes `"3"`.
+fn display_number(display: Option<&str>) -> Option<String> {
+    let display = match display {
+        Some(display) => display.to_owned(),
+        None => std::env::var("DISPLAY").ok()?,
+    };
+
+    let (_, number) = display.rsplit_once(':')?;
+    let number = number.split('.').next()?;
+    Some(number.to_owned())
+}
+
+/// Reads a length-prefixed field of an `Xauthority` entry.
+fn read_field<'a>(bytes: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let (len, rest) = bytes.split_first_chunk::<2>()?;
+    let len = u16::from_be_bytes(*len) as usize;
+    let field = rest.get(..len)?;
+    *bytes = &rest[len..];
+    Some(field)
+}
+
+/// Reads the `Xauthority` file at `path` and returns the authorization protocol name and data
+/// of the entry that applies to `display`.
+///
+/// Local entries are preferred. Otherwise, the first entry for the display number is used,
+/// regardless of the host it applies to.
+pub fn read_cookie(path: &Path, display: Option<&str>) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let contents = std::fs::read(path)?;
+    let number = display_number(display)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid display name"))?;
+
+    let mut fallback = None;
+    let mut bytes = contents.as_slice();
+    while !bytes.is_empty() {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid Xauthority file");
+
+        let (family, rest) = bytes.split_first_chunk::<2>().ok_or_else(invalid)?;
+        let family = u16::from_be_bytes(*family);
+        bytes = rest;
+
+        let _address = read_field(&mut bytes).ok_or_else(invalid)?;
+        let entry_number = read_field(&mut bytes).ok_or_else(invalid)?;
+        let name = read_field(&mut bytes).ok_or_else(invalid)?;
+        let data = read_field(&mut bytes).ok_or_else(invalid)?;
+
+        // An empty display number matches any display.
+        let number_matches = entry_number.is_empty() || entry_number == number.as_bytes();
+        if !number_matches {
+            continue;
+        }
+
+        if matches!(family, FAMILY_LOCAL | FAMILY_WILD) {
+            return Ok((name.to_vec(), data.to_vec()));
+        }
+        fallback.get_or_insert((name, data));
+    }
+
+    fallback
+        .map(|(name, data)| (name.to_vec(), data.to_vec()))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "no Xauthority entry applies to the display",
+            )
+        })
+}
+
+/// Returns the authorization protocol name and data described by `authority`, for the X display
+/// named `display`.
+pub fn resolve(authority: &X11Authority, display: Option<&str>) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    match authority {
+        X11Authority::File(path) => read_cookie(path, display),
+        X11Authority::Cookie { name, data } => Ok((name.as_bytes().to_vec(), data.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The address family of the entries that apply to an IPv4 host.
+    const FAMILY_INTERNET: u16 = 0;
+
+    /// Returns an `Xauthority` entry.
+    fn entry(family: u16, address: &str, number: &str, name: &str, data: &[u8]) -> Vec<u8> {
+        let mut ret = family.to_be_bytes().to_vec();
+        for field in [address.as_bytes(), number.as_bytes(), name.as_bytes(), data] {
+            ret.extend_from_slice(&(field.len() as u16).to_be_bytes());
+            ret.extend_from_slice(field);
+        }
+        ret
+    }
+
+    /// Writes `contents` to a temporary `Xauthority` file and reads the entry for `display`.
+    fn read(test: &str, contents: &[u8], display: &str) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let path = std::env::temp_dir().join(format!("keysim-xauth-{}-{test}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let ret = read_cookie(&path, Some(display));
+        std::fs::remove_file(&path).unwrap();
+        ret
+    }
+
+    #[test]
+    fn display_numbers() {
+        assert_eq!(display_number(Some(":3")).as_deref(), Some("3"));
+        assert_eq!(display_number(Some(":3.0")).as_deref(), Some("3"));
+        assert_eq!(display_number(Some("host:12")).as_deref(), Some("12"));
+        assert_eq!(display_number(Some("host:12.1")).as_deref(), Some("12"));
+        assert_eq!(display_number(Some("[::1]:4")).as_deref(), Some("4"));
+        assert_eq!(display_number(Some("nocolon")), None);
+    }
+
+    #[test]
+    fn screen_suffix_is_ignored() {
+        let contents = entry(FAMILY_LOCAL, "box", "3", "MIT-MAGIC-COOKIE-1", &[1, 2, 3]);
+        let (name, data) = read("screen", &contents, ":3.0").unwrap();
+        assert_eq!(name, b"MIT-MAGIC-COOKIE-1");
+        assert_eq!(data, [1, 2, 3]);
+    }
+
+    #[test]
+    fn remote_host() {
+        let contents = entry(
+            FAMILY_INTERNET,
+            "\x0a\0\0\x01",
+            "5",
+            "MIT-MAGIC-COOKIE-1",
+            &[9],
+        );
+        let (_, data) = read("host", &contents, "10.0.0.1:5").unwrap();
+        assert_eq!(data, [9]);
+
+        let err = read("host-other", &contents, "10.0.0.1:6").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn truncated_entry() {
+        let contents = entry(FAMILY_LOCAL, "box", "3", "MIT-MAGIC-COOKIE-1", &[1, 2, 3]);
+        for len in [1, 2, 8, contents.len() - 1] {
+            let err = read("truncated", &contents[..len], ":3").unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn entries_for_other_displays_are_skipped() {
+        let mut contents = entry(FAMILY_LOCAL, "box", "0", "MIT-MAGIC-COOKIE-1", &[0]);
+        contents.extend(entry(FAMILY_LOCAL, "box", "1", "MIT-MAGIC-COOKIE-1", &[1]));
+        contents.extend(entry(FAMILY_LOCAL, "box", "", "XDM-AUTHORIZATION-1", &[2]));
+        let (_, data) = read("several", &contents, ":1").unwrap();
+        assert_eq!(data, [1]);
+
+        // An empty display number matches any display.
+        let (name, data) = read("several-wild", &contents, ":7").unwrap();
+        assert_eq!(name, b"XDM-AUTHORIZATION-1");
+        assert_eq!(data, [2]);
+    }
+
+    #[test]
+    fn local_entries_are_preferred() {
+        let mut contents = entry(
+            FAMILY_INTERNET,
+            "\x7f\0\0\x01",
+            "2",
+            "MIT-MAGIC-COOKIE-1",
+            &[1],
+        );
+        contents.extend(entry(FAMILY_LOCAL, "box", "2", "MIT-MAGIC-COOKIE-1", &[2]));
+        let (_, data) = read("local", &contents, ":2").unwrap();
+        assert_eq!(data, [2]);
+    }
+}
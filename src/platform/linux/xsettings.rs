@@ -0,0 +1,214 @@
+//! Parses the settings shared by the desktop environment through the XSETTINGS protocol.
+//!
+//! The settings are stored in the `_XSETTINGS_SETTINGS` property of the window that owns the
+//! `_XSETTINGS_S<screen>` selection.
+
+use std::time::Duration;
+
+/// The type of the settings whose value is an integer.
+const TYPE_INTEGER: u8 = 0;
+/// The type of the settings whose value is a string.
+const TYPE_STRING: u8 = 1;
+/// The type of the settings whose value is a color.
+const TYPE_COLOR: u8 = 2;
+
+/// Returns the name of the selection owned by the XSETTINGS manager of `screen`.
+pub fn selection_name(screen: i32) -> String {
+    format!("_XSETTINGS_S{screen}")
+}
+
+/// The name of the property that holds the settings.
+pub const SETTINGS_PROPERTY: &str = "_XSETTINGS_SETTINGS";
+
+/// Reads the settings in `data` and returns the value of the `Net/DoubleClickTime` setting, if
+/// present.
+pub fn double_click_time(data: &[u8]) -> Option<Duration> {
+    let millis = find_integer(data, b"Net/DoubleClickTime")?;
+    u64::try_from(millis).ok().map(Duration::from_millis)
+}
+
+/// Reads the settings in `data` and returns the value of the integer setting named `name`.
+fn find_integer(data: &[u8], name: &[u8]) -> Option<i32> {
+    let mut reader = Reader {
+        data,
+        big_endian: *data.first()? != 0,
+    };
+
+    reader.skip(4)?;
+    let _serial = reader.u32()?;
+    let count = reader.u32()?;
+
+    for _ in 0..count {
+        let type_ = reader.u8()?;
+        reader.skip(1)?;
+        let name_len = reader.u16()? as usize;
+        let setting = reader.bytes(name_len)?;
+        reader.skip(padding(name_len))?;
+        let _last_change = reader.u32()?;
+
+        match type_ {
+            TYPE_INTEGER => {
+                let value = reader.u32()? as i32;
+                if setting == name {
+                    return Some(value);
+                }
+            }
+            TYPE_STRING => {
+                let len = reader.u32()? as usize;
+                reader.skip(len.checked_add(padding(len))?)?;
+            }
+            TYPE_COLOR => reader.skip(8)?,
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+/// Returns the number of padding bytes that follow a field of `len` bytes.
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+/// Reads the fields of the XSETTINGS format.
+struct Reader<'a> {
+    data: &'a [u8],
+    /// Whether the integers are stored in big-endian order.
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(..len)?;
+        self.data = &self.data[len..];
+        Some(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        let bytes = self.bytes(2)?.try_into().ok()?;
+        Some(match self.big_endian {
+            true => u16::from_be_bytes(bytes),
+            false => u16::from_le_bytes(bytes),
+        })
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.bytes(4)?.try_into().ok()?;
+        Some(match self.big_endian {
+            true => u32::from_be_bytes(bytes),
+            false => u32::from_le_bytes(bytes),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The value of a setting.
+    enum Value<'a> {
+        Integer(i32),
+        String(&'a str),
+        Color,
+    }
+
+    /// Returns the contents of an `_XSETTINGS_SETTINGS` property holding `settings`.
+    fn encode(big_endian: bool, settings: &[(&str, Value)]) -> Vec<u8> {
+        let u16 = |n: u16| match big_endian {
+            true => n.to_be_bytes(),
+            false => n.to_le_bytes(),
+        };
+        let u32 = |n: u32| match big_endian {
+            true => n.to_be_bytes(),
+            false => n.to_le_bytes(),
+        };
+        let pad = |data: &mut Vec<u8>, len: usize| data.resize(data.len() + padding(len), 0);
+
+        let mut data = vec![big_endian as u8, 0, 0, 0];
+        data.extend(u32(7));
+        data.extend(u32(settings.len() as u32));
+
+        for (name, value) in settings {
+            let type_ = match value {
+                Value::Integer(_) => TYPE_INTEGER,
+                Value::String(_) => TYPE_STRING,
+                Value::Color => TYPE_COLOR,
+            };
+            data.extend([type_, 0]);
+            data.extend(u16(name.len() as u16));
+            data.extend(name.as_bytes());
+            pad(&mut data, name.len());
+            data.extend(u32(0));
+
+            match value {
+                Value::Integer(n) => data.extend(u32(*n as u32)),
+                Value::String(s) => {
+                    data.extend(u32(s.len() as u32));
+                    data.extend(s.as_bytes());
+                    pad(&mut data, s.len());
+                }
+                Value::Color => data.extend([0xff; 8]),
+            }
+        }
+
+        data
+    }
+
+    #[test]
+    fn both_byte_orders() {
+        for big_endian in [false, true] {
+            let data = encode(big_endian, &[("Net/DoubleClickTime", Value::Integer(250))]);
+            assert_eq!(double_click_time(&data), Some(Duration::from_millis(250)));
+        }
+    }
+
+    #[test]
+    fn other_settings_are_skipped() {
+        for big_endian in [false, true] {
+            let data = encode(
+                big_endian,
+                &[
+                    ("Net/ThemeName", Value::String("Adwaita")),
+                    ("Gtk/CursorThemeName", Value::String("abcd")),
+                    ("Net/DndDragThreshold", Value::Integer(8)),
+                    ("Gtk/ColorScheme", Value::Color),
+                    ("Net/DoubleClickTime", Value::Integer(400)),
+                ],
+            );
+            assert_eq!(double_click_time(&data), Some(Duration::from_millis(400)));
+        }
+    }
+
+    #[test]
+    fn truncated_buffer() {
+        let data = encode(
+            false,
+            &[
+                ("Net/ThemeName", Value::String("Adwaita")),
+                ("Net/DoubleClickTime", Value::Integer(400)),
+            ],
+        );
+        for len in 0..data.len() {
+            assert_eq!(double_click_time(&data[..len]), None);
+        }
+    }
+
+    #[test]
+    fn missing_setting() {
+        let data = encode(true, &[("Net/DndDragThreshold", Value::Integer(8))]);
+        assert_eq!(double_click_time(&data), None);
+        assert_eq!(double_click_time(&encode(true, &[])), None);
+
+        // A negative time is as good as no time at all.
+        let data = encode(true, &[("Net/DoubleClickTime", Value::Integer(-1))]);
+        assert_eq!(double_click_time(&data), None);
+    }
+}
@@ -1,13 +1,17 @@
 use std::fmt;
 use std::fmt::Display;
+use std::io;
 
-use crate::Key;
+use crate::{Button, Key};
 
 /// An error that might occur when interacting with the Windows operating system.
 #[derive(Debug)]
 pub enum Error {
     Blocked,
+    CursorPosition(io::Error),
+    Monitors(io::Error),
     UnsupportedKey(Key),
+    UnsupportedButton(Button),
     UnsupportedChar(char),
 }
 
@@ -15,7 +19,10 @@ impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Blocked => f.write_str("the inputs were blocked by another thread"),
+            Self::CursorPosition(e) => write!(f, "failed to get the position of the cursor: {e}"),
+            Self::Monitors(e) => write!(f, "failed to enumerate the monitors: {e}"),
             Self::UnsupportedKey(k) => write!(f, "the key '{k:?}' is not supported"),
+            Self::UnsupportedButton(b) => write!(f, "the button '{b:?}' is not supported"),
             Self::UnsupportedChar(c) => write!(f, "the character {c:?} is not supported"),
         }
     }
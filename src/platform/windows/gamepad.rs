@@ -0,0 +1,23 @@
+use crate::{GamepadAxis, GamepadButton};
+
+/// A virtual gamepad on the Windows platform.
+///
+/// Gamepads are not supported on Windows, meaning that this type is uninhabited.
+pub enum Gamepad {}
+
+impl Gamepad {
+    /// Presses a button.
+    pub fn press(&self, _button: GamepadButton) -> Result<(), super::Error> {
+        match *self {}
+    }
+
+    /// Releases a button.
+    pub fn release(&self, _button: GamepadButton) -> Result<(), super::Error> {
+        match *self {}
+    }
+
+    /// Sets the value of an analog axis.
+    pub fn set_axis(&self, _axis: GamepadAxis, _value: i32) -> Result<(), super::Error> {
+        match *self {}
+    }
+}
@@ -6,5 +6,8 @@ pub use self::error::*;
 mod simulator;
 pub use self::simulator::*;
 
+mod gamepad;
+pub use self::gamepad::*;
+
 mod sys;
 mod utils;
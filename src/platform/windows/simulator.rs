@@ -1,62 +1,197 @@
-use crate::{Button, Key};
+use std::time::Duration;
+
+use crate::{
+    Backend, Button, Capabilities, Fidelity, GamepadOptions, Key, Screen, SimulatorBuilder,
+    StylusTool, UnicodeSupport,
+};
 
 use super::{sys, utils};
+use winapi::um::winuser;
+
+use crate::motion::SubpixelAccumulator;
 
 /// The simulator on the Windows platform.
 #[derive(Default)]
-pub struct Simulator;
+pub struct Simulator {
+    /// The fractional part of the relative motions that have not been sent yet.
+    motion: SubpixelAccumulator,
+    /// The fractional part of the smooth scrolls that have not been sent yet, in 120ths of a
+    /// wheel click.
+    scroll: SubpixelAccumulator,
+}
 
 impl Simulator {
     /// Creates a new [`Simulator`] instance.
     pub fn new() -> Result<Self, super::Error> {
-        Ok(Self)
+        Ok(Self::default())
+    }
+
+    /// Returns the backends worth trying, in order of preference.
+    pub fn default_backends() -> Vec<Backend> {
+        vec![Backend::Windows]
+    }
+
+    /// Creates a new [`Simulator`] instance using `backend`.
+    ///
+    /// Returns `None` if `backend` is not available on Windows.
+    pub fn with_backend(
+        backend: Backend,
+        _: &SimulatorBuilder,
+    ) -> Option<Result<Self, super::Error>> {
+        match backend {
+            Backend::Windows => Some(Self::new()),
+            _ => None,
+        }
+    }
+
+    /// Returns what the simulator is able to do.
+    pub fn capabilities(&self) -> Capabilities {
+        // `KEYEVENTF_UNICODE` only accepts UTF-16 code units.
+        Capabilities::new("windows", Fidelity::Native, 2, UnicodeSupport::Bmp)
     }
 
     /// Simulates a key press event.
     pub fn press_key(&self, key: Key) -> Result<(), super::Error> {
-        let vk = utils::key_to_vk(key).ok_or(super::Error::UnsupportedKey(key))?;
-        let input = utils::make_key_event(vk as _, true);
+        let input = utils::make_key_input(key, true).ok_or(super::Error::UnsupportedKey(key))?;
         sys::send_events(std::slice::from_ref(&input))
     }
 
     /// Simulates a key release event.
     pub fn release_key(&self, key: Key) -> Result<(), super::Error> {
-        let vk = utils::key_to_vk(key).ok_or(super::Error::UnsupportedKey(key))?;
-        let input = utils::make_key_event(vk as _, false);
+        let input = utils::make_key_input(key, false).ok_or(super::Error::UnsupportedKey(key))?;
         sys::send_events(std::slice::from_ref(&input))
     }
 
     /// Simulates a keystroke.
     pub fn send_key(&self, key: Key) -> Result<(), super::Error> {
-        let vk = utils::key_to_vk(key).ok_or(super::Error::UnsupportedKey(key))?;
         let inputs = [
-            utils::make_key_event(vk as _, true),
-            utils::make_key_event(vk as _, false),
+            utils::make_key_input(key, true).ok_or(super::Error::UnsupportedKey(key))?,
+            utils::make_key_input(key, false).ok_or(super::Error::UnsupportedKey(key))?,
         ];
         sys::send_events(&inputs)
     }
 
     /// Simulates a key press event.
     pub fn press_button(&self, button: Button) -> Result<(), super::Error> {
-        let input = utils::make_button_event(button, true);
+        let input = utils::make_button_event(button, true)
+            .ok_or(super::Error::UnsupportedButton(button))?;
         sys::send_events(std::slice::from_ref(&input))
     }
 
     /// Simulates a key release event.
     pub fn release_button(&self, button: Button) -> Result<(), super::Error> {
-        let input = utils::make_button_event(button, false);
+        let input = utils::make_button_event(button, false)
+            .ok_or(super::Error::UnsupportedButton(button))?;
         sys::send_events(std::slice::from_ref(&input))
     }
 
     /// Simulates a keystroke.
     pub fn send_button(&self, button: Button) -> Result<(), super::Error> {
-        let inputs = [
+        let (Some(press), Some(release)) = (
             utils::make_button_event(button, true),
             utils::make_button_event(button, false),
-        ];
+        ) else {
+            return Err(super::Error::UnsupportedButton(button));
+        };
+        let inputs = [press, release];
         sys::send_events(&inputs)
     }
 
+    /// Moves the mouse pointer to the position (`x`, `y`) of the virtual screen.
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), super::Error> {
+        let input = utils::make_move_event(x, y, sys::virtual_screen());
+        sys::send_events(std::slice::from_ref(&input))
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks.
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), super::Error> {
+        let click = winuser::WHEEL_DELTA as i32;
+        let inputs = utils::make_scroll_events(dx.saturating_mul(click), dy.saturating_mul(click));
+        sys::send_events(&inputs)
+    }
+
+    /// Scrolls by (`dx`, `dy`) wheel clicks, which may be fractional.
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let click = winuser::WHEEL_DELTA as f64;
+        let (dx, dy) = self.scroll.take(dx * click, dy * click);
+        sys::send_events(&utils::make_scroll_events(dx, dy))
+    }
+
+    /// Returns the position of the mouse pointer on the virtual screen.
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, super::Error> {
+        sys::cursor_position().map(Some)
+    }
+
+    /// Returns the monitors that make up the virtual screen.
+    pub fn screens(&self) -> Result<Vec<Screen>, super::Error> {
+        Ok(sys::monitors()?
+            .into_iter()
+            .filter(|&(_, _, width, height)| width > 0 && height > 0)
+            .map(|(x, y, width, height)| Screen::new(x, y, width as u32, height as u32))
+            .collect())
+    }
+
+    /// Touch inputs are not supported on Windows.
+    pub fn touch_down(&self, _slot: u32, _x: i32, _y: i32) -> Option<Result<(), super::Error>> {
+        None
+    }
+
+    /// Touch inputs are not supported on Windows.
+    pub fn touch_move(&self, _slot: u32, _x: i32, _y: i32) -> Option<Result<(), super::Error>> {
+        None
+    }
+
+    /// Touch inputs are not supported on Windows.
+    pub fn touch_up(&self, _slot: u32) -> Option<Result<(), super::Error>> {
+        None
+    }
+
+    /// Touchpad gestures are not supported on Windows.
+    pub fn touchpad_frame(&self, _contacts: &[(i32, i32)]) -> Option<Result<(), super::Error>> {
+        None
+    }
+
+    /// Stylus inputs are not supported on Windows.
+    pub fn stylus(
+        &self,
+        _tool: StylusTool,
+        _x: i32,
+        _y: i32,
+        _pressure: u16,
+        _tilt: (i8, i8),
+    ) -> Option<Result<(), super::Error>> {
+        None
+    }
+
+    /// Stylus inputs are not supported on Windows.
+    pub fn stylus_leave(&self) -> Option<Result<(), super::Error>> {
+        None
+    }
+
+    /// Gamepads are not supported on Windows.
+    pub fn create_gamepad(
+        &self,
+        _options: &GamepadOptions,
+    ) -> Option<Result<super::Gamepad, super::Error>> {
+        None
+    }
+
+    /// Returns the double-click time of the desktop.
+    pub fn double_click_time(&self) -> Option<Duration> {
+        Some(sys::double_click_time())
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) relative to its current position.
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), super::Error> {
+        let (dx, dy) = self.motion.take(dx, dy);
+        if dx == 0 && dy == 0 {
+            return Ok(());
+        }
+
+        let input = utils::make_relative_move_event(dx, dy);
+        sys::send_events(std::slice::from_ref(&input))
+    }
+
     /// Sends a specific unicode code-point.
     pub fn send_char(&self, c: char) -> Result<(), super::Error> {
         let inputs = [
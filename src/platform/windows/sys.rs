@@ -1,5 +1,8 @@
 //! This module wraps the Windows API behind safe function calls.
 
+use std::time::Duration;
+
+use winapi::shared::{minwindef, windef};
 use winapi::um::winuser;
 
 /// Sends the provided events.
@@ -28,3 +31,83 @@ pub fn send_events(events: &[winuser::INPUT]) -> Result<(), super::Error> {
 
     Ok(())
 }
+
+/// Returns the position of the mouse pointer on the virtual screen.
+pub fn cursor_position() -> Result<(i32, i32), super::Error> {
+    let mut point = windef::POINT { x: 0, y: 0 };
+
+    // Safety:
+    //  `point` is a valid `POINT` instance.
+    let status = unsafe { winuser::GetCursorPos(&mut point) };
+
+    if status == 0 {
+        Err(super::Error::CursorPosition(std::io::Error::last_os_error()))
+    } else {
+        Ok((point.x, point.y))
+    }
+}
+
+/// Returns the maximum time between the clicks of a double-click.
+#[inline]
+pub fn double_click_time() -> Duration {
+    // Safety:
+    //  `GetDoubleClickTime` has no safety requirements.
+    let millis = unsafe { winuser::GetDoubleClickTime() };
+    Duration::from_millis(millis as u64)
+}
+
+/// Returns the position and size of each monitor on the virtual screen.
+pub fn monitors() -> Result<Vec<(i32, i32, i32, i32)>, super::Error> {
+    unsafe extern "system" fn callback(
+        _: windef::HMONITOR,
+        _: windef::HDC,
+        rect: windef::LPRECT,
+        data: minwindef::LPARAM,
+    ) -> minwindef::BOOL {
+        // Safety:
+        //  `data` is the pointer to the vector passed to `EnumDisplayMonitors`, and `rect` is
+        //  provided by the system.
+        let monitors = unsafe { &mut *(data as *mut Vec<(i32, i32, i32, i32)>) };
+        let rect = unsafe { &*rect };
+        monitors.push((
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+        ));
+        minwindef::TRUE
+    }
+
+    let mut monitors = Vec::new();
+
+    // Safety:
+    //  The callback is only called during the call, while `monitors` is still borrowed.
+    let status = unsafe {
+        winuser::EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(callback),
+            &mut monitors as *mut Vec<_> as minwindef::LPARAM,
+        )
+    };
+
+    if status == 0 {
+        Err(super::Error::Monitors(std::io::Error::last_os_error()))
+    } else {
+        Ok(monitors)
+    }
+}
+
+/// Returns the position and size of the virtual screen, which covers all the monitors.
+pub fn virtual_screen() -> (i32, i32, i32, i32) {
+    // Safety:
+    //  `GetSystemMetrics` has no safety requirements.
+    unsafe {
+        (
+            winuser::GetSystemMetrics(winuser::SM_XVIRTUALSCREEN),
+            winuser::GetSystemMetrics(winuser::SM_YVIRTUALSCREEN),
+            winuser::GetSystemMetrics(winuser::SM_CXVIRTUALSCREEN),
+            winuser::GetSystemMetrics(winuser::SM_CYVIRTUALSCREEN),
+        )
+    }
+}
@@ -70,6 +70,17 @@ pub fn key_to_vk(key: Key) -> Option<c_int> {
         Key::Seven => Some(b'7' as c_int),
         Key::Eight => Some(b'8' as c_int),
         Key::Nine => Some(b'9' as c_int),
+        Key::Minus => Some(winuser::VK_OEM_MINUS),
+        Key::Equal => Some(winuser::VK_OEM_PLUS),
+        Key::BracketLeft => Some(winuser::VK_OEM_4),
+        Key::BracketRight => Some(winuser::VK_OEM_6),
+        Key::Semicolon => Some(winuser::VK_OEM_1),
+        Key::Quote => Some(winuser::VK_OEM_7),
+        Key::Comma => Some(winuser::VK_OEM_COMMA),
+        Key::Period => Some(winuser::VK_OEM_PERIOD),
+        Key::Slash => Some(winuser::VK_OEM_2),
+        Key::Backslash => Some(winuser::VK_OEM_5),
+        Key::Grave => Some(winuser::VK_OEM_3),
         Key::Escape => Some(winuser::VK_ESCAPE),
         Key::Tab => Some(winuser::VK_TAB),
         Key::CapsLock => Some(winuser::VK_CAPITAL),
@@ -84,6 +95,10 @@ pub fn key_to_vk(key: Key) -> Option<c_int> {
         Key::RightShift => Some(winuser::VK_RSHIFT),
         Key::Enter => Some(winuser::VK_RETURN),
         Key::Backspace => Some(winuser::VK_BACK),
+        Key::PrintScreen => Some(winuser::VK_SNAPSHOT),
+        Key::ScrollLock => Some(winuser::VK_SCROLL),
+        Key::Pause => Some(winuser::VK_PAUSE),
+        Key::ContextMenu => Some(winuser::VK_APPS),
         Key::Insert => Some(winuser::VK_INSERT),
         Key::Delete => Some(winuser::VK_DELETE),
         Key::Home => Some(winuser::VK_HOME),
@@ -96,8 +111,9 @@ pub fn key_to_vk(key: Key) -> Option<c_int> {
         Key::ArrowRight => Some(winuser::VK_RIGHT),
         Key::NumLock => Some(winuser::VK_NUMLOCK),
         Key::NumpadDivide => Some(winuser::VK_DIVIDE),
-        Key::NumpadEqual => None,
+        Key::NumpadEqual => Some(winuser::VK_OEM_NEC_EQUAL),
         Key::NumpadMultiply => Some(winuser::VK_MULTIPLY),
+        Key::NumpadSubtract => Some(winuser::VK_SUBTRACT),
         Key::NumpadAdd => Some(winuser::VK_ADD),
         Key::NumpadEnter => Some(winuser::VK_RETURN),
         Key::NumpadDecimal => Some(winuser::VK_DECIMAL),
@@ -118,6 +134,16 @@ pub fn key_to_vk(key: Key) -> Option<c_int> {
         Key::MediaPrevious => Some(winuser::VK_MEDIA_PREV_TRACK),
         Key::MediaStop => Some(winuser::VK_MEDIA_STOP),
         Key::MediaPause => Some(winuser::VK_MEDIA_PLAY_PAUSE),
+        Key::MediaPlay => Some(winuser::VK_MEDIA_PLAY_PAUSE),
+        Key::BrowserBack => Some(winuser::VK_BROWSER_BACK),
+        Key::BrowserForward => Some(winuser::VK_BROWSER_FORWARD),
+        Key::BrowserRefresh => Some(winuser::VK_BROWSER_REFRESH),
+        // Windows has no virtual-key code for the brightness keys.
+        Key::BrightnessUp | Key::BrightnessDown => None,
+        Key::LaunchMail => Some(winuser::VK_LAUNCH_MAIL),
+        // The second application key launches the calculator by default.
+        Key::LaunchCalculator => Some(winuser::VK_LAUNCH_APP2),
+        Key::Keysym(_) | Key::Scancode(_) => None,
     }
 }
 
@@ -146,6 +172,46 @@ pub fn make_key_event(vk: minwindef::WORD, press: bool) -> winuser::INPUT {
     ret
 }
 
+/// Creates a [`winuser::INPUT`] instance that simulates a key press/release, the key being
+/// identified by its scan code.
+///
+/// Returns `None` if `code` is not a scan code of the set 1.
+pub fn make_scancode_event(code: u32, press: bool) -> Option<winuser::INPUT> {
+    let extended = match code >> 8 {
+        0 => false,
+        0xe0 => true,
+        _ => return None,
+    };
+
+    let mut ret = zeroed_input();
+    ret.type_ = winuser::INPUT_KEYBOARD;
+
+    let kbd = unsafe { ret.u.ki_mut() };
+
+    kbd.wVk = 0;
+    kbd.wScan = (code & 0xff) as minwindef::WORD;
+    kbd.dwFlags = winuser::KEYEVENTF_SCANCODE;
+
+    if extended {
+        kbd.dwFlags |= winuser::KEYEVENTF_EXTENDEDKEY;
+    }
+    if !press {
+        kbd.dwFlags |= winuser::KEYEVENTF_KEYUP;
+    }
+
+    Some(ret)
+}
+
+/// Creates a [`winuser::INPUT`] instance that simulates `key` being pressed/released.
+///
+/// Returns `None` if the key is not supported.
+pub fn make_key_input(key: Key, press: bool) -> Option<winuser::INPUT> {
+    match key {
+        Key::Scancode(code) => make_scancode_event(code, press),
+        _ => key_to_vk(key).map(|vk| make_key_event(vk as _, press)),
+    }
+}
+
 /// Creates a [`winuser::INPUT`] instance that simulates a character being pressed/released.
 pub fn make_char_event(c: char, press: bool) -> Option<winuser::INPUT> {
     let mut ret = zeroed_input();
@@ -164,7 +230,9 @@ pub fn make_char_event(c: char, press: bool) -> Option<winuser::INPUT> {
 }
 
 /// Creates a [`winuser::INPUT`] instance that simulates a mouse button being pressed/released.
-pub fn make_button_event(button: Button, press: bool) -> winuser::INPUT {
+///
+/// Windows only supports two extra buttons, `XBUTTON1` and `XBUTTON2`.
+pub fn make_button_event(button: Button, press: bool) -> Option<winuser::INPUT> {
     let mut ret = zeroed_input();
     ret.type_ = winuser::INPUT_MOUSE;
 
@@ -177,15 +245,124 @@ pub fn make_button_event(button: Button, press: bool) -> winuser::INPUT {
         (Button::Right, true) => mouse.dwFlags = winuser::MOUSEEVENTF_RIGHTDOWN,
         (Button::Middle, false) => mouse.dwFlags = winuser::MOUSEEVENTF_MIDDLEUP,
         (Button::Middle, true) => mouse.dwFlags = winuser::MOUSEEVENTF_MIDDLEDOWN,
-        (Button::Extra(n), false) => {
-            mouse.mouseData = n as _;
-            mouse.dwFlags = winuser::MOUSEEVENTF_XUP
+        (Button::Extra(n), press) => {
+            mouse.mouseData = match n {
+                0 => winuser::XBUTTON1 as _,
+                1 => winuser::XBUTTON2 as _,
+                _ => return None,
+            };
+            mouse.dwFlags = match press {
+                true => winuser::MOUSEEVENTF_XDOWN,
+                false => winuser::MOUSEEVENTF_XUP,
+            };
         }
-        (Button::Extra(n), true) => {
-            mouse.mouseData = n as _;
-            mouse.dwFlags = winuser::MOUSEEVENTF_XDOWN
+    }
+
+    Some(ret)
+}
+
+/// Creates a [`winuser::INPUT`] instance that moves the mouse pointer to the position (`x`, `y`)
+/// of the virtual screen `(left, top, width, height)`.
+pub fn make_move_event(
+    x: i32,
+    y: i32,
+    (left, top, width, height): (i32, i32, i32, i32),
+) -> winuser::INPUT {
+    let mut ret = zeroed_input();
+    ret.type_ = winuser::INPUT_MOUSE;
+
+    let mouse = unsafe { ret.u.mi_mut() };
+
+    // Absolute coordinates are normalized to the range `0..=65535`.
+    let normalize = |v: i32, origin: i32, extent: i32| {
+        let extent = (extent as i64 - 1).max(1);
+        ((v as i64 - origin as i64) * 65535 / extent).clamp(0, 65535) as i32
+    };
+
+    mouse.dx = normalize(x, left, width);
+    mouse.dy = normalize(y, top, height);
+    mouse.dwFlags = winuser::MOUSEEVENTF_MOVE
+        | winuser::MOUSEEVENTF_ABSOLUTE
+        | winuser::MOUSEEVENTF_VIRTUALDESK;
+
+    ret
+}
+
+/// Creates a [`winuser::INPUT`] instance that moves the mouse pointer by (`dx`, `dy`) relative
+/// to its current position.
+///
+/// Relative motions are subject to the pointer speed and acceleration settings of the user.
+pub fn make_relative_move_event(dx: i32, dy: i32) -> winuser::INPUT {
+    let mut ret = zeroed_input();
+    ret.type_ = winuser::INPUT_MOUSE;
+
+    let mouse = unsafe { ret.u.mi_mut() };
+    mouse.dx = dx;
+    mouse.dy = dy;
+    mouse.dwFlags = winuser::MOUSEEVENTF_MOVE;
+
+    ret
+}
+
+/// Creates the [`winuser::INPUT`] instances that scroll by (`dx`, `dy`) 120ths of a wheel click.
+///
+/// Positive values scroll down and to the right.
+pub fn make_scroll_events(dx: i32, dy: i32) -> Vec<winuser::INPUT> {
+    let mut ret = Vec::with_capacity(2);
+
+    // `MOUSEEVENTF_WHEEL` scrolls up when its value is positive.
+    let wheels = [
+        (winuser::MOUSEEVENTF_WHEEL, dy.saturating_neg()),
+        (winuser::MOUSEEVENTF_HWHEEL, dx),
+    ];
+    for (flag, delta) in wheels {
+        if delta == 0 {
+            continue;
         }
+
+        let mut input = zeroed_input();
+        input.type_ = winuser::INPUT_MOUSE;
+
+        let mouse = unsafe { input.u.mi_mut() };
+        mouse.mouseData = delta as _;
+        mouse.dwFlags = flag;
+
+        ret.push(input);
     }
 
     ret
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::NAMED_KEYS;
+
+    /// The keys that have no virtual-key code, as documented on [`Key`].
+    const UNSUPPORTED_KEYS: &[Key] = &[Key::BrightnessUp, Key::BrightnessDown];
+
+    #[test]
+    fn every_named_key_has_a_virtual_key_code() {
+        for &key in NAMED_KEYS {
+            let vk = key_to_vk(key);
+            match UNSUPPORTED_KEYS.contains(&key) {
+                true => assert_eq!(vk, None, "{key:?} is documented as unsupported"),
+                false => assert!(vk.is_some(), "{key:?} has no virtual-key code"),
+            }
+        }
+    }
+
+    #[test]
+    fn keysyms_are_not_supported() {
+        assert_eq!(key_to_vk(Key::Keysym(0x61)), None);
+        assert!(make_key_input(Key::Keysym(0x61), true).is_none());
+    }
+
+    #[test]
+    fn scancodes_are_sent_as_scancodes() {
+        assert_eq!(key_to_vk(Key::Scancode(0x1e)), None);
+        assert!(make_key_input(Key::Scancode(0x1e), true).is_some());
+        assert!(make_key_input(Key::Scancode(0xe05d), false).is_some());
+        assert!(make_key_input(Key::Scancode(0x1234), true).is_none());
+    }
+}
@@ -0,0 +1,633 @@
+//! An in-memory backend that records the inputs it is asked to simulate, rather than sending
+//! them to the operating system.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::motion::SubpixelAccumulator;
+use crate::{
+    Button, Capabilities, Fidelity, GamepadAxis, GamepadButton, InputKinds, Key, Screen,
+    StylusTool, UnicodeSupport,
+};
+
+/// An input recorded by a [`Recorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordedInput {
+    /// A key was pressed.
+    PressKey(Key),
+    /// A key was released.
+    ReleaseKey(Key),
+    /// A mouse button was pressed.
+    PressButton(Button),
+    /// A mouse button was released.
+    ReleaseButton(Button),
+    /// A unicode code-point was typed.
+    Char(char),
+    /// The mouse pointer was moved to an absolute position.
+    MoveMouseTo {
+        /// The horizontal position of the pointer.
+        x: i32,
+        /// The vertical position of the pointer.
+        y: i32,
+    },
+    /// The mouse pointer was moved relative to its current position.
+    ///
+    /// Fractional motions are accumulated until they amount to a whole pixel, like most backends
+    /// do.
+    MoveMouseBy {
+        /// The horizontal motion of the pointer.
+        dx: i32,
+        /// The vertical motion of the pointer.
+        dy: i32,
+    },
+    /// The mouse wheel was scrolled.
+    Scroll {
+        /// The number of horizontal wheel clicks.
+        dx: i32,
+        /// The number of vertical wheel clicks.
+        dy: i32,
+    },
+    /// The mouse wheel was scrolled by fractions of a wheel click.
+    ///
+    /// The distances are expressed in 120ths of a wheel click, like the ones of high-resolution
+    /// wheels. Smaller fractions are accumulated until they amount to one.
+    SmoothScroll {
+        /// The horizontal distance.
+        dx: i32,
+        /// The vertical distance.
+        dy: i32,
+    },
+    /// A finger touched the screen.
+    TouchDown {
+        /// The slot identifying the finger.
+        slot: u32,
+        /// The horizontal position of the finger.
+        x: i32,
+        /// The vertical position of the finger.
+        y: i32,
+    },
+    /// A finger moved on the screen.
+    TouchMove {
+        /// The slot identifying the finger.
+        slot: u32,
+        /// The horizontal position of the finger.
+        x: i32,
+        /// The vertical position of the finger.
+        y: i32,
+    },
+    /// A finger was lifted from the screen.
+    TouchUp {
+        /// The slot identifying the finger.
+        slot: u32,
+    },
+    /// A finger touched the touchpad, as part of a [`Gesture`](crate::Gesture).
+    ///
+    /// The positions on the touchpad are expressed in hundredths of a millimetre, relative to
+    /// its center.
+    TouchpadDown {
+        /// The index of the finger.
+        slot: u32,
+        /// The horizontal position of the finger.
+        x: i32,
+        /// The vertical position of the finger.
+        y: i32,
+    },
+    /// A finger moved on the touchpad, as part of a [`Gesture`](crate::Gesture).
+    TouchpadMove {
+        /// The index of the finger.
+        slot: u32,
+        /// The horizontal position of the finger.
+        x: i32,
+        /// The vertical position of the finger.
+        y: i32,
+    },
+    /// A finger was lifted from the touchpad, as part of a [`Gesture`](crate::Gesture).
+    TouchpadUp {
+        /// The index of the finger.
+        slot: u32,
+    },
+    /// The stylus was moved, bringing it close to the surface if needed.
+    ///
+    /// The pressure and the tilt are rounded the way the `uinput` backend reports them.
+    Stylus {
+        /// The end of the stylus that is used.
+        tool: StylusTool,
+        /// The horizontal position of the stylus.
+        x: i32,
+        /// The vertical position of the stylus.
+        y: i32,
+        /// How hard the stylus is pressed against the surface, from `0` to `4095`.
+        pressure: u16,
+        /// The horizontal tilt of the stylus, in degrees.
+        tilt_x: i8,
+        /// The vertical tilt of the stylus, in degrees.
+        tilt_y: i8,
+    },
+    /// The stylus was moved away from the surface.
+    StylusLeave,
+    /// A gamepad button was pressed.
+    PressGamepadButton(GamepadButton),
+    /// A gamepad button was released.
+    ReleaseGamepadButton(GamepadButton),
+    /// An analog axis of a gamepad was moved.
+    ///
+    /// The value is expressed in the range of the axis on an Xbox controller: from `-32767` to
+    /// `32767` for sticks, and from `0` to `255` for triggers.
+    GamepadAxis {
+        /// The axis that was moved.
+        axis: GamepadAxis,
+        /// The new value of the axis.
+        value: i32,
+    },
+}
+
+/// An event recorded by a [`Recorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordedEvent {
+    /// The time elapsed between the creation of the [`Recorder`] and the moment the input was
+    /// simulated.
+    pub time: Duration,
+    /// The input that was simulated.
+    pub input: RecordedInput,
+}
+
+/// An error returned by a [`Simulator`](crate::Simulator) created with
+/// [`Simulator::recording`](crate::Simulator::recording).
+///
+/// Those errors are only ever returned when the [`Recorder`] has been configured to fail
+/// specific inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordingError {
+    /// The key is not supported.
+    UnsupportedKey(Key),
+    /// The character is not supported.
+    UnsupportedChar(char),
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedKey(k) => write!(f, "the key '{k:?}' is not supported"),
+            Self::UnsupportedChar(c) => write!(f, "the character {c:?} is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+/// The state shared by all the clones of a [`Recorder`].
+#[derive(Default)]
+struct State {
+    /// The events recorded so far.
+    events: Vec<RecordedEvent>,
+    /// The keys that must be rejected with [`RecordingError::UnsupportedKey`].
+    failing_keys: HashSet<Key>,
+    /// The characters that must be rejected with [`RecordingError::UnsupportedChar`].
+    failing_chars: HashSet<char>,
+    /// The fractional part of the relative motions that have not been recorded yet.
+    motion: SubpixelAccumulator,
+    /// The fractional part of the smooth scrolls that have not been recorded yet.
+    scroll: SubpixelAccumulator,
+    /// The position of the pointer, if it is known.
+    position: Option<(i32, i32)>,
+    /// The monitors reported by the simulator.
+    screens: Vec<Screen>,
+    /// The number of fingers touching the touchpad.
+    touchpad_fingers: usize,
+}
+
+/// Records the inputs simulated by a [`Simulator`](crate::Simulator).
+///
+/// This type is a cheap handle: all its clones refer to the same log of events. Keep one around
+/// to inspect the events recorded by the simulator created with
+/// [`Simulator::recording`](crate::Simulator::recording).
+#[derive(Clone)]
+pub struct Recorder {
+    state: Arc<Mutex<State>>,
+    /// The instant at which the recorder was created. Event timestamps are relative to it.
+    epoch: Instant,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("events", &self.state().events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    /// Creates a new [`Recorder`] with an empty log.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::default(),
+            epoch: Instant::now(),
+        }
+    }
+
+    /// Locks the shared state of the recorder.
+    fn state(&self) -> MutexGuard<'_, State> {
+        // The state is never left inconsistent, even when a thread panics while holding the lock.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Makes the simulator fail with [`RecordingError::UnsupportedKey`] whenever `key` is used.
+    pub fn fail_key(&self, key: Key) {
+        self.state().failing_keys.insert(key);
+    }
+
+    /// Makes the simulator fail with [`RecordingError::UnsupportedChar`] whenever `c` is typed.
+    pub fn fail_char(&self, c: char) {
+        self.state().failing_chars.insert(c);
+    }
+
+    /// Sets the monitors reported by [`Simulator::screens`](crate::Simulator::screens).
+    ///
+    /// No monitor is reported by default.
+    pub fn set_screens(&self, screens: impl IntoIterator<Item = Screen>) {
+        self.state().screens = screens.into_iter().collect();
+    }
+
+    /// Returns the events recorded so far, in the order they were simulated.
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        self.state().events.clone()
+    }
+
+    /// Returns the events recorded so far, and clears the log.
+    pub fn take_events(&self) -> Vec<RecordedEvent> {
+        std::mem::take(&mut self.state().events)
+    }
+
+    /// Appends `input` to the log.
+    fn record(&self, input: RecordedInput) {
+        let time = self.epoch.elapsed();
+        self.state().events.push(RecordedEvent { time, input });
+    }
+
+    /// Returns an error if `key` has been configured to fail.
+    fn check_key(&self, key: Key) -> Result<(), RecordingError> {
+        match self.state().failing_keys.contains(&key) {
+            true => Err(RecordingError::UnsupportedKey(key)),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the capabilities of a recording simulator.
+    ///
+    /// Every input can be recorded, even though some of them may be configured to fail.
+    pub(crate) fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            backend: "recording",
+            fidelity: Fidelity::Recorded,
+            inputs: InputKinds {
+                keys: true,
+                buttons: true,
+                chars: true,
+                pointer_motion: true,
+                scroll: true,
+                smooth_scroll: true,
+                touch: true,
+                gestures: true,
+                stylus: true,
+                gamepad: true,
+            },
+            max_extra_buttons: u8::MAX,
+            unicode: UnicodeSupport::Full,
+        }
+    }
+
+    /// Records a key press event.
+    pub(crate) fn press_key(&self, key: Key) -> Result<(), RecordingError> {
+        self.check_key(key)?;
+        self.record(RecordedInput::PressKey(key));
+        Ok(())
+    }
+
+    /// Records a key release event.
+    pub(crate) fn release_key(&self, key: Key) -> Result<(), RecordingError> {
+        self.check_key(key)?;
+        self.record(RecordedInput::ReleaseKey(key));
+        Ok(())
+    }
+
+    /// Records a keystroke.
+    pub(crate) fn send_key(&self, key: Key) -> Result<(), RecordingError> {
+        self.check_key(key)?;
+        self.record(RecordedInput::PressKey(key));
+        self.record(RecordedInput::ReleaseKey(key));
+        Ok(())
+    }
+
+    /// Records a button press event.
+    pub(crate) fn press_button(&self, button: Button) -> Result<(), RecordingError> {
+        self.record(RecordedInput::PressButton(button));
+        Ok(())
+    }
+
+    /// Records a button release event.
+    pub(crate) fn release_button(&self, button: Button) -> Result<(), RecordingError> {
+        self.record(RecordedInput::ReleaseButton(button));
+        Ok(())
+    }
+
+    /// Records a button click.
+    pub(crate) fn send_button(&self, button: Button) -> Result<(), RecordingError> {
+        self.record(RecordedInput::PressButton(button));
+        self.record(RecordedInput::ReleaseButton(button));
+        Ok(())
+    }
+
+    /// Records an absolute motion of the mouse pointer.
+    pub(crate) fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), RecordingError> {
+        self.record(RecordedInput::MoveMouseTo { x, y });
+        self.state().position = Some((x, y));
+        Ok(())
+    }
+
+    /// Records wheel clicks.
+    pub(crate) fn scroll(&self, dx: i32, dy: i32) -> Result<(), RecordingError> {
+        if dx != 0 || dy != 0 {
+            self.record(RecordedInput::Scroll { dx, dy });
+        }
+        Ok(())
+    }
+
+    /// Records a smooth scroll.
+    pub(crate) fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), RecordingError> {
+        let (dx, dy) = self.state().scroll.take(dx * 120.0, dy * 120.0);
+        if dx != 0 || dy != 0 {
+            self.record(RecordedInput::SmoothScroll { dx, dy });
+        }
+        Ok(())
+    }
+
+    /// Records a relative motion of the mouse pointer.
+    pub(crate) fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), RecordingError> {
+        let (dx, dy) = self.state().motion.take(dx, dy);
+        if dx != 0 || dy != 0 {
+            self.record(RecordedInput::MoveMouseBy { dx, dy });
+        }
+
+        let mut state = self.state();
+        if let Some((x, y)) = &mut state.position {
+            *x = x.saturating_add(dx);
+            *y = y.saturating_add(dy);
+        }
+        Ok(())
+    }
+
+    /// Returns the position of the pointer.
+    ///
+    /// The position is only known once the pointer has been moved to an absolute position.
+    pub(crate) fn mouse_position(&self) -> Result<Option<(i32, i32)>, RecordingError> {
+        Ok(self.state().position)
+    }
+
+    /// Returns the monitors configured with [`set_screens`](Self::set_screens).
+    pub(crate) fn screens(&self) -> Result<Vec<Screen>, RecordingError> {
+        Ok(self.state().screens.clone())
+    }
+
+    /// Records a finger touching the screen.
+    pub(crate) fn touch_down(&self, slot: u32, x: i32, y: i32) -> Result<(), RecordingError> {
+        self.record(RecordedInput::TouchDown { slot, x, y });
+        Ok(())
+    }
+
+    /// Records a finger moving on the screen.
+    pub(crate) fn touch_move(&self, slot: u32, x: i32, y: i32) -> Result<(), RecordingError> {
+        self.record(RecordedInput::TouchMove { slot, x, y });
+        Ok(())
+    }
+
+    /// Records a finger being lifted from the screen.
+    pub(crate) fn touch_up(&self, slot: u32) -> Result<(), RecordingError> {
+        self.record(RecordedInput::TouchUp { slot });
+        Ok(())
+    }
+
+    /// Records the fingers touching the touchpad, lifting the ones that are not part of
+    /// `contacts` anymore.
+    pub(crate) fn touchpad_frame(&self, contacts: &[(i32, i32)]) -> Result<(), RecordingError> {
+        let previous = std::mem::replace(&mut self.state().touchpad_fingers, contacts.len());
+
+        for (slot, &(x, y)) in (0..).zip(contacts) {
+            match (slot as usize) < previous {
+                true => self.record(RecordedInput::TouchpadMove { slot, x, y }),
+                false => self.record(RecordedInput::TouchpadDown { slot, x, y }),
+            }
+        }
+        for slot in contacts.len()..previous {
+            self.record(RecordedInput::TouchpadUp { slot: slot as u32 });
+        }
+
+        Ok(())
+    }
+
+    /// Records a stylus sample.
+    pub(crate) fn stylus(
+        &self,
+        tool: StylusTool,
+        x: i32,
+        y: i32,
+        pressure: u16,
+        (tilt_x, tilt_y): (i8, i8),
+    ) -> Result<(), RecordingError> {
+        self.record(RecordedInput::Stylus {
+            tool,
+            x,
+            y,
+            pressure,
+            tilt_x,
+            tilt_y,
+        });
+        Ok(())
+    }
+
+    /// Records the stylus moving away from the surface.
+    pub(crate) fn stylus_leave(&self) -> Result<(), RecordingError> {
+        self.record(RecordedInput::StylusLeave);
+        Ok(())
+    }
+
+    /// Records a gamepad button being pressed.
+    pub(crate) fn press_gamepad_button(&self, button: GamepadButton) -> Result<(), RecordingError> {
+        self.record(RecordedInput::PressGamepadButton(button));
+        Ok(())
+    }
+
+    /// Records a gamepad button being released.
+    pub(crate) fn release_gamepad_button(
+        &self,
+        button: GamepadButton,
+    ) -> Result<(), RecordingError> {
+        self.record(RecordedInput::ReleaseGamepadButton(button));
+        Ok(())
+    }
+
+    /// Records an analog axis of a gamepad being moved.
+    pub(crate) fn gamepad_axis(&self, axis: GamepadAxis, value: i32) -> Result<(), RecordingError> {
+        self.record(RecordedInput::GamepadAxis { axis, value });
+        Ok(())
+    }
+
+    /// Records a unicode code-point.
+    pub(crate) fn send_char(&self, c: char) -> Result<(), RecordingError> {
+        if self.state().failing_chars.contains(&c) {
+            return Err(RecordingError::UnsupportedChar(c));
+        }
+
+        self.record(RecordedInput::Char(c));
+        Ok(())
+    }
+
+    /// Records a collection of characters.
+    ///
+    /// The characters preceding the first one that fails are recorded.
+    pub(crate) fn send_chars(
+        &self,
+        mut it: impl Iterator<Item = char>,
+    ) -> Result<(), RecordingError> {
+        it.try_for_each(|c| self.send_char(c))
+    }
+
+    /// Records a string.
+    pub(crate) fn send_str(&self, s: &str) -> Result<(), RecordingError> {
+        self.send_chars(s.chars())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Simulator;
+
+    /// Returns the inputs recorded by `recorder`, without their timestamps.
+    fn inputs(recorder: &Recorder) -> Vec<RecordedInput> {
+        recorder.events().into_iter().map(|e| e.input).collect()
+    }
+
+    #[test]
+    fn events_are_recorded_in_order() {
+        let recorder = Recorder::new();
+        let sim = Simulator::recording(&recorder);
+        sim.send_key(Key::A).unwrap();
+        sim.press_button(Button::Left).unwrap();
+        sim.move_mouse_to(10, 20).unwrap();
+        sim.send_str("hi").unwrap();
+
+        assert_eq!(
+            inputs(&recorder),
+            [
+                RecordedInput::PressKey(Key::A),
+                RecordedInput::ReleaseKey(Key::A),
+                RecordedInput::PressButton(Button::Left),
+                RecordedInput::MoveMouseTo { x: 10, y: 20 },
+                RecordedInput::Char('h'),
+                RecordedInput::Char('i'),
+            ]
+        );
+
+        let events = recorder.events();
+        assert!(events.windows(2).all(|w| w[0].time <= w[1].time));
+    }
+
+    #[test]
+    fn take_events_clears_the_log() {
+        let recorder = Recorder::new();
+        let sim = Simulator::recording(&recorder);
+        sim.send_key(Key::A).unwrap();
+
+        assert_eq!(recorder.take_events().len(), 2);
+        assert!(recorder.events().is_empty());
+        assert!(recorder.take_events().is_empty());
+
+        sim.send_key(Key::B).unwrap();
+        assert_eq!(
+            inputs(&recorder),
+            [
+                RecordedInput::PressKey(Key::B),
+                RecordedInput::ReleaseKey(Key::B),
+            ]
+        );
+    }
+
+    #[test]
+    fn clones_share_the_log() {
+        let recorder = Recorder::new();
+        let sim = Simulator::recording(&recorder.clone());
+        sim.send_key(Key::A).unwrap();
+        assert_eq!(recorder.events().len(), 2);
+    }
+
+    #[test]
+    fn failing_keys_are_not_recorded() {
+        let recorder = Recorder::new();
+        recorder.fail_key(Key::B);
+        let sim = Simulator::recording(&recorder);
+
+        let err = sim.send_key(Key::B).unwrap_err();
+        assert_eq!(err.to_string(), "the key 'B' is not supported");
+        assert!(sim.press_key(Key::B).is_err());
+        assert!(sim.release_key(Key::B).is_err());
+        assert!(recorder.events().is_empty());
+
+        sim.send_key(Key::A).unwrap();
+        assert_eq!(recorder.events().len(), 2);
+    }
+
+    #[test]
+    fn failing_chars_stop_the_string() {
+        let recorder = Recorder::new();
+        recorder.fail_char('é');
+        let sim = Simulator::recording(&recorder);
+
+        let err = sim.send_str("caé!").unwrap_err();
+        assert_eq!(err.to_string(), "the character 'é' is not supported");
+        assert_eq!(
+            inputs(&recorder),
+            [RecordedInput::Char('c'), RecordedInput::Char('a')]
+        );
+        assert!(sim.send_char('é').is_err());
+        assert_eq!(recorder.events().len(), 2);
+    }
+
+    #[test]
+    fn screens_are_reported() {
+        let recorder = Recorder::new();
+        let sim = Simulator::recording(&recorder);
+        assert!(sim.screens().unwrap().is_empty());
+
+        let screens = [
+            Screen::new(0, 0, 1920, 1080),
+            Screen::new(1920, 0, 1280, 1024),
+        ];
+        recorder.set_screens(screens);
+        assert_eq!(sim.screens().unwrap(), screens);
+
+        recorder.set_screens([]);
+        assert!(sim.screens().unwrap().is_empty());
+    }
+
+    #[test]
+    fn fractional_motions_are_accumulated() {
+        let recorder = Recorder::new();
+        let sim = Simulator::recording(&recorder);
+        sim.move_mouse_by(0.5, 0.0).unwrap();
+        assert!(recorder.events().is_empty());
+        sim.move_mouse_by(0.5, -1.0).unwrap();
+        assert_eq!(
+            inputs(&recorder),
+            [RecordedInput::MoveMouseBy { dx: 1, dy: -1 }]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Describes the monitors that make up the desktop.
+
+/// A monitor, as an area of the desktop.
+///
+/// The positions are expressed in the coordinate space used by
+/// [`Simulator::move_mouse_to`](crate::Simulator::move_mouse_to) and
+/// [`Simulator::mouse_position`](crate::Simulator::mouse_position), in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Screen {
+    /// The horizontal position of the left edge of the monitor.
+    pub x: i32,
+    /// The vertical position of the top edge of the monitor.
+    pub y: i32,
+    /// The width of the monitor.
+    pub width: u32,
+    /// The height of the monitor.
+    pub height: u32,
+}
+
+impl Screen {
+    /// Creates a new [`Screen`] covering the area of size (`width`, `height`) whose top-left
+    /// corner is at the position (`x`, `y`).
+    pub const fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns whether the position (`x`, `y`) falls within the monitor.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        let dx = x as i64 - self.x as i64;
+        let dy = y as i64 - self.y as i64;
+        (0..self.width as i64).contains(&dx) && (0..self.height as i64).contains(&dy)
+    }
+}
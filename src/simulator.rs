@@ -1,72 +1,658 @@
-use crate::{Button, Key};
+use std::time::{Duration, Instant};
+
+use crate::error::ErrorKind;
+use crate::glide::max_steps;
+use crate::{
+    Button, ClickOptions, DragOptions, Gamepad, GamepadOptions, Gesture, Key, MotionProfile,
+    Screen, StylusSample, StylusTool,
+};
+
+/// The time a finger stays on the screen when tapping it.
+const TAP_DURATION: Duration = Duration::from_millis(50);
+/// The approximate time between the motion events of a swipe.
+const SWIPE_INTERVAL: Duration = Duration::from_millis(16);
 
 /// Stores the state required to simulate inputs.
 ///
 /// On most platforms, this type will be a zero-sized type, but if the current platform requires
 /// some kind of state when simulating keypresses, this type is responsible for managing that
 /// state.
-pub struct Simulator(crate::platform::Simulator);
+pub struct Simulator(Inner);
+
+/// The implementation used by a [`Simulator`].
+enum Inner {
+    /// The platform simulator is boxed because it is quite large, and would otherwise bloat the
+    /// recording one.
+    Platform(Box<crate::platform::Simulator>),
+    #[cfg(feature = "mock")]
+    Recording(crate::Recorder),
+}
 
 impl Simulator {
     /// Creates a new [`Simulator`] instance.
+    ///
+    /// This is equivalent to building a [`SimulatorBuilder`](crate::SimulatorBuilder) with its
+    /// default configuration.
     #[inline]
     pub fn new() -> Result<Self, crate::Error> {
-        crate::platform::Simulator::new()
-            .map(Self)
-            .map_err(crate::Error)
+        crate::SimulatorBuilder::new().build()
+    }
+
+    /// Creates a new [`Simulator`] instance connected to the X display named `display`, such as
+    /// `":3"`, rather than the one referred to by the `DISPLAY` environment variable.
+    ///
+    /// Only the X11 backends are tried. Use [`SimulatorBuilder`](crate::SimulatorBuilder) to
+    /// select the credentials used to connect to the X server.
+    #[cfg(all(target_os = "linux", any(feature = "x11", feature = "x11rb")))]
+    pub fn with_x11_display(display: &str) -> Result<Self, crate::Error> {
+        crate::SimulatorBuilder::new()
+            .backends([crate::Backend::X11, crate::Backend::X11rb])
+            .env_override(false)
+            .x11_display(display)
+            .build()
+    }
+
+    /// Wraps a platform simulator.
+    #[inline]
+    pub(crate) fn from_platform(sim: crate::platform::Simulator) -> Self {
+        Self(Inner::Platform(Box::new(sim)))
+    }
+
+    /// Creates a new [`Simulator`] instance that records the inputs it is asked to simulate into
+    /// `recorder`, rather than sending them to the operating system.
+    ///
+    /// This is mostly useful to unit-test code that uses a [`Simulator`] without a live display
+    /// server.
+    #[cfg(feature = "mock")]
+    #[inline]
+    pub fn recording(recorder: &crate::Recorder) -> Self {
+        Self(Inner::Recording(recorder.clone()))
+    }
+
+    /// Creates a new [`Simulator`] instance that writes raw Linux `input_event` structures to
+    /// `file`, rather than to a freshly created `uinput` device.
+    ///
+    /// No `ioctl` request is issued on the file. This is mostly useful to inspect the events that
+    /// would be sent to a `uinput` device, for example by passing one end of a pipe.
+    #[cfg(all(target_os = "linux", feature = "uinput"))]
+    #[inline]
+    pub fn with_uinput_file(file: std::fs::File) -> Self {
+        Self::from_platform(crate::platform::Simulator::new_uinput_file(file))
+    }
+
+    /// Returns what the simulator is able to do.
+    #[inline]
+    pub fn capabilities(&self) -> crate::Capabilities {
+        match &self.0 {
+            Inner::Platform(sim) => sim.capabilities(),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.capabilities(),
+        }
     }
 
     /// Sends a fake key press event to the top-level window.
     #[inline]
     pub fn press_key(&self, key: Key) -> Result<(), crate::Error> {
-        self.0.press_key(key).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.press_key(key).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.press_key(key).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a fake key release event to the top-level window.
     #[inline]
     pub fn release_key(&self, key: Key) -> Result<(), crate::Error> {
-        self.0.release_key(key).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.release_key(key).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.release_key(key).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a fake keystroke event to the top-level window.
     #[inline]
     pub fn send_key(&self, key: Key) -> Result<(), crate::Error> {
-        self.0.send_key(key).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.send_key(key).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.send_key(key).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a fake button press event to the top-level window.
     #[inline]
     pub fn press_button(&self, button: Button) -> Result<(), crate::Error> {
-        self.0.press_button(button).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.press_button(button).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.press_button(button).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a fake button release event to the top-level window.
     #[inline]
     pub fn release_button(&self, button: Button) -> Result<(), crate::Error> {
-        self.0.release_button(button).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.release_button(button).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.release_button(button).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a fake button click event to the top-level window.
     #[inline]
     pub fn send_button(&self, button: Button) -> Result<(), crate::Error> {
-        self.0.send_button(button).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.send_button(button).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.send_button(button).map_err(crate::Error::from),
+        }
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`), in pixels.
+    ///
+    /// The position is relative to the top-left corner of the desktop. When multiple monitors are
+    /// used, it covers all of them, except with the X11 backends, which move the pointer within
+    /// the screen selected by the name of the display (`:0.1` selects the screen `1`).
+    ///
+    /// The `uinput` backend needs to know the size of the desktop, which can be provided with
+    /// [`SimulatorBuilder::uinput_screen_size`](crate::SimulatorBuilder::uinput_screen_size).
+    /// The portal backend can only reach the monitors shared through the screen cast portal.
+    #[inline]
+    pub fn move_mouse_to(&self, x: i32, y: i32) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.move_mouse_to(x, y).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.move_mouse_to(x, y).map_err(crate::Error::from),
+        }
+    }
+
+    /// Moves the mouse pointer to the position (`x`, `y`) progressively, following a path
+    /// described by `profile`.
+    ///
+    /// Intermediate motion events are sent at the rate of the profile, and this function blocks
+    /// until the pointer reaches its target. The positions are the same as the ones of
+    /// [`move_mouse_to`](Self::move_mouse_to).
+    ///
+    /// The path starts at the current position of the pointer. The Wayland, portal and `uinput`
+    /// backends cannot query it, and use the position the pointer was last moved to by
+    /// [`move_mouse_to`](Self::move_mouse_to) or by a previous glide instead. An error is
+    /// returned if that position is not known.
+    pub fn glide_mouse_to(
+        &self,
+        x: i32,
+        y: i32,
+        profile: &MotionProfile,
+    ) -> Result<(), crate::Error> {
+        let from = self
+            .mouse_position()?
+            .ok_or(crate::Error(ErrorKind::UnknownPosition))?;
+
+        let points = profile.plan(from, (x, y));
+        let interval = profile.interval(points.len());
+        let start = Instant::now();
+
+        let mut last = from;
+        for (i, point) in (1u32..).zip(points) {
+            // The deadlines are computed from the start to avoid accumulating delays.
+            let deadline = start + interval * i;
+            if let Some(delay) = deadline.checked_duration_since(Instant::now()) {
+                std::thread::sleep(delay);
+            }
+
+            if point != last {
+                self.move_mouse_to(point.0, point.1)?;
+                last = point;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drags the mouse pointer from `from` to `to` while holding `button`.
+    ///
+    /// The pointer is moved to `from`, then the button is pressed and the pointer moves to `to`
+    /// in several steps, as described by `options`, before the button is released. This function
+    /// blocks until the button is released.
+    ///
+    /// Once pressed, the button is released even if one of the steps fails, in which case the
+    /// error of that step is returned.
+    pub fn drag(
+        &self,
+        button: Button,
+        from: (i32, i32),
+        to: (i32, i32),
+        options: &DragOptions,
+    ) -> Result<(), crate::Error> {
+        self.move_mouse_to(from.0, from.1)?;
+        self.press_button(button)?;
+
+        let result = self.drag_motion(from, to, options);
+        let released = self.release_button(button);
+        result.and(released)
+    }
+
+    /// Moves the mouse pointer while a button is held, as part of [`drag`](Self::drag).
+    fn drag_motion(
+        &self,
+        from: (i32, i32),
+        to: (i32, i32),
+        options: &DragOptions,
+    ) -> Result<(), crate::Error> {
+        let (interval, points) = options.plan(from, to);
+
+        std::thread::sleep(options.hold);
+        for (x, y) in points {
+            std::thread::sleep(interval);
+            self.move_mouse_to(x, y)?;
+        }
+        std::thread::sleep(options.hold);
+
+        Ok(())
+    }
+
+    /// Puts a finger on the touchscreen at the position (`x`, `y`).
+    ///
+    /// `slot` identifies the finger in the following calls to [`touch_move`](Self::touch_move)
+    /// and [`touch_up`](Self::touch_up), so that several fingers can touch the screen at once.
+    /// Putting down a finger whose slot is already touching the screen ends its previous contact.
+    /// The positions are the same as the ones of [`move_mouse_to`](Self::move_mouse_to).
+    ///
+    /// Only the `uinput` backend supports touch inputs, through a virtual multi-touch screen
+    /// that tracks up to 10 fingers. It requires the size of the desktop to be known, like
+    /// absolute pointer motion. X servers cannot simulate touch events through the "XTEST"
+    /// extension, and the other backends return an error (see
+    /// [`InputKinds::touch`](crate::InputKinds::touch)).
+    #[inline]
+    pub fn touch_down(&self, slot: u32, x: i32, y: i32) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => unsupported(sim.touch_down(slot, x, y), "touch inputs"),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.touch_down(slot, x, y).map_err(crate::Error::from),
+        }
+    }
+
+    /// Moves the finger identified by `slot` to the position (`x`, `y`).
+    ///
+    /// See [`touch_down`](Self::touch_down).
+    #[inline]
+    pub fn touch_move(&self, slot: u32, x: i32, y: i32) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => unsupported(sim.touch_move(slot, x, y), "touch inputs"),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.touch_move(slot, x, y).map_err(crate::Error::from),
+        }
+    }
+
+    /// Lifts the finger identified by `slot` from the touchscreen.
+    ///
+    /// See [`touch_down`](Self::touch_down).
+    #[inline]
+    pub fn touch_up(&self, slot: u32) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => unsupported(sim.touch_up(slot), "touch inputs"),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.touch_up(slot).map_err(crate::Error::from),
+        }
+    }
+
+    /// Taps the touchscreen at the position (`x`, `y`) with a single finger.
+    ///
+    /// The finger uses the slot `0`, and stays on the screen for 50 milliseconds.
+    pub fn tap(&self, x: i32, y: i32) -> Result<(), crate::Error> {
+        self.touch_down(0, x, y)?;
+        std::thread::sleep(TAP_DURATION);
+        self.touch_up(0)
+    }
+
+    /// Swipes a single finger on the touchscreen from `from` to `to`.
+    ///
+    /// The finger uses the slot `0`, and moves in steps of about 16 milliseconds until it
+    /// reaches its target after `duration`, where it is lifted immediately. This function blocks
+    /// until the finger is lifted.
+    ///
+    /// Once down, the finger is lifted even if one of the steps fails, in which case the error
+    /// of that step is returned.
+    pub fn swipe(
+        &self,
+        from: (i32, i32),
+        to: (i32, i32),
+        duration: Duration,
+    ) -> Result<(), crate::Error> {
+        let steps = duration.as_millis() / SWIPE_INTERVAL.as_millis();
+        let options = DragOptions::new()
+            .hold(Duration::ZERO)
+            .steps(steps.min(max_steps(duration) as u128) as u32)
+            .duration(duration);
+        let (interval, points) = options.plan(from, to);
+
+        self.touch_down(0, from.0, from.1)?;
+
+        let result = points.into_iter().try_for_each(|(x, y)| {
+            std::thread::sleep(interval);
+            self.touch_move(0, x, y)
+        });
+        let lifted = self.touch_up(0);
+        result.and(lifted)
+    }
+
+    /// Performs a multi-finger gesture on the touchpad, such as a pinch, a rotation or a swipe.
+    ///
+    /// The fingers are put down, moved in `steps` motion events spread over the duration of the
+    /// gesture, then lifted. This function blocks until the fingers are lifted. Once down, the
+    /// fingers are lifted even if one of the motion events fails, in which case the error of that
+    /// event is returned.
+    ///
+    /// Only the `uinput` backend supports gestures, through a virtual touchpad of 100 by 70
+    /// millimetres. The compositor interprets the motion of the fingers, usually through
+    /// `libinput`, which may require some movement before recognizing a gesture. The other
+    /// backends return an error (see [`InputKinds::gestures`](crate::InputKinds::gestures)).
+    pub fn gesture(&self, gesture: &Gesture) -> Result<(), crate::Error> {
+        let (interval, frames) = gesture.plan();
+        let mut frames = frames.iter();
+
+        if let Some(first) = frames.next() {
+            self.touchpad_frame(first)?;
+        }
+
+        let result = frames.try_for_each(|contacts| {
+            std::thread::sleep(interval);
+            self.touchpad_frame(contacts)
+        });
+        let lifted = self.touchpad_frame(&[]);
+        result.and(lifted)
+    }
+
+    /// Puts the fingers of a gesture on the touchpad, lifting the other ones.
+    fn touchpad_frame(&self, contacts: &[(i32, i32)]) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => unsupported(sim.touchpad_frame(contacts), "touchpad gestures"),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.touchpad_frame(contacts).map_err(crate::Error::from),
+        }
+    }
+
+    /// Moves the stylus to the position, pressure and tilt of `sample`.
+    ///
+    /// `tool` is brought close to the surface if it was not already. If the other end of the
+    /// stylus was close to the surface, it is moved away first, which is how switching to the
+    /// eraser works on real tablets. The stylus touches the surface whenever the pressure of the
+    /// sample is not null, and stays close to the surface until
+    /// [`stylus_leave`](Self::stylus_leave) is called. The time of the sample is ignored.
+    ///
+    /// Only the `uinput` backend supports stylus inputs, through a virtual pen tablet mapped to
+    /// the whole desktop. It requires the size of the desktop to be known, like absolute pointer
+    /// motion. The other backends return an error (see
+    /// [`InputKinds::stylus`](crate::InputKinds::stylus)).
+    #[inline]
+    pub fn stylus(&self, tool: StylusTool, sample: &StylusSample) -> Result<(), crate::Error> {
+        let (x, y) = (sample.x, sample.y);
+        let pressure = sample.pressure_level();
+        let tilt = sample.tilt_degrees();
+
+        match &self.0 {
+            Inner::Platform(sim) => {
+                unsupported(sim.stylus(tool, x, y, pressure, tilt), "stylus inputs")
+            }
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec
+                .stylus(tool, x, y, pressure, tilt)
+                .map_err(crate::Error::from),
+        }
+    }
+
+    /// Moves the stylus away from the surface, lifting it first if it touches the surface.
+    ///
+    /// See [`stylus`](Self::stylus).
+    #[inline]
+    pub fn stylus_leave(&self) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => unsupported(sim.stylus_leave(), "stylus inputs"),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.stylus_leave().map_err(crate::Error::from),
+        }
+    }
+
+    /// Replays a pen stroke made of timed `samples`.
+    ///
+    /// Each sample is sent once its time has elapsed since the start of the stroke, or
+    /// immediately if an earlier sample was late, and the stylus is moved away from the surface
+    /// after the last one. This function blocks until the stroke completes.
+    ///
+    /// Once the stroke has started, the stylus is moved away even if one of the samples fails,
+    /// in which case the error of that sample is returned. See [`stylus`](Self::stylus) for the
+    /// backends that support stylus inputs.
+    pub fn stroke(&self, tool: StylusTool, samples: &[StylusSample]) -> Result<(), crate::Error> {
+        let start = Instant::now();
+
+        let result = samples.iter().try_for_each(|sample| {
+            std::thread::sleep(sample.time.saturating_sub(start.elapsed()));
+            self.stylus(tool, sample)
+        });
+        let left = self.stylus_leave();
+        result.and(left)
+    }
+
+    /// Creates a virtual gamepad, identified by `options`.
+    ///
+    /// The gamepad is independent from the keyboard and mouse of the simulator: several gamepads
+    /// can be created, and each one is removed from the system when dropped. Note that it might
+    /// take a few milliseconds before games notice a new gamepad.
+    ///
+    /// Only the `uinput` backend supports gamepads, through a virtual device that reports the
+    /// buttons and axes of an Xbox 360 controller. The other backends return an error (see
+    /// [`InputKinds::gamepad`](crate::InputKinds::gamepad)).
+    pub fn create_gamepad(&self, options: &GamepadOptions) -> Result<Gamepad, crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => {
+                unsupported(sim.create_gamepad(options), "gamepads").map(Gamepad::from_platform)
+            }
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => Ok(Gamepad::recording(rec)),
+        }
+    }
+
+    /// Returns the monitors that make up the desktop.
+    ///
+    /// The monitors are expressed in the coordinate space of
+    /// [`move_mouse_to`](Self::move_mouse_to), whose origin depends on the backend:
+    ///
+    /// - With the X11 backends, it is the top-left corner of the root window of the screen
+    ///   selected by the name of the display. The monitors are read from the "RANDR" extension,
+    ///   or the whole screen is returned as a single monitor if the X server does not support it.
+    /// - On Windows, it is the top-left corner of the primary monitor, and the other monitors
+    ///   may have negative positions.
+    /// - With the Wayland and portal backends, it is the origin of the compositor's layout. The
+    ///   portal backend only returns the monitors shared through the screen cast portal.
+    /// - With the `uinput` backend, the whole desktop is returned as a single monitor at the
+    ///   origin, provided that its size is known.
+    #[inline]
+    pub fn screens(&self) -> Result<Vec<Screen>, crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.screens().map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.screens().map_err(crate::Error::from),
+        }
+    }
+
+    /// Clicks `button` `count` times, as described by `options`.
+    ///
+    /// Unless an explicit interval is set in `options`, consecutive clicks are spaced by a fraction
+    /// of the double-click time of the desktop, so that they are recognized as a single double-
+    /// or triple-click. On X11, the double-click time is read from the `Net/DoubleClickTime`
+    /// XSETTINGS setting, when a settings manager is running. On Windows, it is the value returned
+    /// by `GetDoubleClickTime`. Elsewhere, 400 milliseconds are assumed.
+    ///
+    /// This function blocks until the last click is released. No further clicks are sent after an
+    /// error, but once pressed, the button is released again if its first release fails. A
+    /// `count` of `0` does nothing.
+    pub fn click(
+        &self,
+        button: Button,
+        count: u32,
+        options: &ClickOptions,
+    ) -> Result<(), crate::Error> {
+        let gap = options.gap(
+            self.double_click_time()
+                .unwrap_or(crate::click::DEFAULT_DOUBLE_CLICK_TIME),
+        );
+
+        for i in 0..count {
+            if i != 0 {
+                std::thread::sleep(gap);
+            }
+
+            self.press_button(button)?;
+            std::thread::sleep(options.hold);
+
+            if let Err(e) = self.release_button(button) {
+                // Give the button another chance not to remain held.
+                let _ = self.release_button(button);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the double-click time of the desktop, if it is known.
+    fn double_click_time(&self) -> Option<Duration> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.double_click_time(),
+            #[cfg(feature = "mock")]
+            Inner::Recording(_) => None,
+        }
+    }
+
+    /// Returns the position of the mouse pointer, if it is known.
+    ///
+    /// The position is expressed in the same coordinate space as the one of
+    /// [`move_mouse_to`](Self::move_mouse_to) and [`screens`](Self::screens).
+    ///
+    /// The X11 backends query the position of the pointer on the screen selected by the name of
+    /// the display, and return `None` when the pointer is on another screen. On Windows, the
+    /// position of the cursor on the virtual screen is returned. The Wayland, portal and
+    /// `uinput` backends cannot query it, and return the position the pointer was last moved to
+    /// by [`move_mouse_to`](Self::move_mouse_to), or `None` if it has been moved by a relative
+    /// motion since.
+    #[inline]
+    pub fn mouse_position(&self) -> Result<Option<(i32, i32)>, crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.mouse_position().map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.mouse_position().map_err(crate::Error::from),
+        }
+    }
+
+    /// Scrolls the mouse wheel by `dx` horizontal and `dy` vertical clicks.
+    ///
+    /// Positive values scroll down and to the right, negative values scroll up and to the left,
+    /// like the coordinates of the pointer. The distance covered by a single click is decided by
+    /// the application that receives it, usually a few lines of text.
+    ///
+    /// On X11, each click is sent as a press and a release of the buttons `4` to `7`.
+    #[inline]
+    pub fn scroll(&self, dx: i32, dy: i32) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.scroll(dx, dy).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.scroll(dx, dy).map_err(crate::Error::from),
+        }
+    }
+
+    /// Scrolls the mouse wheel by `dx` horizontal and `dy` vertical clicks, which may be
+    /// fractional.
+    ///
+    /// Unlike [`scroll`](Self::scroll), applications that support smooth scrolling scroll by the
+    /// exact distance rather than by whole steps. The directions are the same as the ones of
+    /// [`scroll`](Self::scroll).
+    ///
+    /// The backends that cannot send fractions of a wheel click (see
+    /// [`InputKinds::smooth_scroll`](crate::InputKinds::smooth_scroll)) fall back to whole
+    /// wheel clicks. The fractional part of the distance is remembered and added to the next
+    /// call, so that many small scrolls eventually amount to a click. The X11 backend uses the
+    /// scroll valuators of the "XTEST" pointer device when the X server provides them. Deltas
+    /// that are not finite are ignored.
+    #[inline]
+    pub fn scroll_smooth(&self, dx: f64, dy: f64) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.scroll_smooth(dx, dy).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.scroll_smooth(dx, dy).map_err(crate::Error::from),
+        }
+    }
+
+    /// Moves the mouse pointer by (`dx`, `dy`) pixels, relative to its current position.
+    ///
+    /// Unlike [`move_mouse_to`](Self::move_mouse_to), this is noticed by the applications that
+    /// grab the pointer and only respond to relative motions, such as games and 3D viewports.
+    ///
+    /// Most backends can only move the pointer by whole pixels. The fractional part of the
+    /// motion is remembered and added to the next call, meaning that moving the pointer by `0.25`
+    /// pixels four times moves it by exactly one pixel. Deltas that are not finite are ignored.
+    ///
+    /// # Pointer acceleration
+    ///
+    /// Relative motions are processed like the ones of a physical mouse: the pointer speed and
+    /// acceleration settings of the user (or the ones of the application that grabbed the
+    /// pointer) apply to them. As a consequence, the pointer generally does not move by exactly
+    /// (`dx`, `dy`) pixels, and a single large motion does not move it as far as many small
+    /// ones that add up to the same distance. Use [`move_mouse_to`](Self::move_mouse_to) when
+    /// the final position matters, or disable acceleration for the virtual device (for example
+    /// with a flat acceleration profile in `libinput`).
+    #[inline]
+    pub fn move_mouse_by(&self, dx: f64, dy: f64) -> Result<(), crate::Error> {
+        match &self.0 {
+            Inner::Platform(sim) => sim.move_mouse_by(dx, dy).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.move_mouse_by(dx, dy).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a unicode code-point to the top-level window.
     #[inline]
     pub fn send_char(&self, c: char) -> Result<(), crate::Error> {
-        self.0.send_char(c).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.send_char(c).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.send_char(c).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a bunch of unicode code-points to the top-level window.
     #[inline]
     pub fn send_chars(&self, it: impl IntoIterator<Item = char>) -> Result<(), crate::Error> {
-        self.0.send_chars(it.into_iter()).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.send_chars(it.into_iter()).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.send_chars(it.into_iter()).map_err(crate::Error::from),
+        }
     }
 
     /// Sends a string to the top-level window.
+    ///
+    /// With the `x11` backend, the characters that the keyboard layout cannot produce are bound
+    /// to unused keycodes while they are typed. The keyboard mapping of the X server is restored
+    /// before this function returns.
     #[inline]
     pub fn send_str(&self, s: &str) -> Result<(), crate::Error> {
-        self.0.send_str(s).map_err(crate::Error)
+        match &self.0 {
+            Inner::Platform(sim) => sim.send_str(s).map_err(crate::Error::from),
+            #[cfg(feature = "mock")]
+            Inner::Recording(rec) => rec.send_str(s).map_err(crate::Error::from),
+        }
+    }
+}
+
+/// Converts the result of a platform function that returns `None` when the backend cannot
+/// simulate `inputs`.
+fn unsupported<T, E>(result: Option<Result<T, E>>, inputs: &'static str) -> Result<T, crate::Error>
+where
+    crate::Error: From<E>,
+{
+    match result {
+        Some(result) => result.map_err(crate::Error::from),
+        None => Err(crate::Error(ErrorKind::Unsupported(inputs))),
     }
 }
@@ -0,0 +1,90 @@
+//! Describes the pen strokes replayed with [`Simulator::stroke`](crate::Simulator::stroke).
+
+use std::time::Duration;
+
+/// The pressure level reported when the stylus is pressed as hard as possible.
+pub(crate) const MAX_PRESSURE: u16 = 4095;
+
+/// The greatest tilt of the stylus, in degrees.
+pub(crate) const MAX_TILT: i8 = 90;
+
+/// The end of the stylus that is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StylusTool {
+    /// The tip of the stylus.
+    Pen,
+    /// The eraser at the back of the stylus.
+    Eraser,
+}
+
+/// The state of a stylus at a given point of a stroke.
+///
+/// Positions are expressed in the coordinate space used by
+/// [`Simulator::move_mouse_to`](crate::Simulator::move_mouse_to), in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StylusSample {
+    /// The time elapsed between the start of the stroke and the sample.
+    ///
+    /// This is ignored by [`Simulator::stylus`](crate::Simulator::stylus).
+    pub time: Duration,
+    /// The horizontal position of the stylus.
+    pub x: i32,
+    /// The vertical position of the stylus.
+    pub y: i32,
+    /// How hard the stylus is pressed against the surface, from `0.0` to `1.0`.
+    ///
+    /// A pressure of `0.0` means that the stylus hovers above the surface without touching it.
+    pub pressure: f64,
+    /// The angle between the stylus and the vertical axis, along the horizontal axis, in degrees.
+    ///
+    /// The angle ranges from `-90.0` to `90.0`, and is positive when the top of the stylus leans
+    /// to the right.
+    pub tilt_x: f64,
+    /// The angle between the stylus and the vertical axis, along the vertical axis, in degrees.
+    ///
+    /// The angle ranges from `-90.0` to `90.0`, and is positive when the top of the stylus leans
+    /// towards the user.
+    pub tilt_y: f64,
+}
+
+impl StylusSample {
+    /// Creates a new [`StylusSample`] at the position (`x`, `y`), `time` after the start of the
+    /// stroke.
+    ///
+    /// The stylus is pressed as hard as possible, and is not tilted.
+    pub const fn new(time: Duration, x: i32, y: i32) -> Self {
+        Self {
+            time,
+            x,
+            y,
+            pressure: 1.0,
+            tilt_x: 0.0,
+            tilt_y: 0.0,
+        }
+    }
+
+    /// Sets how hard the stylus is pressed against the surface, from `0.0` to `1.0`.
+    pub const fn pressure(mut self, pressure: f64) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Sets the tilt of the stylus, in degrees.
+    pub const fn tilt(mut self, tilt_x: f64, tilt_y: f64) -> Self {
+        self.tilt_x = tilt_x;
+        self.tilt_y = tilt_y;
+        self
+    }
+
+    /// Returns the pressure of the sample, from `0` to [`MAX_PRESSURE`].
+    pub(crate) fn pressure_level(&self) -> u16 {
+        // Casting saturates, and turns NaN into zero.
+        (self.pressure.clamp(0.0, 1.0) * MAX_PRESSURE as f64).round() as u16
+    }
+
+    /// Returns the tilt of the sample, in whole degrees.
+    pub(crate) fn tilt_degrees(&self) -> (i8, i8) {
+        let degrees = |v: f64| (v.round() as i8).clamp(-MAX_TILT, MAX_TILT);
+        (degrees(self.tilt_x), degrees(self.tilt_y))
+    }
+}
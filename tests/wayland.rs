@@ -0,0 +1,143 @@
+//! Runs the Wayland backend against a headless `sway` compositor.
+//!
+//! This test needs `sway` to be installed, and is ignored by default. Run it with
+//! `cargo test --features wayland --test wayland -- --ignored`.
+
+#![cfg(all(target_os = "linux", feature = "wayland"))]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use keysim::{Backend, Button, Key, SimulatorBuilder};
+
+/// A headless `sway` instance, killed when dropped.
+struct Compositor {
+    process: Child,
+    /// The runtime directory of the compositor, which holds its socket.
+    runtime_dir: PathBuf,
+}
+
+impl Compositor {
+    /// Starts `sway` with its headless backend, and waits for its socket to appear.
+    fn start() -> Self {
+        let runtime_dir =
+            std::env::temp_dir().join(format!("keysim-wayland-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&runtime_dir);
+        fs::create_dir_all(&runtime_dir).unwrap();
+        fs::set_permissions(&runtime_dir, fs::Permissions::from_mode(0o700)).unwrap();
+
+        let config = runtime_dir.join("config");
+        fs::write(
+            &config,
+            "output HEADLESS-1 resolution 1280x720 position 0 0\n",
+        )
+        .unwrap();
+
+        let process = Command::new("sway")
+            .arg("--config")
+            .arg(&config)
+            .env("XDG_RUNTIME_DIR", &runtime_dir)
+            .env("WLR_BACKENDS", "headless")
+            .env("WLR_RENDERER", "pixman")
+            .env("WLR_LIBINPUT_NO_DEVICES", "1")
+            .env_remove("WAYLAND_DISPLAY")
+            .env_remove("DISPLAY")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start `sway`, which this test needs");
+
+        let mut ret = Self {
+            process,
+            runtime_dir,
+        };
+        ret.wait_for_socket();
+        ret
+    }
+
+    /// Waits until the compositor has created its socket, and points the environment at it.
+    fn wait_for_socket(&mut self) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        loop {
+            if let Some(socket) = find_socket(&self.runtime_dir) {
+                std::env::set_var("XDG_RUNTIME_DIR", &self.runtime_dir);
+                std::env::set_var("WAYLAND_DISPLAY", socket);
+                return;
+            }
+
+            if let Some(status) = self.process.try_wait().unwrap() {
+                panic!("`sway` exited before creating its socket: {status}");
+            }
+            assert!(
+                Instant::now() < deadline,
+                "`sway` did not create its socket"
+            );
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Returns whether the compositor is still running.
+    fn is_running(&mut self) -> bool {
+        self.process.try_wait().unwrap().is_none()
+    }
+}
+
+impl Drop for Compositor {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = fs::remove_dir_all(&self.runtime_dir);
+    }
+}
+
+/// Returns the name of the Wayland socket in `dir`, if any.
+fn find_socket(dir: &Path) -> Option<String> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .find(|name| name.starts_with("wayland-") && !name.ends_with(".lock"))
+}
+
+#[test]
+#[ignore = "needs `sway`, run with `--ignored`"]
+fn headless_sway() {
+    let mut compositor = Compositor::start();
+
+    let sim = SimulatorBuilder::new()
+        .backend(Backend::Wayland)
+        .env_override(false)
+        .build()
+        .unwrap();
+    assert_eq!(sim.capabilities().backend, "wayland");
+
+    let screens = sim.screens().unwrap();
+    assert_eq!(screens.len(), 1);
+    assert_eq!((screens[0].width, screens[0].height), (1280, 720));
+
+    // Typing uploads new keymaps, which the compositor must accept.
+    sim.send_str("Hello, world!").unwrap();
+    sim.send_str("àéîõü €ω 😀").unwrap();
+    sim.press_key(Key::LeftShift).unwrap();
+    sim.send_key(Key::A).unwrap();
+    sim.release_key(Key::LeftShift).unwrap();
+
+    sim.move_mouse_to(100, 200).unwrap();
+    assert_eq!(sim.mouse_position().unwrap(), Some((100, 200)));
+    sim.send_button(Button::Left).unwrap();
+    sim.scroll(0, 2).unwrap();
+    sim.move_mouse_by(5.0, 5.0).unwrap();
+    assert_eq!(sim.mouse_position().unwrap(), None);
+
+    // A rejected request makes the compositor close the connection, which only shows up on the
+    // next requests.
+    std::thread::sleep(Duration::from_millis(200));
+    sim.send_key(Key::Space).unwrap();
+    sim.send_button(Button::Right).unwrap();
+
+    drop(sim);
+    assert!(compositor.is_running(), "`sway` exited while being used");
+}